@@ -1,18 +1,28 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::config::{Config, ConfigEvent, poll_config_events};
 use crate::core::user_notice::{UserNotice, UserNoticeLevel, UserNoticeQueue};
 use crate::core::{
-    ColorTheme, ColorThemeId, Entry, SessionEvent, SessionTab, ShellCommandError,
-    ShellCommandRequest, ShellEvent, ShellExecutionResult, ShellPermission, ShellWorker,
-    SlashCommand, SlashCommandError, list_entries, load_session_tabs, parse_slash_command,
-    poll_session_events, save_session_async,
+    Bookmarks, BulkRenamePlan, ColorTheme, ColorThemeId, ConflictAction, ConflictChoice,
+    ConflictOverride, ConflictPolicy, CopyEvent, CopyProgress, CopyRequest, CopyWorker, Entry,
+    EntryFilter, EntryMetadata, FileOpError, FindEvent, FindRequest, FinderWorker, Frecency,
+    FrecencyEvent, FrecencyWorker, FsWatcher, GitIgnore, GrepEvent, GrepRequest, GrepWorker,
+    HistoryEntry, Journal, JournalError, JournalOp, JournalPath, ListingEvent, ListingRequest,
+    ListingWorker, Regex, SORT_FLAGS, SearchMatcher, SearchMode, SessionEvent, SessionTab,
+    ShellCommandError, ShellCommandRequest, ShellEvent, ShellExecutionResult, ShellPermission,
+    ShellWorker, SlashCommand, SlashCommandError, SortKey, SortMode, TrashStore, TrashedItem,
+    TreeEvent, TreeRequest, TreeWorker, WatchEvent, apply_bulk_rename, build_bulk_rename_buffer,
+    entry_metadata, expand_path, glob_match, list_entries, list_entries_sorted, load_session_tabs,
+    move_entry, move_replacing, parse_slash_command, plan_bulk_rename, poll_session_events,
+    remove_path, rename_entry, rename_path, resolve_conflict, resolve_move_target,
+    save_session_async,
 };
 use crate::error::{AppError, AppResult};
 use crate::tabs::{TabSummary, TabsEvent, TabsState};
+use uuid::Uuid;
 
 pub trait EntryOpener {
     fn open(&self, path: &Path) -> AppResult<()>;
@@ -66,6 +76,7 @@ pub struct App {
     pub parent_entries: Vec<Entry>,
     pub cursor: Option<usize>,
     pub show_hidden: bool,
+    pub hide_ignored: bool,
     clock: Arc<dyn AppClock>,
     tabs: TabsState,
     tab_color_changed: Option<TabColorChanged>,
@@ -85,12 +96,60 @@ pub struct App {
     shell_worker: ShellWorker,
     shell_output_view: ShellOutputView,
     shell_output_active: bool,
+    copy_worker: CopyWorker,
+    copy_progress: Option<CopyProgress>,
+    copy_request_id: u64,
+    finder_worker: FinderWorker,
+    grep_worker: GrepWorker,
+    results_request_id: u64,
+    results_view: Option<ResultsView>,
+    preview_focus: Option<(PathBuf, usize)>,
+    trash_store: Option<TrashStore>,
+    trash_view: Option<TrashView>,
+    pending_confirmation: Option<PendingConfirmation>,
+    visual_range: Option<VisualRange>,
+    bulk_rename: Option<BulkRenameSession>,
+    journal: Journal,
+    bookmarks: Bookmarks,
+    frecency: Frecency,
+    frecency_worker: FrecencyWorker,
+    frecency_save_deadline: Option<Instant>,
+    tree_worker: TreeWorker,
+    tree_roots: Vec<Entry>,
+    tree_children: HashMap<PathBuf, Vec<Entry>>,
+    tree_pending: BTreeSet<PathBuf>,
+    tree_focus: Option<String>,
+    fs_watcher: Option<FsWatcher>,
+    watched_dir: Option<PathBuf>,
+    watch_refresh_pending: bool,
+    fs_changed: bool,
+    listing_worker: ListingWorker,
+    listing_request_id: u64,
+    listing_budget: Duration,
+    listing: Option<PendingListing>,
+    parent_listing_worker: ListingWorker,
+    parent_listing: Option<PendingListing>,
+    conflict_policy: ConflictPolicy,
+    search_mode: SearchMode,
+    pending_conflicts: Option<PendingConflicts>,
+    register: Option<Register>,
+    clipboard_request: Option<String>,
     session_save_pending: bool,
     session_save_deadline: Option<Instant>,
 }
 
+const REGISTER_HINT_NAMES: usize = 3;
+const CLIPBOARD_MAX_BYTES: u64 = 256 * 1024;
+const SEARCH_MAX_DEPTH: usize = 16;
+const GREP_MAX_FILE_BYTES: u64 = 1024 * 1024;
+const PATH_COMPLETION_COMMANDS: [&str; 4] = ["/shell", "/mkdir", "/touch", "/cwd"];
+const MAX_Z_CANDIDATES: usize = 8;
+const BOOKMARK_COMPLETION_COMMANDS: [&str; 2] = ["/go ", "/bookmark rm "];
 const SLASH_FEEDBACK_TTL: Duration = Duration::from_secs(4);
 const SESSION_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+const FRECENCY_SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+const LISTING_SYNC_BUDGET: Duration = Duration::from_millis(100);
+const LISTING_TIMEOUT: Duration = Duration::from_secs(10);
 
 impl App {
     #[allow(dead_code)]
@@ -128,6 +187,7 @@ impl App {
             parent_entries,
             cursor,
             show_hidden,
+            hide_ignored: false,
             clock,
             tabs,
             tab_color_changed: None,
@@ -144,9 +204,48 @@ impl App {
             slash_history_index: None,
             shell_permission: ShellPermission::from_env(config.allow_shell),
             allow_opener: config.allow_opener,
+            conflict_policy: config.conflict_policy,
+            search_mode: config.search_mode,
+            pending_conflicts: None,
+            register: None,
+            clipboard_request: None,
             shell_worker: ShellWorker::new(),
             shell_output_view: ShellOutputView::new(),
             shell_output_active: false,
+            copy_worker: CopyWorker::new(),
+            copy_progress: None,
+            copy_request_id: 0,
+            finder_worker: FinderWorker::new(),
+            grep_worker: GrepWorker::new(),
+            results_request_id: 0,
+            results_view: None,
+            preview_focus: None,
+            trash_store: TrashStore::from_env(),
+            trash_view: None,
+            pending_confirmation: None,
+            visual_range: None,
+            bulk_rename: None,
+            journal: Journal::from_env(),
+            bookmarks: Bookmarks::from_env(),
+            frecency: Frecency::from_env(),
+            frecency_worker: FrecencyWorker::new(),
+            frecency_save_deadline: None,
+            tree_worker: TreeWorker::new(),
+            tree_roots: Vec::new(),
+            tree_children: HashMap::new(),
+            tree_pending: BTreeSet::new(),
+            tree_focus: None,
+            fs_watcher: (config.watch_interval_ms > 0)
+                .then(|| FsWatcher::new(Duration::from_millis(config.watch_interval_ms))),
+            watched_dir: None,
+            watch_refresh_pending: false,
+            fs_changed: false,
+            listing_worker: ListingWorker::new(),
+            listing_request_id: 0,
+            listing_budget: LISTING_SYNC_BUDGET,
+            listing: None,
+            parent_listing_worker: ListingWorker::new(),
+            parent_listing: None,
             session_save_pending: false,
             session_save_deadline: None,
         }
@@ -168,6 +267,7 @@ impl App {
             parent_entries,
             cursor,
             show_hidden,
+            hide_ignored: false,
             clock,
             tabs,
             tab_color_changed: None,
@@ -184,9 +284,48 @@ impl App {
             slash_history_index: None,
             shell_permission: ShellPermission::from_env(config.allow_shell),
             allow_opener: config.allow_opener,
+            conflict_policy: config.conflict_policy,
+            search_mode: config.search_mode,
+            pending_conflicts: None,
+            register: None,
+            clipboard_request: None,
             shell_worker: ShellWorker::new(),
             shell_output_view: ShellOutputView::new(),
             shell_output_active: false,
+            copy_worker: CopyWorker::new(),
+            copy_progress: None,
+            copy_request_id: 0,
+            finder_worker: FinderWorker::new(),
+            grep_worker: GrepWorker::new(),
+            results_request_id: 0,
+            results_view: None,
+            preview_focus: None,
+            trash_store: TrashStore::from_env(),
+            trash_view: None,
+            pending_confirmation: None,
+            visual_range: None,
+            bulk_rename: None,
+            journal: Journal::from_env(),
+            bookmarks: Bookmarks::from_env(),
+            frecency: Frecency::from_env(),
+            frecency_worker: FrecencyWorker::new(),
+            frecency_save_deadline: None,
+            tree_worker: TreeWorker::new(),
+            tree_roots: Vec::new(),
+            tree_children: HashMap::new(),
+            tree_pending: BTreeSet::new(),
+            tree_focus: None,
+            fs_watcher: (config.watch_interval_ms > 0)
+                .then(|| FsWatcher::new(Duration::from_millis(config.watch_interval_ms))),
+            watched_dir: None,
+            watch_refresh_pending: false,
+            fs_changed: false,
+            listing_worker: ListingWorker::new(),
+            listing_request_id: 0,
+            listing_budget: LISTING_SYNC_BUDGET,
+            listing: None,
+            parent_listing_worker: ListingWorker::new(),
+            parent_listing: None,
             session_save_pending: false,
             session_save_deadline: None,
         }
@@ -222,8 +361,9 @@ impl App {
                 restored_dir,
             )
         };
-        let entries = list_entries(&restored_dir, show_hidden)?;
-        let parent_entries = list_parent_entries(&restored_dir, show_hidden)?;
+        let sort = tabs.active_sort();
+        let entries = list_entries_sorted(&restored_dir, show_hidden, &sort)?;
+        let parent_entries = list_parent_entries(&restored_dir, show_hidden, &sort)?;
         let cursor = if entries.is_empty() { None } else { Some(0) };
         Ok(Self::new_with_tabs(
             restored_dir,
@@ -314,6 +454,34 @@ impl App {
         self.refresh()
     }
 
+    pub fn history_back(&mut self) -> AppResult<()> {
+        let current = self.history_entry();
+        match self.tabs.history_back(current) {
+            Some(entry) => self.restore_history_entry(entry),
+            None => Ok(()),
+        }
+    }
+
+    pub fn history_forward(&mut self) -> AppResult<()> {
+        let current = self.history_entry();
+        match self.tabs.history_forward(current) {
+            Some(entry) => self.restore_history_entry(entry),
+            None => Ok(()),
+        }
+    }
+
+    fn history_entry(&self) -> HistoryEntry {
+        HistoryEntry {
+            path: self.current_dir.clone(),
+            focus: self.selected_entry().map(|entry| entry.name.clone()),
+        }
+    }
+
+    fn restore_history_entry(&mut self, entry: HistoryEntry) -> AppResult<()> {
+        self.enter_dir(entry.path);
+        self.refresh_with_selection(entry.focus.as_deref())
+    }
+
     pub fn move_to_parent(&mut self) -> AppResult<()> {
         let Some(parent) = self.current_dir.parent() else {
             return Ok(());
@@ -340,1675 +508,6005 @@ impl App {
         let selected_index = self.cursor;
         self.show_hidden = !self.show_hidden;
         self.reload_entries()?;
-        self.cursor = resolve_cursor(&self.entries, selected_name.as_deref(), selected_index);
+        self.restore_cursor(selected_name.as_deref(), selected_index);
         Ok(())
     }
 
-    pub fn search_text(&self) -> &str {
-        &self.search_buffer
-    }
-
-    pub fn slash_input_active(&self) -> bool {
-        self.slash_input_active
-    }
-
-    pub fn slash_input_text(&self) -> &str {
-        &self.slash_input_buffer
-    }
-
-    pub fn slash_feedback(&self) -> Option<&SlashFeedback> {
-        let feedback = self.slash_feedback.as_ref()?;
-        if feedback.is_expired_with(self.clock.as_ref()) {
-            return None;
-        }
-        Some(feedback)
+    pub fn toggle_ignored(&mut self) -> AppResult<()> {
+        let selected_name = self.selected_entry().map(|entry| entry.name.clone());
+        let selected_index = self.cursor;
+        self.hide_ignored = !self.hide_ignored;
+        self.reload_entries()?;
+        self.restore_cursor(selected_name.as_deref(), selected_index);
+        Ok(())
     }
 
-    fn push_user_notice(&mut self, notice: UserNotice) {
-        let _ = self.user_notice_queue.push(notice, self.clock.now());
+    pub fn is_marked(&self, name: &str) -> bool {
+        self.tabs
+            .active_marks()
+            .is_some_and(|marks| marks.contains(name))
     }
 
-    pub fn user_notice(&mut self) -> Option<UserNotice> {
-        self.user_notice_queue.current(self.clock.now()).cloned()
+    pub fn marked_names(&self) -> Option<&BTreeSet<String>> {
+        self.tabs.active_marks()
     }
 
-    pub fn shell_output_active(&self) -> bool {
-        self.shell_output_active
+    pub fn mark_count(&self) -> usize {
+        self.tabs.active_marks().map(BTreeSet::len).unwrap_or(0)
     }
 
-    pub fn shell_output_text(&self, height: usize, width: usize) -> Option<String> {
-        if self.shell_output_view.is_empty() {
-            None
-        } else {
-            Some(self.shell_output_view.view_text(height, width))
-        }
+    pub fn tree_enabled(&self) -> bool {
+        self.tabs.active_tree().is_some_and(|tree| tree.enabled)
     }
 
-    pub fn flush_session_save(&mut self) {
-        if !self.session_save_pending {
+    pub fn expand_selected(&mut self) {
+        let Some((index, entry)) = self.cursor.zip(self.selected_entry().cloned()) else {
+            return;
+        };
+        if !entry.is_dir {
             return;
         }
-        let Some(deadline) = self.session_save_deadline else {
+        let path = self.current_dir.join(&entry.name);
+        let Some(tree) = self.tabs.active_tree_mut() else {
             return;
         };
-        if self.clock.now() < deadline {
+        if !tree.expanded.insert(path.clone()) {
+            let child_prefix = format!("{}/", entry.name);
+            if self
+                .entries
+                .get(index + 1)
+                .is_some_and(|next| next.name.starts_with(&child_prefix))
+            {
+                self.cursor = Some(index + 1);
+            }
             return;
         }
-        self.session_save_pending = false;
-        self.session_save_deadline = None;
-        let tabs = self.tabs.session_tabs();
-        save_session_async(tabs);
+        self.request_tree_children(path);
+        self.rebuild_tree();
     }
 
-    pub fn force_session_save(&mut self) {
-        self.session_save_pending = false;
-        self.session_save_deadline = None;
-        let tabs = self.tabs.session_tabs();
-        save_session_async(tabs);
+    pub fn collapse_selected(&mut self) -> AppResult<()> {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return self.move_to_parent();
+        };
+        let path = self.current_dir.join(&entry.name);
+        let parent_name = entry
+            .name
+            .rsplit_once('/')
+            .map(|(parent, _)| parent.to_string());
+        let Some(tree) = self.tabs.active_tree_mut() else {
+            return Ok(());
+        };
+        if entry.is_dir && tree.expanded.remove(&path) {
+            self.rebuild_tree();
+            return Ok(());
+        }
+        let Some(parent_name) = parent_name else {
+            return self.move_to_parent();
+        };
+        tree.expanded.remove(&self.current_dir.join(&parent_name));
+        self.rebuild_tree();
+        self.cursor = resolve_cursor(&self.entries, Some(&parent_name), self.cursor);
+        Ok(())
     }
 
-    pub fn page_up_shell_output(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.page_up();
+    pub fn poll_tree_events(&mut self) {
+        let mut changed = false;
+        while let Some(event) = self.tree_worker.poll() {
+            self.handle_tree_event(event);
+            changed = true;
+        }
+        if changed && self.tree_enabled() {
+            self.rebuild_tree();
         }
     }
 
-    pub fn page_down_shell_output(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.page_down();
+    fn handle_tree_event(&mut self, TreeEvent { dir, result }: TreeEvent) {
+        self.tree_pending.remove(&dir);
+        match result {
+            Ok(children) => {
+                self.tree_children.insert(dir, children);
+            }
+            Err(error) => {
+                self.tree_children.remove(&dir);
+                if let Some(tree) = self.tabs.active_tree_mut() {
+                    tree.expanded.remove(&dir);
+                }
+                self.slash_feedback = Some(self.timed_feedback(
+                    format!("tree: {}: {error}", dir.display()),
+                    FeedbackStatus::Error,
+                ));
+            }
         }
     }
 
-    pub fn home_shell_output(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.home();
+    fn request_expanded_children(&mut self) {
+        let Some(tree) = self.tabs.active_tree() else {
+            return;
+        };
+        let dirs = tree
+            .expanded
+            .iter()
+            .filter(|dir| dir.starts_with(&self.current_dir))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.tree_children
+            .retain(|dir, _| dir.starts_with(&self.current_dir));
+        for dir in dirs {
+            self.request_tree_children(dir);
         }
     }
 
-    pub fn end_shell_output(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.end();
+    fn request_tree_children(&mut self, dir: PathBuf) {
+        if !self.tree_pending.insert(dir.clone()) {
+            return;
         }
+        self.tree_worker.request(TreeRequest {
+            dir,
+            show_hidden: self.show_hidden,
+            hide_ignored: self.hide_ignored,
+            sort: self.tabs.active_sort(),
+        });
     }
 
-    pub fn scroll_shell_output_left(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.scroll_left();
+    fn rebuild_tree(&mut self) {
+        let selected_name = self.selected_entry().map(|entry| entry.name.clone());
+        let selected_index = self.cursor;
+        self.entries = self.flatten_tree();
+        let focus = self
+            .tree_focus
+            .take_if(|focus| self.entries.iter().any(|entry| entry.name == *focus))
+            .or(selected_name);
+        if self.tree_pending.is_empty() {
+            self.tree_focus = None;
+        }
+        self.cursor = resolve_cursor(&self.entries, focus.as_deref(), selected_index);
+        self.prune_marks();
+    }
+
+    fn flatten_tree(&self) -> Vec<Entry> {
+        let mut rows = Vec::new();
+        self.push_tree_rows(&self.tree_roots, "", &mut rows);
+        rows
+    }
+
+    fn push_tree_rows(&self, level: &[Entry], prefix: &str, rows: &mut Vec<Entry>) {
+        let expanded = self.tabs.active_tree().map(|tree| &tree.expanded);
+        for entry in level {
+            let name = format!("{prefix}{}", entry.name);
+            let path = self.current_dir.join(&name);
+            rows.push(Entry {
+                name: name.clone(),
+                ..entry.clone()
+            });
+            if !entry.is_dir || !expanded.is_some_and(|expanded| expanded.contains(&path)) {
+                continue;
+            }
+            let Some(children) = self.tree_children.get(&path) else {
+                continue;
+            };
+            let children = children
+                .iter()
+                .filter(|child| {
+                    self.tabs
+                        .active_filter()
+                        .is_none_or(|filter| filter.matches(&child.name))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            self.push_tree_rows(&children, &format!("{name}/"), rows);
         }
     }
 
-    pub fn scroll_shell_output_right(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.scroll_right();
+    fn handle_tree_command(&mut self, args: &[String]) -> SlashFeedback {
+        let enabled = match args {
+            [] => !self.tree_enabled(),
+            [arg] if arg == "on" => true,
+            [arg] if arg == "off" => false,
+            _ => {
+                return self
+                    .timed_feedback("tree: invalid args".to_string(), FeedbackStatus::Error);
+            }
+        };
+        let focus = self
+            .selected_entry()
+            .and_then(|entry| entry.name.split('/').next())
+            .map(str::to_string);
+        if let Some(tree) = self.tabs.active_tree_mut() {
+            tree.enabled = enabled;
         }
+        if let Err(error) = self.refresh_with_selection(focus.as_deref()) {
+            return self.timed_feedback(format!("tree: {error}"), FeedbackStatus::Error);
+        }
+        self.timed_feedback(
+            format!("tree: {}", if enabled { "on" } else { "off" }),
+            FeedbackStatus::Success,
+        )
     }
 
-    pub fn preview_visible(&self) -> bool {
-        self.preview_visible
+    pub fn filter_label(&self) -> Option<String> {
+        self.tabs.active_filter().map(EntryFilter::label)
     }
 
-    pub fn preview_ratio_percent(&self) -> u16 {
-        self.preview_ratio_percent
+    pub fn toggle_mark(&mut self) {
+        let Some(name) = self.selected_entry().map(|entry| entry.name.clone()) else {
+            return;
+        };
+        if let Some(marks) = self.tabs.active_marks_mut()
+            && !marks.remove(&name)
+        {
+            marks.insert(name);
+        }
+        self.visual_range = None;
+        self.move_cursor_down();
     }
 
-    pub fn tab_count(&self) -> usize {
-        self.tabs.count()
+    pub fn extend_selection_up(&mut self) {
+        self.extend_selection(Self::move_cursor_up);
     }
 
-    pub fn active_tab_number(&self) -> usize {
-        self.tabs.active_number()
+    pub fn extend_selection_down(&mut self) {
+        self.extend_selection(Self::move_cursor_down);
     }
 
-    pub fn active_theme(&self) -> ColorTheme {
-        self.tabs.active_theme_id().theme()
+    pub fn select_all(&mut self) {
+        let names = self
+            .entries
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect();
+        self.set_marks(names);
     }
 
-    pub fn take_tab_color_changed(&mut self) -> Option<TabColorChanged> {
-        self.tab_color_changed.take()
+    pub fn clear_marks(&mut self) {
+        self.set_marks(BTreeSet::new());
     }
 
-    pub fn new_tab(&mut self) -> AppResult<()> {
-        self.tabs.push_new(self.current_dir.as_path());
-        self.announce_active_tab_color();
-        self.clear_search_state();
-        self.handle_tab_events();
-        Ok(())
+    pub fn invert_marks(&mut self) {
+        let names = self
+            .entries
+            .iter()
+            .filter(|entry| !self.is_marked(&entry.name))
+            .map(|entry| entry.name.clone())
+            .collect();
+        self.set_marks(names);
     }
 
-    pub fn next_tab(&mut self) -> AppResult<()> {
-        let Some(next) = self.tabs.next_index() else {
-            return Ok(());
-        };
-        self.switch_to_tab(next)
+    pub fn select_glob(&mut self, pattern: &str) -> usize {
+        let matched = self
+            .entries
+            .iter()
+            .filter(|entry| glob_match(pattern, &entry.name))
+            .map(|entry| entry.name.clone())
+            .collect::<Vec<_>>();
+        let count = matched.len();
+        let mut marks = self.marked_names().cloned().unwrap_or_default();
+        marks.extend(matched);
+        self.set_marks(marks);
+        count
     }
 
-    pub fn prev_tab(&mut self) -> AppResult<()> {
-        let Some(prev) = self.tabs.prev_index() else {
-            return Ok(());
-        };
-        self.switch_to_tab(prev)
+    pub fn trash_selected(&mut self) {
+        self.slash_feedback = Some(self.trash_targets());
     }
 
-    pub fn activate_slash_input(&mut self) {
-        self.slash_input_active = true;
-        self.slash_input_buffer.clear();
-        self.slash_input_buffer.push('/');
-        self.slash_history_index = None;
+    pub fn request_permanent_delete(&mut self) {
+        let targets = self.action_targets();
+        if targets.is_empty() {
+            self.slash_feedback =
+                Some(self.timed_feedback("delete: no selection".to_string(), FeedbackStatus::Warn));
+            return;
+        }
+        self.pending_confirmation = Some(PendingConfirmation::PermanentDelete { paths: targets });
+    }
+
+    pub fn confirmation_prompt(&self) -> Option<String> {
+        match self.pending_confirmation.as_ref()? {
+            PendingConfirmation::PermanentDelete { paths } => {
+                let label = match paths.as_slice() {
+                    [path] => path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.display().to_string()),
+                    _ => format!("{} items", paths.len()),
+                };
+                Some(format!("delete {label} permanently? (y/N)"))
+            }
+            PendingConfirmation::BulkRename { plan } => {
+                Some(format!("rename {} entries? (y/N)", plan.renames.len()))
+            }
+            PendingConfirmation::DropCopy { paths } => {
+                Some(format!("copy {} items here? (y/N)", paths.len()))
+            }
+        }
     }
 
-    pub fn cancel_slash_input(&mut self) {
-        self.slash_input_active = false;
-        self.slash_input_buffer.clear();
-        self.slash_history_index = None;
+    pub fn bulk_rename_preview(&self) -> Option<Vec<String>> {
+        match self.pending_confirmation.as_ref()? {
+            PendingConfirmation::BulkRename { plan } => Some(plan.preview_lines()),
+            _ => None,
+        }
     }
 
-    pub fn append_slash_char(&mut self, ch: char) {
-        if !self.slash_input_active {
-            return;
+    pub fn drop_copy_preview(&self) -> Option<Vec<String>> {
+        match self.pending_confirmation.as_ref()? {
+            PendingConfirmation::DropCopy { paths } => Some(
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect(),
+            ),
+            _ => None,
         }
-        self.slash_input_buffer.push(ch);
-        self.slash_history_index = None;
     }
 
-    pub fn backspace_slash_char(&mut self) {
-        if !self.slash_input_active {
-            return;
-        }
-        if self.slash_input_buffer.len() <= 1 {
-            self.cancel_slash_input();
-            return;
-        }
-        self.slash_input_buffer.pop();
-        self.slash_history_index = None;
+    pub fn request_drop_copy(&mut self, paths: Vec<PathBuf>) {
+        let feedback = if paths.is_empty() {
+            self.timed_feedback("paste: no paths".to_string(), FeedbackStatus::Warn)
+        } else if let Some(missing) = paths.iter().find(|path| path.symlink_metadata().is_err()) {
+            self.timed_feedback(
+                format!("paste: not found: {}", missing.display()),
+                FeedbackStatus::Error,
+            )
+        } else {
+            let count = paths.len();
+            self.pending_confirmation = Some(PendingConfirmation::DropCopy { paths });
+            self.timed_feedback(
+                format!("paste: {count} dropped paths to review"),
+                FeedbackStatus::Warn,
+            )
+        };
+        self.slash_feedback = Some(feedback);
     }
 
-    pub fn submit_slash_command(&mut self) -> Option<SlashCommand> {
-        if !self.slash_input_active {
+    pub fn take_clipboard_request(&mut self) -> Option<String> {
+        self.clipboard_request.take()
+    }
+
+    pub fn take_editor_request(&mut self) -> Option<PathBuf> {
+        let session = self.bulk_rename.as_mut()?;
+        if session.launched {
             return None;
         }
-        let input_snapshot = self.slash_input_buffer.clone();
-        let command = parse_slash_command(&self.slash_input_buffer);
-        self.slash_input_active = false;
-        self.slash_input_buffer.clear();
-        self.slash_history_index = None;
-        match command {
-            Ok(command) => {
-                self.slash_history.push(input_snapshot);
-                self.slash_feedback = Some(self.handle_slash_command(&command));
-                Some(command)
+        session.launched = true;
+        Some(session.buffer_path.clone())
+    }
+
+    pub fn finish_bulk_rename_edit(&mut self, result: Result<(), String>) {
+        let Some(session) = self.bulk_rename.take() else {
+            return;
+        };
+        let edited = std::fs::read_to_string(&session.buffer_path);
+        let _ = std::fs::remove_file(&session.buffer_path);
+        let feedback = match (result, edited) {
+            (Err(message), _) => self.timed_feedback(
+                format!("bulkrename: editor failed: {message}"),
+                FeedbackStatus::Error,
+            ),
+            (Ok(()), Err(error)) => {
+                self.timed_feedback(format!("bulkrename: {error}"), FeedbackStatus::Error)
             }
-            Err(error) => {
-                self.slash_feedback = Some(self.timed_feedback(
-                    format!("slash error: {}", format_slash_error(error)),
-                    FeedbackStatus::Error,
-                ));
-                None
+            (Ok(()), Ok(edited)) => {
+                match plan_bulk_rename(&session.dir, &session.originals, &edited) {
+                    Ok(plan) if plan.is_empty() => self
+                        .timed_feedback("bulkrename: no changes".to_string(), FeedbackStatus::Warn),
+                    Ok(plan) => {
+                        let count = plan.renames.len();
+                        self.pending_confirmation = Some(PendingConfirmation::BulkRename { plan });
+                        self.timed_feedback(
+                            format!("bulkrename: {count} renames to review"),
+                            FeedbackStatus::Warn,
+                        )
+                    }
+                    Err(error) => {
+                        self.timed_feedback(format!("bulkrename: {error}"), FeedbackStatus::Error)
+                    }
+                }
             }
-        }
+        };
+        self.slash_feedback = Some(feedback);
     }
 
-    pub fn slash_history_prev(&mut self) {
-        if self.slash_history.is_empty() {
+    pub fn confirm_pending(&mut self) {
+        let Some(confirmation) = self.pending_confirmation.take() else {
             return;
-        }
-        let next_index = match self.slash_history_index {
-            None => self.slash_history.len().saturating_sub(1),
-            Some(index) => index.saturating_sub(1),
         };
-        self.slash_history_index = Some(next_index);
-        if let Some(entry) = self.slash_history.get(next_index) {
-            self.slash_input_buffer = entry.clone();
+        let feedback = match confirmation {
+            PendingConfirmation::PermanentDelete { paths } => self.delete_permanently(&paths),
+            PendingConfirmation::BulkRename { plan } => self.apply_bulk_rename_plan(&plan),
+            PendingConfirmation::DropCopy { paths } => {
+                self.begin_transfer(TransferKind::Paste, paths, self.current_dir.clone())
+            }
+        };
+        self.slash_feedback = Some(feedback);
+    }
+
+    pub fn cancel_pending(&mut self) {
+        if self.pending_confirmation.take().is_some() {
+            self.slash_feedback =
+                Some(self.timed_feedback("cancelled".to_string(), FeedbackStatus::Warn));
         }
     }
 
-    pub fn slash_history_next(&mut self) {
-        let Some(index) = self.slash_history_index else {
+    pub fn conflict_active(&self) -> bool {
+        self.pending_conflicts.is_some()
+    }
+
+    pub fn conflict_prompt(&self) -> Option<ConflictPrompt> {
+        let pending = self.pending_conflicts.as_ref()?;
+        let (source, target) = pending.queue.front()?;
+        Some(ConflictPrompt {
+            source: source.clone(),
+            target: target.clone(),
+            source_metadata: entry_metadata(source).ok(),
+            target_metadata: entry_metadata(target).ok(),
+            index: pending.total - pending.queue.len() + 1,
+            total: pending.total,
+            apply_to_all: pending.apply_to_all,
+        })
+    }
+
+    pub fn toggle_conflict_apply_to_all(&mut self) {
+        if let Some(pending) = self.pending_conflicts.as_mut() {
+            pending.apply_to_all = !pending.apply_to_all;
+        }
+    }
+
+    pub fn resolve_pending_conflict(&mut self, choice: ConflictChoice) {
+        let Some(pending) = self.pending_conflicts.as_mut() else {
             return;
         };
-        let next_index = index.saturating_add(1);
-        if next_index >= self.slash_history.len() {
-            self.slash_history_index = None;
-            self.slash_input_buffer = "/".to_string();
+        let count = if pending.apply_to_all {
+            pending.queue.len()
+        } else {
+            1
+        };
+        for _ in 0..count {
+            if let Some((source, target)) = pending.queue.pop_front() {
+                pending.decide(choice, source, &target);
+            }
+        }
+        if !pending.queue.is_empty() {
             return;
         }
-        self.slash_history_index = Some(next_index);
-        if let Some(entry) = self.slash_history.get(next_index) {
-            self.slash_input_buffer = entry.clone();
+        if let Some(pending) = self.pending_conflicts.take() {
+            let feedback = self.run_transfer(
+                pending.kind,
+                pending.sources,
+                pending.dest,
+                pending.overrides,
+            );
+            self.slash_feedback = Some(feedback);
         }
     }
 
-    pub fn slash_candidates(&self) -> SlashCandidates {
-        let Some(prefix) = self.slash_command_prefix() else {
-            return SlashCandidates::default();
-        };
-        if prefix.is_empty() {
-            return SlashCandidates::default();
-        }
-        if let Some(prefix) = self.color_candidate_prefix() {
-            return SlashCandidates {
-                items: color_theme_candidates(prefix),
-            };
-        }
-        if let Some(target) = self.shell_completion_target() {
-            return SlashCandidates {
-                items: self.shell_path_candidates(&target.prefix),
-            };
+    pub fn cancel_conflicts(&mut self) {
+        if self.pending_conflicts.take().is_some() {
+            self.slash_feedback =
+                Some(self.timed_feedback("cancelled".to_string(), FeedbackStatus::Warn));
         }
-        let items = slash_command_specs()
+    }
+
+    pub fn results_view_active(&self) -> bool {
+        self.results_view.is_some()
+    }
+
+    pub fn results_view_lines(&self) -> Option<(String, Vec<String>, Option<usize>)> {
+        let view = self.results_view.as_ref()?;
+        let status = if view.running { ", searching" } else { "" };
+        let title = match view.kind {
+            ResultsKind::Bookmarks => format!("bookmarks ({})", view.items.len()),
+            _ => format!(
+                "{} {} ({} found{status})",
+                view.kind.label(),
+                view.query,
+                view.items.len()
+            ),
+        };
+        let lines = view
+            .items
             .iter()
-            .map(|spec| spec.name)
-            .filter(|command| command.starts_with(prefix))
-            .map(|command| SlashCandidate {
-                text: format!("/{command}"),
-                description: None,
+            .map(|item| {
+                if view.kind == ResultsKind::Bookmarks {
+                    return format!("{}  {}", item.text, item.path.display());
+                }
+                let path = item.path.strip_prefix(&view.root).unwrap_or(&item.path);
+                match item.line {
+                    Some(line) => format!("{}:{line}: {}", path.display(), item.text),
+                    None => path.display().to_string(),
+                }
             })
-            .collect();
-        SlashCandidates { items }
+            .collect::<Vec<_>>();
+        let cursor = (!lines.is_empty()).then_some(view.cursor);
+        Some((title, lines, cursor))
     }
 
-    pub fn complete_slash_candidate(&mut self) {
-        if let Some(target) = self.shell_completion_target() {
-            let Some(candidate) = self
-                .shell_path_candidates(&target.prefix)
-                .into_iter()
-                .next()
-            else {
-                return;
-            };
-            let mut buffer = String::new();
-            buffer.push_str(&self.slash_input_buffer[..target.range.start]);
-            buffer.push_str(&candidate.text);
-            if !candidate.text.ends_with('/')
-                && !candidate.text.ends_with(std::path::MAIN_SEPARATOR)
-            {
-                buffer.push(' ');
-            }
-            buffer.push_str(&self.slash_input_buffer[target.range.end..]);
-            self.slash_input_buffer = buffer;
-            self.slash_history_index = None;
-            return;
-        }
-        let Some(candidate) = self.slash_candidates().items.into_iter().next() else {
-            return;
-        };
-        if self.color_candidate_prefix().is_some() {
-            self.slash_input_buffer = format!("/color {}", candidate.text);
-        } else {
-            if self.slash_input_buffer.contains(char::is_whitespace) {
-                return;
-            }
-            self.slash_input_buffer = format!("{} ", candidate.text);
+    pub fn move_results_cursor_up(&mut self) {
+        if let Some(view) = self.results_view.as_mut() {
+            view.cursor = view.cursor.saturating_sub(1);
         }
-        self.slash_history_index = None;
     }
 
-    pub fn slash_hint(&self) -> Option<String> {
-        if !self.slash_input_active {
-            return None;
-        }
-        let trimmed = self.slash_input_buffer.trim_end();
-        let stripped = trimmed.strip_prefix('/')?;
-        let mut parts = stripped.split_whitespace();
-        let name = parts.next()?;
-        if parts.next().is_some() {
-            return None;
-        }
-        let spec = slash_command_spec(name)?;
-        if spec.options.is_empty() {
-            return Some(spec.description.to_string());
+    pub fn move_results_cursor_down(&mut self) {
+        if let Some(view) = self.results_view.as_mut()
+            && view.cursor + 1 < view.items.len()
+        {
+            view.cursor += 1;
         }
-        Some(format!(
-            "{} | options: {}",
-            spec.description,
-            spec.options.join(", ")
-        ))
     }
 
-    pub fn toggle_shell_output(&mut self) {
-        if self.shell_output_view.is_empty() {
+    pub fn cancel_or_close_results(&mut self) {
+        let Some(view) = self.results_view.as_ref() else {
+            return;
+        };
+        if !view.running {
+            self.results_view = None;
             return;
         }
-        self.shell_output_active = !self.shell_output_active;
+        let text = format!(
+            "{}: cancelled ({} found)",
+            view.kind.label(),
+            view.items.len()
+        );
+        self.stop_results_search();
+        self.slash_feedback = Some(self.timed_feedback(text, FeedbackStatus::Warn));
     }
 
-    pub fn poll_shell_events(&mut self) {
-        while let Some(event) = self.shell_worker.poll() {
-            match event {
-                ShellEvent::Stdout(line) => {
-                    self.shell_output_view.append_stdout_line(&line);
-                }
-                ShellEvent::Stderr(line) => {
-                    self.shell_output_view.append_stderr_line(&line);
-                }
-                ShellEvent::Finished(result) => {
-                    self.shell_output_view.finish(&result);
-                    self.push_user_notice(UserNotice::new(
-                        shell_result_notice_level(&result),
-                        format_shell_notice(&result),
-                        "shell",
-                    ));
-                }
-                ShellEvent::Failed(error) => {
-                    self.shell_output_view.append_stderr_line(&error);
-                    self.push_user_notice(UserNotice::new(UserNoticeLevel::Error, error, "shell"));
-                }
-            }
+    pub fn open_selected_result(&mut self) -> AppResult<()> {
+        let Some((kind, item)) = self.results_view.as_ref().and_then(|view| {
+            view.items
+                .get(view.cursor)
+                .cloned()
+                .map(|item| (view.kind, item))
+        }) else {
+            return Ok(());
+        };
+        if kind == ResultsKind::Bookmarks {
+            self.results_view = None;
+            self.slash_feedback = Some(self.go_to_bookmark(&item.text));
+            return Ok(());
+        }
+        let (Some(parent), Some(name)) = (item.path.parent(), item.path.file_name()) else {
+            return Ok(());
+        };
+        self.stop_results_search();
+        self.results_view = None;
+        if let Some(line) = item.line {
+            self.preview_visible = true;
+            self.preview_focus = Some((item.path.clone(), line));
         }
+        let name = name.to_string_lossy().to_string();
+        self.change_dir(parent.to_path_buf());
+        self.refresh_with_selection(Some(&name))
     }
 
-    pub fn poll_session_events(&mut self) {
-        for event in poll_session_events() {
-            match event {
-                SessionEvent::SaveFailed => {
-                    self.push_user_notice(UserNotice::with_ttl_ms(
-                        UserNoticeLevel::Warn,
-                        "save failed",
-                        "session",
-                        None,
-                    ));
-                }
-            }
-        }
+    pub fn preview_focus_line(&self, path: &Path) -> Option<usize> {
+        self.preview_focus
+            .as_ref()
+            .filter(|(focus_path, _)| focus_path == path)
+            .map(|(_, line)| *line)
     }
 
-    pub fn poll_config_events(&mut self) {
-        for event in poll_config_events() {
-            match event {
-                ConfigEvent::ConfigRootUnavailable => {
-                    self.slash_feedback = Some(self.timed_feedback(
-                        "config: not writable; set OX_CONFIG_HOME".to_string(),
-                        FeedbackStatus::Error,
-                    ));
-                }
-            }
+    pub fn poll_results_events(&mut self) {
+        while let Some(event) = self.finder_worker.poll() {
+            self.handle_find_event(event);
+        }
+        while let Some(event) = self.grep_worker.poll() {
+            self.handle_grep_event(event);
         }
     }
 
-    pub fn close_shell_output(&mut self) {
-        self.shell_output_active = false;
+    fn handle_find_event(&mut self, event: FindEvent) {
+        match event {
+            FindEvent::Found { id, path } => self.push_result(
+                id,
+                ResultItem {
+                    path,
+                    line: None,
+                    text: String::new(),
+                },
+            ),
+            FindEvent::Finished {
+                id,
+                cancelled: false,
+                ..
+            } => self.finish_results(id),
+            FindEvent::Finished { .. } => {}
+        }
     }
 
-    pub fn scroll_shell_output_up(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.scroll_up();
+    fn handle_grep_event(&mut self, event: GrepEvent) {
+        match event {
+            GrepEvent::Hit { id, hit } => self.push_result(
+                id,
+                ResultItem {
+                    path: hit.path,
+                    line: Some(hit.line),
+                    text: hit.text,
+                },
+            ),
+            GrepEvent::Finished {
+                id,
+                cancelled: false,
+                ..
+            } => self.finish_results(id),
+            GrepEvent::Finished { .. } => {}
         }
     }
 
-    pub fn scroll_shell_output_down(&mut self) {
-        if self.shell_output_active {
-            self.shell_output_view.scroll_down();
+    fn push_result(&mut self, id: u64, item: ResultItem) {
+        if let Some(view) = self.results_view.as_mut()
+            && view.id == id
+        {
+            view.items.push(item);
         }
     }
 
-    pub fn append_search_char(&mut self, ch: char) {
-        if self.entries.is_empty() {
+    fn finish_results(&mut self, id: u64) {
+        let Some(view) = self.results_view.as_mut() else {
+            return;
+        };
+        if view.id != id || !view.running {
             return;
         }
-        if self.search_buffer.is_empty() {
-            self.search_origin = self.cursor;
-        }
-        self.search_buffer.push(ch);
-        self.apply_search();
+        view.running = false;
+        let text = format!("{}: {} found", view.kind.label(), view.items.len());
+        self.slash_feedback = Some(self.timed_feedback(text, FeedbackStatus::Success));
+    }
+
+    fn start_results(&mut self, kind: ResultsKind, query: &str) -> u64 {
+        self.stop_results_search();
+        self.trash_view = None;
+        self.results_request_id = self.results_request_id.wrapping_add(1).max(1);
+        self.results_view = Some(ResultsView {
+            id: self.results_request_id,
+            kind,
+            query: query.to_string(),
+            root: self.current_dir.clone(),
+            items: Vec::new(),
+            cursor: 0,
+            running: true,
+        });
+        self.results_request_id
     }
 
-    pub fn backspace_search_char(&mut self) {
-        if self.search_buffer.is_empty() {
-            return;
-        }
-        self.search_buffer.pop();
-        if self.search_buffer.is_empty() {
-            self.restore_search_origin();
+    fn stop_results_search(&mut self) {
+        let Some(view) = self.results_view.as_mut() else {
             return;
+        };
+        if view.running {
+            view.running = false;
+            match view.kind {
+                ResultsKind::Where => self.finder_worker.cancel(),
+                ResultsKind::Grep => self.grep_worker.cancel(),
+                ResultsKind::Bookmarks => {}
+            }
         }
-        self.apply_search();
     }
 
-    pub fn reset_search(&mut self) {
-        if self.search_buffer.is_empty() {
+    pub fn open_bookmark_picker(&mut self) {
+        if self.bookmarks.is_empty() {
+            self.slash_feedback =
+                Some(self.timed_feedback("bookmark: none".to_string(), FeedbackStatus::Warn));
             return;
         }
-        self.search_buffer.clear();
-        self.restore_search_origin();
+        self.stop_results_search();
+        self.trash_view = None;
+        let items = self
+            .bookmarks
+            .iter()
+            .map(|(name, path)| ResultItem {
+                path: path.to_path_buf(),
+                line: None,
+                text: name.to_string(),
+            })
+            .collect();
+        self.results_view = Some(ResultsView {
+            id: 0,
+            kind: ResultsKind::Bookmarks,
+            query: String::new(),
+            root: self.current_dir.clone(),
+            items,
+            cursor: 0,
+            running: false,
+        });
     }
 
-    fn refresh(&mut self) -> AppResult<()> {
-        self.refresh_with_selection(None)
+    pub fn trash_view_active(&self) -> bool {
+        self.trash_view.is_some()
     }
 
-    fn reload_entries(&mut self) -> AppResult<()> {
-        self.entries = list_entries(&self.current_dir, self.show_hidden)?;
-        self.parent_entries = list_parent_entries(&self.current_dir, self.show_hidden)?;
-        self.clear_search_state();
-        Ok(())
+    pub fn trash_view_items(&self) -> Option<(&[TrashedItem], usize)> {
+        let view = self.trash_view.as_ref()?;
+        Some((&view.items, view.cursor))
     }
 
-    fn refresh_with_selection(&mut self, focus_name: Option<&str>) -> AppResult<()> {
-        self.reload_entries()?;
-        self.cursor = resolve_cursor(&self.entries, focus_name, None);
-        Ok(())
+    pub fn move_trash_cursor_up(&mut self) {
+        if let Some(view) = self.trash_view.as_mut() {
+            view.cursor = view.cursor.saturating_sub(1);
+        }
     }
 
-    fn apply_search(&mut self) {
-        if self.search_buffer.is_empty() {
-            return;
-        }
-        let needle = self.search_buffer.as_str();
-        if let Some(index) = self
-            .entries
-            .iter()
-            .position(|entry| entry.name.starts_with(needle))
+    pub fn move_trash_cursor_down(&mut self) {
+        if let Some(view) = self.trash_view.as_mut()
+            && view.cursor + 1 < view.items.len()
         {
-            self.cursor = Some(index);
+            view.cursor += 1;
         }
     }
 
-    fn clear_search_state(&mut self) {
-        self.search_buffer.clear();
-        self.search_origin = None;
+    pub fn close_trash_view(&mut self) {
+        self.trash_view = None;
     }
 
-    fn restore_search_origin(&mut self) {
-        let origin = self.search_origin.take();
-        self.cursor = match origin {
-            Some(index) => clamp_cursor(&self.entries, index),
-            None => clamp_cursor(&self.entries, 0).or(self.cursor),
+    pub fn restore_selected_trash_item(&mut self) {
+        let Some(store) = self.trash_store.clone() else {
+            return;
+        };
+        let Some(item) = self
+            .trash_view
+            .as_ref()
+            .and_then(|view| view.items.get(view.cursor).cloned())
+        else {
+            return;
+        };
+        let feedback = match store.restore(&item) {
+            Ok(restored) => {
+                self.open_trash_view_with(&store);
+                if restored.parent() == Some(self.current_dir.as_path()) {
+                    let name = restored
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string());
+                    if let Err(error) = self.refresh_with_selection(name.as_deref()) {
+                        return self.push_user_notice(UserNotice::new(
+                            UserNoticeLevel::Error,
+                            error.to_string(),
+                            "trash",
+                        ));
+                    }
+                }
+                self.timed_feedback(
+                    format!("trash: restored {}", restored.display()),
+                    FeedbackStatus::Success,
+                )
+            }
+            Err(error) => self.file_op_error_feedback("trash", error),
         };
+        self.slash_feedback = Some(feedback);
     }
 
-    fn set_current_dir(&mut self, path: PathBuf) {
-        self.current_dir = path;
+    pub fn search_text(&self) -> &str {
+        &self.search_buffer
     }
 
-    fn change_dir(&mut self, path: PathBuf) {
-        self.set_current_dir(path);
-        self.tabs.update_active_path(self.current_dir.as_path());
-        self.handle_tab_events();
+    pub fn slash_input_active(&self) -> bool {
+        self.slash_input_active
     }
 
-    fn switch_to_tab(&mut self, index: usize) -> AppResult<()> {
-        let Some(next_dir) = self.tabs.switch_to(index, self.current_dir.as_path()) else {
-            return Ok(());
-        };
-        self.set_current_dir(next_dir);
-        self.announce_active_tab_color();
-        self.refresh()?;
-        self.handle_tab_events();
-        Ok(())
+    pub fn slash_input_text(&self) -> &str {
+        &self.slash_input_buffer
     }
 
-    fn handle_slash_command(&mut self, command: &SlashCommand) -> SlashFeedback {
-        match command.name.as_str() {
-            "preview" => self.handle_preview_command(&command.args),
-            "tab" => self.handle_tab_command(&command.args),
-            "color" => self.handle_color_command(&command.args),
-            "paste" => self.timed_feedback("paste: ready".to_string(), FeedbackStatus::Success),
-            "shell" => self.handle_shell_command(command),
-            _ => self.timed_feedback(
-                format!("unknown command: {}", command.name),
-                FeedbackStatus::Error,
-            ),
+    pub fn slash_feedback(&self) -> Option<&SlashFeedback> {
+        let feedback = self.slash_feedback.as_ref()?;
+        if feedback.is_expired_with(self.clock.as_ref()) {
+            return None;
         }
+        Some(feedback)
     }
 
-    fn handle_preview_command(&mut self, args: &[String]) -> SlashFeedback {
-        match args {
-            [] => {
-                let next = !self.preview_visible;
-                self.preview_visible = next;
-                self.preview_paused = !next;
-                self.preview_feedback(next)
-            }
-            [arg] if arg == "show" => {
-                self.preview_visible = true;
-                self.preview_paused = false;
-                self.preview_feedback(true)
-            }
-            [arg] if arg == "hide" => {
-                self.preview_visible = false;
-                self.preview_paused = true;
-                self.preview_feedback(false)
-            }
-            _ => self.timed_feedback("preview: invalid args".to_string(), FeedbackStatus::Error),
-        }
+    fn push_user_notice(&mut self, notice: UserNotice) {
+        let _ = self.user_notice_queue.push(notice, self.clock.now());
     }
 
-    fn handle_tab_command(&mut self, args: &[String]) -> SlashFeedback {
-        match args {
-            [] => self.tab_list_feedback(),
-            [arg] if arg == "new" => match self.new_tab() {
-                Ok(()) => self.tab_list_feedback(),
-                Err(error) => self.tab_error_feedback(error),
-            },
-            [arg] if arg == "next" => match self.next_tab() {
-                Ok(()) => self.tab_list_feedback(),
-                Err(error) => self.tab_error_feedback(error),
-            },
-            [arg] if arg == "prev" => match self.prev_tab() {
-                Ok(()) => self.tab_list_feedback(),
-                Err(error) => self.tab_error_feedback(error),
-            },
-            [arg] => match arg.parse::<usize>() {
-                Ok(number) if number >= 1 && number <= self.tab_count() => {
-                    match self.switch_to_tab(number.saturating_sub(1)) {
-                        Ok(()) => self.tab_list_feedback(),
-                        Err(error) => self.tab_error_feedback(error),
-                    }
-                }
-                _ => self.timed_feedback("tab: invalid args".to_string(), FeedbackStatus::Error),
-            },
-            _ => self.timed_feedback("tab: invalid args".to_string(), FeedbackStatus::Error),
-        }
+    pub fn user_notice(&mut self) -> Option<UserNotice> {
+        self.user_notice_queue.current(self.clock.now()).cloned()
     }
 
-    fn handle_color_command(&mut self, args: &[String]) -> SlashFeedback {
-        let name = args.join(" ").trim().to_string();
-        if name.is_empty() {
-            return self.timed_feedback("color: missing theme".to_string(), FeedbackStatus::Error);
-        }
-        let Some(theme_id) = ColorThemeId::from_name(&name) else {
-            return self.timed_feedback("color: unknown theme".to_string(), FeedbackStatus::Error);
-        };
-        let context = CommandContext::Tab(self.tabs.active_tab_id());
-        let preference = self.tabs.set_active_theme(theme_id);
-        let theme = theme_id.theme();
-        self.tab_color_changed = Some(TabColorChanged {
-            tab_id: preference.tab_id,
-            theme,
-        });
-        self.handle_tab_events();
-        match context {
-            CommandContext::Tab(_) => {
-                self.timed_feedback(format!("theme: {}", theme.name), FeedbackStatus::Success)
-            }
+    pub fn shell_output_active(&self) -> bool {
+        self.shell_output_active
+    }
+
+    pub fn shell_output_text(&self, height: usize, width: usize) -> Option<String> {
+        if self.shell_output_view.is_empty() {
+            None
+        } else {
+            Some(self.shell_output_view.view_text(height, width))
         }
     }
 
-    fn handle_shell_command(&mut self, command: &SlashCommand) -> SlashFeedback {
-        if !self.shell_permission.is_allowed() {
-            self.push_user_notice(UserNotice::new(
-                UserNoticeLevel::Warn,
-                "Shell commands disabled",
-                "shell",
-            ));
-            return self
-                .timed_feedback("Shell commands disabled".to_string(), FeedbackStatus::Warn);
+    pub fn flush_session_save(&mut self) {
+        if !self.session_save_pending {
+            return;
         }
-        let raw = command
-            .raw
-            .strip_prefix("/shell")
-            .unwrap_or(command.raw.as_str())
-            .trim_start();
-        let request = match ShellCommandRequest::new(self.current_dir.clone(), raw) {
-            Ok(request) => request,
-            Err(error) => return self.shell_error_feedback(error),
+        let Some(deadline) = self.session_save_deadline else {
+            return;
         };
-        self.shell_output_view.start(&request);
-        self.shell_worker.request(request);
-        self.push_user_notice(UserNotice::new(
-            UserNoticeLevel::Info,
-            format_shell_start_notice(raw),
-            "shell",
-        ));
-        self.timed_feedback("shell: running".to_string(), FeedbackStatus::Success)
+        if self.clock.now() < deadline {
+            return;
+        }
+        self.session_save_pending = false;
+        self.session_save_deadline = None;
+        let tabs = self.tabs.session_tabs();
+        save_session_async(tabs);
     }
 
-    fn tab_list_feedback(&self) -> SlashFeedback {
-        let summaries = self.tabs.summaries();
-        SlashFeedback {
-            text: String::new(),
-            status: FeedbackStatus::Success,
-            tabs: Some(summaries),
-            expires_at: self.clock.now() + SLASH_FEEDBACK_TTL,
-        }
+    pub fn force_session_save(&mut self) {
+        self.session_save_pending = false;
+        self.session_save_deadline = None;
+        let tabs = self.tabs.session_tabs();
+        save_session_async(tabs);
     }
 
-    fn handle_tab_events(&mut self) {
-        let events = self.tabs.take_events();
-        if events.is_empty() {
+    pub fn flush_frecency_save(&mut self) {
+        let Some(deadline) = self.frecency_save_deadline else {
+            return;
+        };
+        if self.clock.now() < deadline {
             return;
         }
-        if events.iter().any(should_save_session) {
-            self.session_save_pending = true;
-            self.session_save_deadline = Some(self.clock.now() + SESSION_SAVE_DEBOUNCE);
+        self.force_frecency_save();
+    }
+
+    pub fn force_frecency_save(&mut self) {
+        self.frecency_save_deadline = None;
+        if let Some(snapshot) = self.frecency.snapshot() {
+            self.frecency_worker.request(snapshot);
         }
     }
 
-    fn slash_command_prefix(&self) -> Option<&str> {
-        let mut parts = self.slash_input_buffer.split_whitespace();
-        let command = parts.next()?;
-        command.strip_prefix('/')
+    pub fn page_up_shell_output(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.page_up();
+        }
     }
 
-    fn color_candidate_prefix(&self) -> Option<&str> {
-        let trimmed = self.slash_input_buffer.trim_end();
-        let stripped = trimmed.strip_prefix('/')?;
-        let rest = stripped.strip_prefix("color")?;
-        if rest.is_empty() {
-            return Some("");
+    pub fn page_down_shell_output(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.page_down();
         }
-        if rest.starts_with(char::is_whitespace) {
-            return Some(rest.trim_start());
+    }
+
+    pub fn home_shell_output(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.home();
         }
-        None
     }
 
-    fn shell_completion_target(&self) -> Option<ShellCompletionTarget> {
-        let input = self.slash_input_buffer.as_str();
-        let after = input.strip_prefix("/shell")?;
-        if after.is_empty() {
-            return None;
+    pub fn end_shell_output(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.end();
         }
-        if !after.starts_with(char::is_whitespace) {
-            return None;
+    }
+
+    pub fn scroll_shell_output_left(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.scroll_left();
         }
-        let has_trailing_space = input
-            .chars()
-            .last()
-            .map(|ch| ch.is_whitespace())
-            .unwrap_or(false);
-        let (start, end) = if has_trailing_space {
-            (input.len(), input.len())
-        } else {
-            let trimmed = input.trim_end();
-            let end = trimmed.len();
-            let start = trimmed
-                .char_indices()
-                .rev()
-                .find(|(_, ch)| ch.is_whitespace())
-                .map(|(idx, ch)| idx + ch.len_utf8())
-                .unwrap_or(0);
-            (start, end)
-        };
-        let prefix = if start == end {
-            String::new()
-        } else {
-            input[start..end].to_string()
-        };
-        Some(ShellCompletionTarget {
-            range: start..end,
-            prefix,
-        })
     }
 
-    fn shell_path_candidates(&self, prefix: &str) -> Vec<SlashCandidate> {
-        if prefix.contains('"') || prefix.contains('\'') {
-            return Vec::new();
+    pub fn scroll_shell_output_right(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.scroll_right();
         }
-        let (dir_prefix, name_prefix) = split_path_prefix(prefix);
-        let base_dir = if Path::new(prefix).is_absolute() {
-            PathBuf::from(&dir_prefix)
-        } else {
-            self.current_dir.join(&dir_prefix)
-        };
-        let entries = match list_entries(&base_dir, self.show_hidden) {
-            Ok(entries) => entries,
-            Err(_) => return Vec::new(),
-        };
-        entries
-            .into_iter()
-            .filter(|entry| entry.name.starts_with(&name_prefix))
-            .map(|entry| {
-                let mut text = String::new();
-                text.push_str(&dir_prefix);
-                text.push_str(&entry.name);
-                if entry.is_dir {
-                    text.push('/');
-                }
-                SlashCandidate {
-                    text,
-                    description: None,
-                }
-            })
-            .collect()
     }
 
-    fn timed_feedback(&self, text: String, status: FeedbackStatus) -> SlashFeedback {
-        timed_feedback_with(self.clock.as_ref(), text, status)
+    pub fn preview_visible(&self) -> bool {
+        self.preview_visible
     }
 
-    fn preview_feedback(&self, enabled: bool) -> SlashFeedback {
-        self.timed_feedback(
-            format!("preview: {}", if enabled { "on" } else { "off" }),
-            FeedbackStatus::Success,
-        )
+    pub fn preview_ratio_percent(&self) -> u16 {
+        self.preview_ratio_percent
     }
 
-    fn opener_disabled_feedback(&self) -> SlashFeedback {
-        self.timed_feedback(
-            "opener is disabled. Set allow_opener = true to open with external apps.".to_string(),
-            FeedbackStatus::Warn,
-        )
+    pub fn tab_count(&self) -> usize {
+        self.tabs.count()
     }
 
-    fn tab_error_feedback(&self, error: AppError) -> SlashFeedback {
-        self.timed_feedback(format!("tab: {}", error), FeedbackStatus::Error)
+    pub fn active_tab_number(&self) -> usize {
+        self.tabs.active_number()
     }
 
-    fn shell_error_feedback(&self, error: ShellCommandError) -> SlashFeedback {
-        self.timed_feedback(error.to_string(), FeedbackStatus::Error)
+    pub fn active_theme(&self) -> ColorTheme {
+        self.tabs.active_theme_id().theme()
     }
 
-    fn announce_active_tab_color(&mut self) {
-        let theme_id = self.tabs.active_theme_id();
-        self.tab_color_changed = Some(TabColorChanged {
-            tab_id: self.tabs.active_tab_id(),
-            theme: theme_id.theme(),
-        });
+    pub fn take_tab_color_changed(&mut self) -> Option<TabColorChanged> {
+        self.tab_color_changed.take()
     }
-}
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+    pub fn new_tab(&mut self) -> AppResult<()> {
+        self.tabs.push_new(self.current_dir.as_path());
+        self.announce_active_tab_color();
+        self.clear_search_state();
+        self.handle_tab_events();
+        Ok(())
+    }
+
+    pub fn next_tab(&mut self) -> AppResult<()> {
+        let Some(next) = self.tabs.next_index() else {
+            return Ok(());
+        };
+        self.switch_to_tab(next)
+    }
+
+    pub fn prev_tab(&mut self) -> AppResult<()> {
+        let Some(prev) = self.tabs.prev_index() else {
+            return Ok(());
+        };
+        self.switch_to_tab(prev)
+    }
+
+    pub fn activate_slash_input(&mut self) {
+        self.slash_input_active = true;
+        self.slash_input_buffer.clear();
+        self.slash_input_buffer.push('/');
+        self.slash_history_index = None;
+    }
+
+    pub fn cancel_slash_input(&mut self) {
+        self.slash_input_active = false;
+        self.slash_input_buffer.clear();
+        self.slash_history_index = None;
+    }
+
+    pub fn append_slash_char(&mut self, ch: char) {
+        if !self.slash_input_active {
+            return;
+        }
+        self.slash_input_buffer.push(ch);
+        self.slash_history_index = None;
+    }
+
+    pub fn backspace_slash_char(&mut self) {
+        if !self.slash_input_active {
+            return;
+        }
+        if self.slash_input_buffer.len() <= 1 {
+            self.cancel_slash_input();
+            return;
+        }
+        self.slash_input_buffer.pop();
+        self.slash_history_index = None;
+    }
+
+    pub fn submit_slash_command(&mut self) -> Option<SlashCommand> {
+        if !self.slash_input_active {
+            return None;
+        }
+        let input_snapshot = self.slash_input_buffer.clone();
+        let command = parse_slash_command(&self.slash_input_buffer);
+        self.slash_input_active = false;
+        self.slash_input_buffer.clear();
+        self.slash_history_index = None;
+        match command {
+            Ok(command) => {
+                self.slash_history.push(input_snapshot);
+                self.slash_feedback = Some(self.handle_slash_command(&command));
+                Some(command)
+            }
+            Err(error) => {
+                self.slash_feedback = Some(self.timed_feedback(
+                    format!("slash error: {}", format_slash_error(error)),
+                    FeedbackStatus::Error,
+                ));
+                None
+            }
+        }
+    }
+
+    pub fn slash_history_prev(&mut self) {
+        if self.slash_history.is_empty() {
+            return;
+        }
+        let next_index = match self.slash_history_index {
+            None => self.slash_history.len().saturating_sub(1),
+            Some(index) => index.saturating_sub(1),
+        };
+        self.slash_history_index = Some(next_index);
+        if let Some(entry) = self.slash_history.get(next_index) {
+            self.slash_input_buffer = entry.clone();
+        }
+    }
+
+    pub fn slash_history_next(&mut self) {
+        let Some(index) = self.slash_history_index else {
+            return;
+        };
+        let next_index = index.saturating_add(1);
+        if next_index >= self.slash_history.len() {
+            self.slash_history_index = None;
+            self.slash_input_buffer = "/".to_string();
+            return;
+        }
+        self.slash_history_index = Some(next_index);
+        if let Some(entry) = self.slash_history.get(next_index) {
+            self.slash_input_buffer = entry.clone();
+        }
+    }
+
+    pub fn slash_candidates(&self) -> SlashCandidates {
+        let Some(prefix) = self.slash_command_prefix() else {
+            return SlashCandidates::default();
+        };
+        if prefix.is_empty() {
+            return SlashCandidates::default();
+        }
+        if let Some(prefix) = self.color_candidate_prefix() {
+            return SlashCandidates {
+                items: color_theme_candidates(prefix),
+            };
+        }
+        if let Some(target) = self.shell_completion_target() {
+            return SlashCandidates {
+                items: self.shell_path_candidates(&target.prefix, target.dirs_only),
+            };
+        }
+        if let Some((previous, prefix)) = self.sort_candidate_target() {
+            return SlashCandidates {
+                items: sort_candidates(&previous, prefix),
+            };
+        }
+        if let Some((_, prefix)) = self.bookmark_candidate_target() {
+            return SlashCandidates {
+                items: self.bookmark_candidates(prefix),
+            };
+        }
+        if let Some(fragments) = self.z_candidate_fragments() {
+            return SlashCandidates {
+                items: self.z_candidates(&fragments),
+            };
+        }
+        let items = slash_command_specs()
+            .iter()
+            .map(|spec| spec.name)
+            .filter(|command| command.starts_with(prefix))
+            .map(|command| SlashCandidate {
+                text: format!("/{command}"),
+                description: None,
+            })
+            .collect();
+        SlashCandidates { items }
+    }
+
+    pub fn complete_slash_candidate(&mut self) {
+        if let Some(target) = self.shell_completion_target() {
+            let Some(candidate) = self
+                .shell_path_candidates(&target.prefix, target.dirs_only)
+                .into_iter()
+                .next()
+            else {
+                return;
+            };
+            let mut buffer = String::new();
+            buffer.push_str(&self.slash_input_buffer[..target.range.start]);
+            buffer.push_str(&candidate.text);
+            if !candidate.text.ends_with('/')
+                && !candidate.text.ends_with(std::path::MAIN_SEPARATOR)
+            {
+                buffer.push(' ');
+            }
+            buffer.push_str(&self.slash_input_buffer[target.range.end..]);
+            self.slash_input_buffer = buffer;
+            self.slash_history_index = None;
+            return;
+        }
+        if let Some((previous, prefix)) = self.sort_candidate_target() {
+            let Some(candidate) = sort_candidates(&previous, prefix).into_iter().next() else {
+                return;
+            };
+            let mut words = previous;
+            words.push(candidate.text);
+            self.slash_input_buffer = format!("/sort {} ", words.join(" "));
+            self.slash_history_index = None;
+            return;
+        }
+        if let Some((command, prefix)) = self.bookmark_candidate_target() {
+            let Some(candidate) = self.bookmark_candidates(prefix).into_iter().next() else {
+                return;
+            };
+            self.slash_input_buffer = format!("{command}{}", candidate.text);
+            self.slash_history_index = None;
+            return;
+        }
+        if let Some(fragments) = self.z_candidate_fragments() {
+            let Some(candidate) = self.z_candidates(&fragments).into_iter().next() else {
+                return;
+            };
+            self.slash_input_buffer = format!("/z {}", candidate.text);
+            self.slash_history_index = None;
+            return;
+        }
+        let Some(candidate) = self.slash_candidates().items.into_iter().next() else {
+            return;
+        };
+        if self.color_candidate_prefix().is_some() {
+            self.slash_input_buffer = format!("/color {}", candidate.text);
+        } else {
+            if self.slash_input_buffer.contains(char::is_whitespace) {
+                return;
+            }
+            self.slash_input_buffer = format!("{} ", candidate.text);
+        }
+        self.slash_history_index = None;
+    }
+
+    pub fn slash_hint(&self) -> Option<String> {
+        if !self.slash_input_active {
+            return None;
+        }
+        let trimmed = self.slash_input_buffer.trim_end();
+        let stripped = trimmed.strip_prefix('/')?;
+        let mut parts = stripped.split_whitespace();
+        let name = parts.next()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        let spec = slash_command_spec(name)?;
+        let mut hint = if spec.options.is_empty() {
+            spec.description.to_string()
+        } else {
+            format!(
+                "{} | options: {}",
+                spec.description,
+                spec.options.join(", ")
+            )
+        };
+        if name == "paste"
+            && let Some(register) = self.register.as_ref()
+        {
+            hint.push_str(" | ");
+            hint.push_str(&register.summary());
+        }
+        Some(hint)
+    }
+
+    pub fn toggle_shell_output(&mut self) {
+        if self.shell_output_view.is_empty() {
+            return;
+        }
+        self.shell_output_active = !self.shell_output_active;
+    }
+
+    pub fn poll_shell_events(&mut self) {
+        while let Some(event) = self.shell_worker.poll() {
+            match event {
+                ShellEvent::Stdout(line) => {
+                    self.shell_output_view.append_stdout_line(&line);
+                }
+                ShellEvent::Stderr(line) => {
+                    self.shell_output_view.append_stderr_line(&line);
+                }
+                ShellEvent::Finished(result) => {
+                    self.shell_output_view.finish(&result);
+                    self.push_user_notice(UserNotice::new(
+                        shell_result_notice_level(&result),
+                        format_shell_notice(&result),
+                        "shell",
+                    ));
+                }
+                ShellEvent::Failed(error) => {
+                    self.shell_output_view.append_stderr_line(&error);
+                    self.push_user_notice(UserNotice::new(UserNoticeLevel::Error, error, "shell"));
+                }
+            }
+        }
+    }
+
+    pub fn copy_progress(&self) -> Option<&CopyProgress> {
+        self.copy_progress.as_ref()
+    }
+
+    pub fn poll_copy_events(&mut self) {
+        while let Some(event) = self.copy_worker.poll() {
+            self.handle_copy_event(event);
+        }
+    }
+
+    fn handle_copy_event(&mut self, event: CopyEvent) {
+        match event {
+            CopyEvent::Started {
+                id,
+                total_files,
+                total_bytes,
+            } => {
+                self.copy_progress = Some(CopyProgress {
+                    id,
+                    done_files: 0,
+                    total_files,
+                    done_bytes: 0,
+                    total_bytes,
+                    current: None,
+                });
+            }
+            CopyEvent::Progress(progress) => {
+                self.copy_progress = Some(progress);
+            }
+            CopyEvent::ItemFailed { path, message, .. } => {
+                self.push_user_notice(UserNotice::new(
+                    UserNoticeLevel::Error,
+                    format!("{}: {message}", path.display()),
+                    "copy",
+                ));
+            }
+            CopyEvent::Finished(summary) => {
+                self.copy_progress = None;
+                let (verb, source) = if summary.removed_sources {
+                    ("moved", "move")
+                } else {
+                    ("copied", "copy")
+                };
+                let (level, text) = if summary.failed == 0 {
+                    (
+                        UserNoticeLevel::Success,
+                        format!("{verb} {} files", summary.copied_files),
+                    )
+                } else {
+                    (
+                        UserNoticeLevel::Warn,
+                        format!(
+                            "{verb} {} files, {} failed",
+                            summary.copied_files, summary.failed
+                        ),
+                    )
+                };
+                self.push_user_notice(UserNotice::new(level, text, source));
+                if !summary.copied.is_empty() {
+                    let items = summary
+                        .copied
+                        .iter()
+                        .map(|item| {
+                            JournalPath::replacing(
+                                item.source.clone(),
+                                item.dest.clone(),
+                                item.replaced.clone(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    let label = format!("{source} {} items", items.len());
+                    let op = if summary.removed_sources {
+                        JournalOp::Move { items }
+                    } else {
+                        JournalOp::Copy { items }
+                    };
+                    self.record_journal(label, op);
+                }
+                if summary.removed_sources {
+                    self.prune_cut_register();
+                }
+                if summary.dest_dir == self.current_dir || summary.removed_sources {
+                    self.reload_keeping_selection();
+                }
+            }
+        }
+    }
+
+    pub fn poll_session_events(&mut self) {
+        for event in poll_session_events() {
+            match event {
+                SessionEvent::SaveFailed => {
+                    self.push_user_notice(UserNotice::with_ttl_ms(
+                        UserNoticeLevel::Warn,
+                        "save failed",
+                        "session",
+                        None,
+                    ));
+                }
+            }
+        }
+    }
+
+    pub fn poll_frecency_events(&mut self) {
+        while let Some(event) = self.frecency_worker.poll() {
+            self.handle_frecency_event(event);
+        }
+    }
+
+    fn handle_frecency_event(&mut self, event: FrecencyEvent) {
+        match event {
+            FrecencyEvent::Saved { pruned } => self.frecency.forget(&pruned),
+            FrecencyEvent::SaveFailed(error) => {
+                self.push_user_notice(UserNotice::with_ttl_ms(
+                    UserNoticeLevel::Warn,
+                    format!("save failed: {error}"),
+                    "frecency",
+                    None,
+                ));
+            }
+        }
+    }
+
+    pub fn poll_config_events(&mut self) {
+        for event in poll_config_events() {
+            match event {
+                ConfigEvent::ConfigRootUnavailable => {
+                    self.slash_feedback = Some(self.timed_feedback(
+                        "config: not writable; set OX_CONFIG_HOME".to_string(),
+                        FeedbackStatus::Error,
+                    ));
+                }
+            }
+        }
+    }
+
+    pub fn poll_watch_events(&mut self) {
+        let Some(watcher) = self.fs_watcher.as_ref() else {
+            return;
+        };
+        if self.watched_dir.as_ref() != Some(&self.current_dir) {
+            let mut dirs = vec![self.current_dir.clone()];
+            dirs.extend(self.current_dir.parent().map(Path::to_path_buf));
+            watcher.watch(dirs);
+            self.watched_dir = Some(self.current_dir.clone());
+            self.watch_refresh_pending = false;
+        }
+        while let Some(event) = self.fs_watcher.as_ref().and_then(FsWatcher::poll) {
+            self.handle_watch_event(event);
+        }
+        if !self.watch_refresh_pending
+            || !self.search_buffer.is_empty()
+            || self.visual_range.is_some()
+        {
+            return;
+        }
+        self.watch_refresh_pending = false;
+        self.fs_changed = true;
+        self.reload_keeping_selection();
+    }
+
+    fn handle_watch_event(&mut self, event: WatchEvent) {
+        if self.current_dir.starts_with(&event.dir) {
+            self.watch_refresh_pending = true;
+        }
+    }
+
+    pub fn take_fs_changed(&mut self) -> bool {
+        std::mem::take(&mut self.fs_changed)
+    }
+
+    pub fn close_shell_output(&mut self) {
+        self.shell_output_active = false;
+    }
+
+    pub fn scroll_shell_output_up(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.scroll_up();
+        }
+    }
+
+    pub fn scroll_shell_output_down(&mut self) {
+        if self.shell_output_active {
+            self.shell_output_view.scroll_down();
+        }
+    }
+
+    pub fn append_search_char(&mut self, ch: char) {
+        if self.entries.is_empty() {
+            return;
+        }
+        if self.search_buffer.is_empty() {
+            self.search_origin = self.cursor;
+        }
+        self.search_buffer.push(ch);
+        self.apply_search();
+    }
+
+    pub fn backspace_search_char(&mut self) {
+        if self.search_buffer.is_empty() {
+            return;
+        }
+        self.search_buffer.pop();
+        if self.search_buffer.is_empty() {
+            self.restore_search_origin();
+            return;
+        }
+        self.apply_search();
+    }
+
+    pub fn search_mode(&self) -> SearchMode {
+        self.search_mode
+    }
+
+    pub fn search_next(&mut self) {
+        self.step_search(true);
+    }
+
+    pub fn search_prev(&mut self) {
+        self.step_search(false);
+    }
+
+    fn step_search(&mut self, forward: bool) {
+        let Some(matcher) = self.search_matcher() else {
+            return;
+        };
+        let hits = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| matcher.is_match(entry.display_name()))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let (Some(first), Some(last)) = (hits.first(), hits.last()) else {
+            return;
+        };
+        let cursor = self.cursor.unwrap_or(0);
+        let next = if forward {
+            hits.iter().find(|&&hit| hit > cursor).unwrap_or(first)
+        } else {
+            hits.iter().rev().find(|&&hit| hit < cursor).unwrap_or(last)
+        };
+        self.cursor = Some(*next);
+    }
+
+    pub fn reset_search(&mut self) {
+        if self.search_buffer.is_empty() {
+            return;
+        }
+        self.search_buffer.clear();
+        self.restore_search_origin();
+    }
+
+    fn refresh(&mut self) -> AppResult<()> {
+        self.refresh_with_selection(None)
+    }
+
+    fn reload_entries(&mut self) -> AppResult<()> {
+        let started = Instant::now();
+        let id = self.next_listing_id();
+        let request = self.listing_request(id, self.current_dir.clone());
+        self.listing_worker.request(request);
+        let parent = self.current_dir.parent().map(Path::to_path_buf);
+        let parent_id = self.next_listing_id();
+        match parent.as_ref() {
+            Some(parent) => {
+                let request = self.listing_request(parent_id, parent.clone());
+                self.parent_listing_worker.request(request);
+            }
+            None => self.parent_listing_worker.cancel(),
+        }
+        let ignore = self.listing_ignore(&self.current_dir);
+        let mut partial = Vec::new();
+        match self
+            .listing_worker
+            .wait(id, self.listing_budget, &mut partial)
+        {
+            Some(result) => {
+                self.listing = None;
+                self.set_entries(result?, ignore.as_ref());
+            }
+            None => {
+                let loaded = partial.len();
+                self.entries = self.visible_entries(partial, ignore.as_ref());
+                self.listing = Some(PendingListing {
+                    id,
+                    dir: self.current_dir.clone(),
+                    loaded,
+                    last_progress: self.clock.now(),
+                    focus: None,
+                    retried: false,
+                    ignore,
+                });
+            }
+        }
+        self.parent_listing = None;
+        self.parent_entries = Vec::new();
+        if let Some(parent) = parent {
+            let remaining = self.listing_budget.saturating_sub(started.elapsed());
+            let ignore = self.listing_ignore(&parent);
+            match self
+                .parent_listing_worker
+                .wait(parent_id, remaining, &mut Vec::new())
+            {
+                Some(result) => self.set_parent_entries(result?, ignore.as_ref()),
+                None => {
+                    self.parent_listing = Some(PendingListing {
+                        id: parent_id,
+                        dir: parent,
+                        loaded: 0,
+                        last_progress: self.clock.now(),
+                        focus: None,
+                        retried: false,
+                        ignore,
+                    });
+                }
+            }
+        }
+        self.clear_search_state();
+        if self.listing.is_none() {
+            self.prune_marks();
+        } else {
+            self.visual_range = None;
+        }
+        Ok(())
+    }
+
+    fn next_listing_id(&mut self) -> u64 {
+        self.listing_request_id += 1;
+        self.listing_request_id
+    }
+
+    fn listing_request(&self, id: u64, dir: PathBuf) -> ListingRequest {
+        ListingRequest {
+            id,
+            dir,
+            show_hidden: self.show_hidden,
+            sort: self.tabs.active_sort(),
+        }
+    }
+
+    fn listing_ignore(&self, dir: &Path) -> Option<GitIgnore> {
+        self.hide_ignored.then(|| GitIgnore::for_dir(dir))
+    }
+
+    fn set_parent_entries(&mut self, mut entries: Vec<Entry>, ignore: Option<&GitIgnore>) {
+        if let (Some(ignore), Some(parent)) = (ignore, self.current_dir.parent()) {
+            retain_unignored(parent, ignore, &mut entries);
+        }
+        self.parent_entries = entries;
+    }
+
+    fn visible_entries(&self, mut entries: Vec<Entry>, ignore: Option<&GitIgnore>) -> Vec<Entry> {
+        if let Some(ignore) = ignore {
+            retain_unignored(&self.current_dir, ignore, &mut entries);
+        }
+        if let Some(filter) = self.tabs.active_filter() {
+            entries.retain(|entry| filter.matches(&entry.name));
+        }
+        entries
+    }
+
+    fn set_entries(&mut self, entries: Vec<Entry>, ignore: Option<&GitIgnore>) {
+        self.entries = self.visible_entries(entries, ignore);
+        if self.tree_enabled() {
+            self.tree_roots = std::mem::take(&mut self.entries);
+            self.request_expanded_children();
+            self.entries = self.flatten_tree();
+        }
+    }
+
+    fn restore_cursor(&mut self, focus_name: Option<&str>, selected_index: Option<usize>) {
+        self.cursor = resolve_cursor(&self.entries, focus_name, selected_index);
+        // 展開中の子はあとから届くので、まだ並んでいない行はそのときに選び直す。
+        self.tree_focus = focus_name
+            .filter(|_| self.tree_enabled() && !self.tree_pending.is_empty())
+            .filter(|focus| self.entries.iter().all(|entry| entry.name != *focus))
+            .map(str::to_string);
+        if let Some(listing) = self.listing.as_mut() {
+            listing.focus = focus_name.map(str::to_string);
+        }
+    }
+
+    pub fn listing_progress(&self) -> Option<usize> {
+        self.listing.as_ref().map(|listing| listing.loaded)
+    }
+
+    pub fn poll_listing_events(&mut self) {
+        self.poll_parent_listing_events();
+        while let Some(event) = self.listing_worker.poll() {
+            self.handle_listing_event(event);
+        }
+        if let Some(listing) = self.listing.as_ref()
+            && self.clock.now().duration_since(listing.last_progress) >= LISTING_TIMEOUT
+        {
+            let text = listing_timeout_text(listing);
+            self.listing_worker.restart();
+            if listing.retried {
+                self.listing = None;
+            } else {
+                let focus = listing
+                    .focus
+                    .clone()
+                    .or_else(|| self.selected_entry().map(|entry| entry.name.clone()));
+                let ignore = listing.ignore.clone();
+                let id = self.next_listing_id();
+                let request = self.listing_request(id, self.current_dir.clone());
+                self.listing_worker.request(request);
+                self.listing = Some(PendingListing {
+                    id,
+                    dir: self.current_dir.clone(),
+                    loaded: 0,
+                    last_progress: self.clock.now(),
+                    focus,
+                    retried: true,
+                    ignore,
+                });
+                self.entries.clear();
+            }
+            self.push_user_notice(UserNotice::new(UserNoticeLevel::Error, text, "listing"));
+        }
+    }
+
+    fn handle_listing_event(&mut self, event: ListingEvent) {
+        let Some(listing) = self.listing.as_ref() else {
+            return;
+        };
+        let focus = listing
+            .focus
+            .clone()
+            .or_else(|| self.selected_entry().map(|entry| entry.name.clone()));
+        match event {
+            ListingEvent::Chunk { id, entries } if id == listing.id => {
+                let loaded = listing.loaded + entries.len();
+                let visible = self.visible_entries(entries, listing.ignore.as_ref());
+                self.entries.extend(visible);
+                self.cursor = resolve_cursor(&self.entries, focus.as_deref(), self.cursor);
+                let found = self.selected_entry().map(|entry| &entry.name) == focus.as_ref();
+                let now = self.clock.now();
+                if let Some(listing) = self.listing.as_mut() {
+                    listing.loaded = loaded;
+                    listing.last_progress = now;
+                    if found {
+                        listing.focus = None;
+                    }
+                }
+            }
+            ListingEvent::Finished { id, result } if id == listing.id => {
+                let ignore = self.listing.take().and_then(|listing| listing.ignore);
+                match result {
+                    Ok(entries) => {
+                        self.set_entries(entries, ignore.as_ref());
+                        self.prune_marks();
+                        self.cursor = resolve_cursor(&self.entries, focus.as_deref(), None);
+                    }
+                    Err(error) => self.push_user_notice(UserNotice::new(
+                        UserNoticeLevel::Error,
+                        error.to_string(),
+                        "listing",
+                    )),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn poll_parent_listing_events(&mut self) {
+        while let Some(event) = self.parent_listing_worker.poll() {
+            self.handle_parent_listing_event(event);
+        }
+        if let Some(listing) = self.parent_listing.as_ref()
+            && self.clock.now().duration_since(listing.last_progress) >= LISTING_TIMEOUT
+        {
+            let text = listing_timeout_text(listing);
+            self.parent_listing_worker.restart();
+            if listing.retried {
+                self.parent_listing = None;
+            } else {
+                let dir = listing.dir.clone();
+                let ignore = listing.ignore.clone();
+                let id = self.next_listing_id();
+                let request = self.listing_request(id, dir.clone());
+                self.parent_listing_worker.request(request);
+                self.parent_listing = Some(PendingListing {
+                    id,
+                    dir,
+                    loaded: 0,
+                    last_progress: self.clock.now(),
+                    focus: None,
+                    retried: true,
+                    ignore,
+                });
+            }
+            self.push_user_notice(UserNotice::new(UserNoticeLevel::Error, text, "listing"));
+        }
+    }
+
+    fn handle_parent_listing_event(&mut self, event: ListingEvent) {
+        let Some(listing) = self.parent_listing.as_mut() else {
+            return;
+        };
+        match event {
+            ListingEvent::Chunk { id, entries } if id == listing.id => {
+                listing.loaded += entries.len();
+                listing.last_progress = self.clock.now();
+            }
+            ListingEvent::Finished { id, result } if id == listing.id => {
+                let ignore = self
+                    .parent_listing
+                    .take()
+                    .and_then(|listing| listing.ignore);
+                match result {
+                    Ok(entries) => self.set_parent_entries(entries, ignore.as_ref()),
+                    Err(error) => self.push_user_notice(UserNotice::new(
+                        UserNoticeLevel::Error,
+                        error.to_string(),
+                        "listing",
+                    )),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn reload_keeping_selection(&mut self) {
+        let selected_name = self.selected_entry().map(|entry| entry.name.clone());
+        let selected_index = self.cursor;
+        match self.reload_entries() {
+            Ok(()) => self.restore_cursor(selected_name.as_deref(), selected_index),
+            Err(error) => {
+                self.push_user_notice(UserNotice::new(
+                    UserNoticeLevel::Error,
+                    error.to_string(),
+                    "refresh",
+                ));
+            }
+        }
+    }
+
+    fn refresh_with_selection(&mut self, focus_name: Option<&str>) -> AppResult<()> {
+        self.reload_entries()?;
+        self.restore_cursor(focus_name, None);
+        Ok(())
+    }
+
+    fn apply_search(&mut self) {
+        let Some(matcher) = self.search_matcher() else {
+            return;
+        };
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| matcher.is_match(entry.display_name()))
+        {
+            self.cursor = Some(index);
+        }
+    }
+
+    fn search_matcher(&self) -> Option<SearchMatcher> {
+        SearchMatcher::new(self.search_mode, &self.search_buffer)
+    }
+
+    fn clear_search_state(&mut self) {
+        self.search_buffer.clear();
+        self.search_origin = None;
+    }
+
+    fn restore_search_origin(&mut self) {
+        let origin = self.search_origin.take();
+        self.cursor = match origin {
+            Some(index) => clamp_cursor(&self.entries, index),
+            None => clamp_cursor(&self.entries, 0).or(self.cursor),
+        };
+    }
+
+    fn set_current_dir(&mut self, path: PathBuf) {
+        self.current_dir = path;
+    }
+
+    fn change_dir(&mut self, path: PathBuf) {
+        if path != self.current_dir {
+            let from = self.history_entry();
+            self.tabs.record_visit(from);
+        }
+        self.enter_dir(path);
+    }
+
+    fn enter_dir(&mut self, path: PathBuf) {
+        self.set_current_dir(path);
+        self.frecency.record(&self.current_dir, unix_now());
+        self.frecency_save_deadline = Some(self.clock.now() + FRECENCY_SAVE_DEBOUNCE);
+        self.tabs.update_active_path(self.current_dir.as_path());
+        self.handle_tab_events();
+    }
+
+    fn switch_to_tab(&mut self, index: usize) -> AppResult<()> {
+        let Some(next_dir) = self.tabs.switch_to(index, self.current_dir.as_path()) else {
+            return Ok(());
+        };
+        self.set_current_dir(next_dir);
+        self.announce_active_tab_color();
+        self.refresh()?;
+        self.handle_tab_events();
+        Ok(())
+    }
+
+    fn handle_slash_command(&mut self, command: &SlashCommand) -> SlashFeedback {
+        match command.name.as_str() {
+            "preview" => self.handle_preview_command(&command.args),
+            "tab" => self.handle_tab_command(&command.args),
+            "color" => self.handle_color_command(&command.args),
+            "sort" => self.handle_sort_command(&command.args),
+            "filter" => self.handle_filter_command(&command.args),
+            "where" => self.handle_where_command(&command.args),
+            "grep" => self.handle_grep_command(&command.args),
+            "bookmark" => self.handle_bookmark_command(&command.args),
+            "go" => self.handle_go_command(&command.args),
+            "z" => self.handle_z_command(&command.args),
+            "cwd" => self.handle_cwd_command(&command.args),
+            "tree" => self.handle_tree_command(&command.args),
+            "paste" => self.handle_paste_command(&command.args),
+            "yank" => self.handle_register_command(&command.args, RegisterKind::Yank),
+            "cut" => self.handle_register_command(&command.args, RegisterKind::Cut),
+            "path" => self.handle_path_command(&command.args),
+            "clip" => self.handle_clip_command(&command.args),
+            "copy" => self.handle_copy_command(&command.args),
+            "select" => self.handle_select_command(&command.args),
+            "rename" => self.handle_rename_command(&command.args),
+            "bulkrename" => self.handle_bulkrename_command(&command.args),
+            "move" => self.handle_move_command(&command.args),
+            "mkdir" => self.handle_mkdir_command(&command.args),
+            "touch" => self.handle_touch_command(&command.args),
+            "delete" => self.handle_delete_command(&command.args),
+            "trash" => self.handle_trash_command(&command.args),
+            "undo" => self.handle_undo_command(&command.args, true),
+            "redo" => self.handle_undo_command(&command.args, false),
+            "shell" => self.handle_shell_command(command),
+            _ => self.timed_feedback(
+                format!("unknown command: {}", command.name),
+                FeedbackStatus::Error,
+            ),
+        }
+    }
+
+    fn handle_preview_command(&mut self, args: &[String]) -> SlashFeedback {
+        match args {
+            [] => {
+                let next = !self.preview_visible;
+                self.preview_visible = next;
+                self.preview_paused = !next;
+                self.preview_feedback(next)
+            }
+            [arg] if arg == "show" => {
+                self.preview_visible = true;
+                self.preview_paused = false;
+                self.preview_feedback(true)
+            }
+            [arg] if arg == "hide" => {
+                self.preview_visible = false;
+                self.preview_paused = true;
+                self.preview_feedback(false)
+            }
+            _ => self.timed_feedback("preview: invalid args".to_string(), FeedbackStatus::Error),
+        }
+    }
+
+    fn handle_tab_command(&mut self, args: &[String]) -> SlashFeedback {
+        match args {
+            [] => self.tab_list_feedback(),
+            [arg] if arg == "new" => match self.new_tab() {
+                Ok(()) => self.tab_list_feedback(),
+                Err(error) => self.tab_error_feedback(error),
+            },
+            [arg] if arg == "next" => match self.next_tab() {
+                Ok(()) => self.tab_list_feedback(),
+                Err(error) => self.tab_error_feedback(error),
+            },
+            [arg] if arg == "prev" => match self.prev_tab() {
+                Ok(()) => self.tab_list_feedback(),
+                Err(error) => self.tab_error_feedback(error),
+            },
+            [arg] => match arg.parse::<usize>() {
+                Ok(number) if number >= 1 && number <= self.tab_count() => {
+                    match self.switch_to_tab(number.saturating_sub(1)) {
+                        Ok(()) => self.tab_list_feedback(),
+                        Err(error) => self.tab_error_feedback(error),
+                    }
+                }
+                _ => self.timed_feedback("tab: invalid args".to_string(), FeedbackStatus::Error),
+            },
+            _ => self.timed_feedback("tab: invalid args".to_string(), FeedbackStatus::Error),
+        }
+    }
+
+    fn handle_sort_command(&mut self, args: &[String]) -> SlashFeedback {
+        if args.is_empty() {
+            let label = self.tabs.active_sort().label();
+            return self.timed_feedback(format!("sort: {label}"), FeedbackStatus::Success);
+        }
+        let Some(sort) = SortMode::parse(&args.join(" ")) else {
+            return self.timed_feedback("sort: invalid args".to_string(), FeedbackStatus::Error);
+        };
+        self.tabs.set_active_sort(sort);
+        self.handle_tab_events();
+        self.reload_keeping_selection();
+        self.timed_feedback(format!("sort: {}", sort.label()), FeedbackStatus::Success)
+    }
+
+    fn handle_filter_command(&mut self, args: &[String]) -> SlashFeedback {
+        let filter = match args {
+            [] => {
+                let label = self.filter_label().unwrap_or_else(|| "none".to_string());
+                return self.timed_feedback(format!("filter: {label}"), FeedbackStatus::Success);
+            }
+            [arg] if arg == "clear" => None,
+            [arg, pattern @ ..] if arg == "regex" && !pattern.is_empty() => {
+                match EntryFilter::regex(&pattern.join(" ")) {
+                    Ok(filter) => Some(filter),
+                    Err(error) => {
+                        return self
+                            .timed_feedback(format!("filter: {error}"), FeedbackStatus::Error);
+                    }
+                }
+            }
+            _ => Some(EntryFilter::glob(&args.join(" "))),
+        };
+        self.tabs.set_active_filter(filter);
+        self.reload_keeping_selection();
+        let text = match self.filter_label() {
+            Some(label) => format!("filter: {label} ({} shown)", self.entries.len()),
+            None => "filter: cleared".to_string(),
+        };
+        self.timed_feedback(text, FeedbackStatus::Success)
+    }
+
+    fn handle_where_command(&mut self, args: &[String]) -> SlashFeedback {
+        let query = args.join(" ");
+        if query.is_empty() {
+            return self.timed_feedback("where: missing name".to_string(), FeedbackStatus::Error);
+        }
+        let id = self.start_results(ResultsKind::Where, &query);
+        self.finder_worker.request(FindRequest {
+            id,
+            root: self.current_dir.clone(),
+            query: query.clone(),
+            show_hidden: self.show_hidden,
+            hide_ignored: self.hide_ignored,
+            max_depth: SEARCH_MAX_DEPTH,
+        });
+        self.timed_feedback(format!("where: searching {query}"), FeedbackStatus::Success)
+    }
+
+    fn handle_grep_command(&mut self, args: &[String]) -> SlashFeedback {
+        let query = args.join(" ");
+        if query.is_empty() {
+            return self.timed_feedback("grep: missing pattern".to_string(), FeedbackStatus::Error);
+        }
+        let ignore_case = !query.chars().any(char::is_uppercase);
+        let pattern = match Regex::with_case(&query, ignore_case) {
+            Ok(pattern) => pattern,
+            Err(error) => {
+                return self.timed_feedback(format!("grep: {error}"), FeedbackStatus::Error);
+            }
+        };
+        let id = self.start_results(ResultsKind::Grep, &query);
+        self.grep_worker.request(GrepRequest {
+            id,
+            root: self.current_dir.clone(),
+            pattern,
+            show_hidden: self.show_hidden,
+            hide_ignored: self.hide_ignored,
+            max_depth: SEARCH_MAX_DEPTH,
+            max_file_bytes: GREP_MAX_FILE_BYTES,
+        });
+        self.timed_feedback(format!("grep: searching {query}"), FeedbackStatus::Success)
+    }
+
+    fn handle_bookmark_command(&mut self, args: &[String]) -> SlashFeedback {
+        match args {
+            [] => {
+                self.open_bookmark_picker();
+                let count = self.bookmarks.iter().count();
+                self.timed_feedback(format!("bookmark: {count} saved"), FeedbackStatus::Success)
+            }
+            [arg, name] if arg == "add" => {
+                let path = self.current_dir.clone();
+                match self.bookmarks.add(name, &path) {
+                    Ok(()) => self.timed_feedback(
+                        format!("bookmark: {name} -> {}", path.display()),
+                        FeedbackStatus::Success,
+                    ),
+                    Err(error) => {
+                        self.timed_feedback(format!("bookmark: {error}"), FeedbackStatus::Error)
+                    }
+                }
+            }
+            [arg] if arg == "rm" => {
+                let path = self.current_dir.clone();
+                match self.bookmarks.remove_path(&path) {
+                    Ok(names) => self.timed_feedback(
+                        format!("bookmark: removed {}", names.join(", ")),
+                        FeedbackStatus::Success,
+                    ),
+                    Err(error) => {
+                        self.timed_feedback(format!("bookmark: {error}"), FeedbackStatus::Error)
+                    }
+                }
+            }
+            [arg, name] if arg == "rm" => match self.bookmarks.remove(name) {
+                Ok(_) => self
+                    .timed_feedback(format!("bookmark: removed {name}"), FeedbackStatus::Success),
+                Err(error) => {
+                    self.timed_feedback(format!("bookmark: {error}"), FeedbackStatus::Error)
+                }
+            },
+            _ => self.timed_feedback("bookmark: invalid args".to_string(), FeedbackStatus::Error),
+        }
+    }
+
+    fn handle_go_command(&mut self, args: &[String]) -> SlashFeedback {
+        match args {
+            [name] => self.go_to_bookmark(name),
+            [] => self.timed_feedback("go: missing name".to_string(), FeedbackStatus::Error),
+            _ => self.timed_feedback("go: invalid args".to_string(), FeedbackStatus::Error),
+        }
+    }
+
+    fn go_to_bookmark(&mut self, name: &str) -> SlashFeedback {
+        let Some(path) = self.bookmarks.get(name).map(Path::to_path_buf) else {
+            return self.timed_feedback(format!("go: no bookmark: {name}"), FeedbackStatus::Error);
+        };
+        if !path.is_dir() {
+            return self.timed_feedback(
+                format!("go: not a directory: {}", path.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        self.change_dir(path.clone());
+        if let Err(error) = self.refresh() {
+            return self.timed_feedback(format!("go: {error}"), FeedbackStatus::Error);
+        }
+        self.timed_feedback(format!("go: {}", path.display()), FeedbackStatus::Success)
+    }
+
+    fn handle_cwd_command(&mut self, args: &[String]) -> SlashFeedback {
+        let raw = args.join(" ");
+        if raw.is_empty() {
+            return self.timed_feedback(
+                format!("cwd: {}", self.current_dir.display()),
+                FeedbackStatus::Success,
+            );
+        }
+        let path = match expand_path(&raw, &self.current_dir) {
+            Ok(path) => path,
+            Err(error) => {
+                return self.timed_feedback(format!("cwd: {error}"), FeedbackStatus::Error);
+            }
+        };
+        if !path.is_dir() {
+            let reason = if path.exists() {
+                "not a directory"
+            } else {
+                "no such directory"
+            };
+            return self.timed_feedback(
+                format!("cwd: {reason}: {}", path.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        if let Err(error) = std::fs::read_dir(&path) {
+            return self.timed_feedback(
+                format!("cwd: {}: {error}", path.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        self.change_dir(path.clone());
+        if let Err(error) = self.refresh() {
+            return self.timed_feedback(format!("cwd: {error}"), FeedbackStatus::Error);
+        }
+        self.timed_feedback(format!("cwd: {}", path.display()), FeedbackStatus::Success)
+    }
+
+    fn handle_z_command(&mut self, args: &[String]) -> SlashFeedback {
+        if args.is_empty() {
+            return self.timed_feedback("z: missing query".to_string(), FeedbackStatus::Error);
+        }
+        let Some(path) = self
+            .frecency
+            .matches(args, &self.current_dir, unix_now())
+            .into_iter()
+            .find(|path| path.is_dir())
+        else {
+            return self.timed_feedback(
+                format!("z: no match: {}", args.join(" ")),
+                FeedbackStatus::Error,
+            );
+        };
+        self.change_dir(path.clone());
+        if let Err(error) = self.refresh() {
+            return self.timed_feedback(format!("z: {error}"), FeedbackStatus::Error);
+        }
+        self.timed_feedback(format!("z: {}", path.display()), FeedbackStatus::Success)
+    }
+
+    fn handle_color_command(&mut self, args: &[String]) -> SlashFeedback {
+        let name = args.join(" ").trim().to_string();
+        if name.is_empty() {
+            return self.timed_feedback("color: missing theme".to_string(), FeedbackStatus::Error);
+        }
+        let Some(theme_id) = ColorThemeId::from_name(&name) else {
+            return self.timed_feedback("color: unknown theme".to_string(), FeedbackStatus::Error);
+        };
+        let context = CommandContext::Tab(self.tabs.active_tab_id());
+        let preference = self.tabs.set_active_theme(theme_id);
+        let theme = theme_id.theme();
+        self.tab_color_changed = Some(TabColorChanged {
+            tab_id: preference.tab_id,
+            theme,
+        });
+        self.handle_tab_events();
+        match context {
+            CommandContext::Tab(_) => {
+                self.timed_feedback(format!("theme: {}", theme.name), FeedbackStatus::Success)
+            }
+        }
+    }
+
+    fn handle_shell_command(&mut self, command: &SlashCommand) -> SlashFeedback {
+        if !self.shell_permission.is_allowed() {
+            self.push_user_notice(UserNotice::new(
+                UserNoticeLevel::Warn,
+                "Shell commands disabled",
+                "shell",
+            ));
+            return self
+                .timed_feedback("Shell commands disabled".to_string(), FeedbackStatus::Warn);
+        }
+        let raw = command
+            .raw
+            .strip_prefix("/shell")
+            .unwrap_or(command.raw.as_str())
+            .trim_start();
+        let request = match ShellCommandRequest::new(self.current_dir.clone(), raw) {
+            Ok(request) => request.with_targets(&self.action_target_names()),
+            Err(error) => return self.shell_error_feedback(error),
+        };
+        let notice = format_shell_start_notice(&request.raw_command);
+        self.shell_output_view.start(&request);
+        self.shell_worker.request(request);
+        self.push_user_notice(UserNotice::new(UserNoticeLevel::Info, notice, "shell"));
+        self.timed_feedback("shell: running".to_string(), FeedbackStatus::Success)
+    }
+
+    fn handle_register_command(&mut self, args: &[String], kind: RegisterKind) -> SlashFeedback {
+        let label = kind.label();
+        if !args.is_empty() {
+            return self.timed_feedback(format!("{label}: invalid args"), FeedbackStatus::Error);
+        }
+        let paths = self.action_targets();
+        if paths.is_empty() {
+            return self.timed_feedback(format!("{label}: no selection"), FeedbackStatus::Warn);
+        }
+        let count = paths.len();
+        self.register = Some(Register { kind, paths });
+        self.clear_marks();
+        self.timed_feedback(
+            format!("{label}: {count} items in register"),
+            FeedbackStatus::Success,
+        )
+    }
+
+    fn handle_paste_command(&mut self, args: &[String]) -> SlashFeedback {
+        if args.is_empty() {
+            return self.paste_register();
+        }
+        let sources = args
+            .iter()
+            .map(|arg| self.current_dir.join(arg))
+            .collect::<Vec<_>>();
+        if let Some(missing) = sources.iter().find(|path| path.symlink_metadata().is_err()) {
+            return self.timed_feedback(
+                format!("paste: not found: {}", missing.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        self.begin_transfer(TransferKind::Paste, sources, self.current_dir.clone())
+    }
+
+    fn handle_path_command(&mut self, args: &[String]) -> SlashFeedback {
+        let mut relative = false;
+        let mut lines = false;
+        for arg in args {
+            match arg.as_str() {
+                "relative" => relative = true,
+                "lines" => lines = true,
+                _ => {
+                    return self
+                        .timed_feedback("path: invalid args".to_string(), FeedbackStatus::Error);
+                }
+            }
+        }
+        let targets = self.action_targets();
+        if targets.is_empty() {
+            return self.timed_feedback("path: no selection".to_string(), FeedbackStatus::Warn);
+        }
+        let paths = targets
+            .iter()
+            .map(|path| {
+                let shown = if relative {
+                    path.strip_prefix(&self.current_dir).unwrap_or(path)
+                } else {
+                    path.as_path()
+                };
+                shown.display().to_string()
+            })
+            .collect::<Vec<_>>();
+        let text = paths.join(if lines { "\n" } else { " " });
+        let message = match paths.as_slice() {
+            [path] => format!("path: copied {path}"),
+            _ => format!("path: copied {} paths", paths.len()),
+        };
+        self.clipboard_request = Some(text);
+        self.timed_feedback(message, FeedbackStatus::Success)
+    }
+
+    fn handle_clip_command(&mut self, args: &[String]) -> SlashFeedback {
+        if !args.is_empty() {
+            return self.timed_feedback("clip: invalid args".to_string(), FeedbackStatus::Error);
+        }
+        let targets = self.action_targets();
+        let [path] = targets.as_slice() else {
+            return self.timed_feedback(
+                "clip: select a single file".to_string(),
+                FeedbackStatus::Warn,
+            );
+        };
+        let metadata = match std::fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => metadata,
+            Ok(_) => {
+                return self.timed_feedback(
+                    format!("clip: not a file: {}", path.display()),
+                    FeedbackStatus::Error,
+                );
+            }
+            Err(error) => {
+                return self.timed_feedback(format!("clip: {error}"), FeedbackStatus::Error);
+            }
+        };
+        if metadata.len() > CLIPBOARD_MAX_BYTES {
+            return self.timed_feedback(
+                format!("clip: file is larger than {CLIPBOARD_MAX_BYTES} bytes"),
+                FeedbackStatus::Error,
+            );
+        }
+        let text = match std::fs::read(path).map(String::from_utf8) {
+            Ok(Ok(text)) => text,
+            Ok(Err(_)) => {
+                return self
+                    .timed_feedback("clip: not a text file".to_string(), FeedbackStatus::Error);
+            }
+            Err(error) => {
+                return self.timed_feedback(format!("clip: {error}"), FeedbackStatus::Error);
+            }
+        };
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let bytes = text.len();
+        self.clipboard_request = Some(text);
+        self.timed_feedback(
+            format!("clip: copied {name} ({bytes} bytes)"),
+            FeedbackStatus::Success,
+        )
+    }
+
+    fn paste_register(&mut self) -> SlashFeedback {
+        let Some(register) = self.register.clone() else {
+            return self
+                .timed_feedback("paste: nothing to paste".to_string(), FeedbackStatus::Warn);
+        };
+        if let Some(missing) = register
+            .paths
+            .iter()
+            .find(|path| path.symlink_metadata().is_err())
+        {
+            return self.timed_feedback(
+                format!("paste: not found: {}", missing.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        let kind = match register.kind {
+            RegisterKind::Yank => TransferKind::Paste,
+            RegisterKind::Cut => TransferKind::Move,
+        };
+        self.begin_transfer(kind, register.paths, self.current_dir.clone())
+    }
+
+    fn handle_rename_command(&mut self, args: &[String]) -> SlashFeedback {
+        let new_name = args.join(" ");
+        if new_name.is_empty() {
+            return self.timed_feedback("rename: missing name".to_string(), FeedbackStatus::Error);
+        }
+        let Some(selected) = self.selected_entry().map(|entry| entry.name.clone()) else {
+            return self.timed_feedback("rename: no selection".to_string(), FeedbackStatus::Warn);
+        };
+        let source = self.current_dir.join(&selected);
+        let focus = match Path::new(&selected).parent() {
+            Some(prefix) if !prefix.as_os_str().is_empty() => {
+                prefix.join(&new_name).to_string_lossy().to_string()
+            }
+            _ => new_name.clone(),
+        };
+        match rename_entry(&source, &new_name) {
+            Ok(target) => {
+                self.record_journal(
+                    format!("rename {selected} -> {new_name}"),
+                    JournalOp::Move {
+                        items: vec![JournalPath::new(source.clone(), target)],
+                    },
+                );
+                if let Err(error) = self.refresh_with_selection(Some(&focus)) {
+                    return self.timed_feedback(format!("rename: {error}"), FeedbackStatus::Error);
+                }
+                self.timed_feedback(
+                    format!("rename: {selected} -> {new_name}"),
+                    FeedbackStatus::Success,
+                )
+            }
+            Err(error) => self.file_op_error_feedback("rename", error),
+        }
+    }
+
+    fn handle_bulkrename_command(&mut self, args: &[String]) -> SlashFeedback {
+        if !args.is_empty() {
+            return self.timed_feedback(
+                "bulkrename: invalid args".to_string(),
+                FeedbackStatus::Error,
+            );
+        }
+        let originals = self
+            .entries
+            .iter()
+            .filter(|entry| self.mark_count() == 0 || self.is_marked(&entry.name))
+            .map(|entry| entry.name.clone())
+            .collect::<Vec<_>>();
+        if originals.is_empty() {
+            return self.timed_feedback("bulkrename: no entries".to_string(), FeedbackStatus::Warn);
+        }
+        if originals
+            .iter()
+            .any(|name| Path::new(name).components().count() > 1)
+        {
+            return self.timed_feedback(
+                "bulkrename: nested tree rows are not supported".to_string(),
+                FeedbackStatus::Error,
+            );
+        }
+        let buffer_path =
+            std::env::temp_dir().join(format!("ox-bulkrename-{}.txt", Uuid::now_v7()));
+        if let Err(error) = std::fs::write(&buffer_path, build_bulk_rename_buffer(&originals)) {
+            return self.timed_feedback(format!("bulkrename: {error}"), FeedbackStatus::Error);
+        }
+        let count = originals.len();
+        self.bulk_rename = Some(BulkRenameSession {
+            dir: self.current_dir.clone(),
+            originals,
+            buffer_path,
+            launched: false,
+        });
+        self.timed_feedback(
+            format!("bulkrename: editing {count} names"),
+            FeedbackStatus::Success,
+        )
+    }
+
+    fn apply_bulk_rename_plan(&mut self, plan: &BulkRenamePlan) -> SlashFeedback {
+        let result = apply_bulk_rename(plan);
+        let moved = match &result {
+            Ok(_) => plan
+                .renames
+                .iter()
+                .map(|rename| (plan.dir.join(&rename.from), plan.dir.join(&rename.to)))
+                .collect(),
+            Err(failure) => failure.moved.clone(),
+        };
+        if !moved.is_empty() {
+            let items = moved
+                .into_iter()
+                .map(|(from, to)| JournalPath::new(from, to))
+                .collect::<Vec<_>>();
+            self.record_journal(
+                format!("bulkrename {} entries", items.len()),
+                JournalOp::Move { items },
+            );
+        }
+        self.clear_marks();
+        self.reload_keeping_selection();
+        match result {
+            Ok(count) => self.timed_feedback(
+                format!("bulkrename: renamed {count} entries"),
+                FeedbackStatus::Success,
+            ),
+            Err(failure) => self.timed_feedback(
+                format!(
+                    "bulkrename: renamed {} entries, then failed: {}",
+                    failure.renamed, failure.error
+                ),
+                FeedbackStatus::Error,
+            ),
+        }
+    }
+
+    fn handle_move_command(&mut self, args: &[String]) -> SlashFeedback {
+        let dest = args.join(" ");
+        if dest.is_empty() {
+            return self.timed_feedback("move: missing dest".to_string(), FeedbackStatus::Error);
+        }
+        let targets = self.action_targets();
+        if self.selected_entry().is_none() {
+            return self.timed_feedback("move: no selection".to_string(), FeedbackStatus::Warn);
+        }
+        let dest_path = self.current_dir.join(&dest);
+        if targets.len() > 1 && !dest_path.is_dir() {
+            return self.timed_feedback(
+                format!("move: not a directory: {}", dest_path.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        self.begin_transfer(TransferKind::Move, targets, dest_path)
+    }
+
+    fn execute_move(
+        &mut self,
+        targets: Vec<PathBuf>,
+        dest_path: PathBuf,
+        overrides: Vec<ConflictOverride>,
+    ) -> SlashFeedback {
+        if targets.is_empty() {
+            return self.timed_feedback("move: nothing to move".to_string(), FeedbackStatus::Warn);
+        }
+        let selected = self
+            .selected_entry()
+            .map(|entry| entry.name.clone())
+            .unwrap_or_default();
+        let selected_index = self.cursor;
+        let mut moved = Vec::new();
+        let mut crossing = Vec::new();
+        let mut last_error = None;
+        for source in &targets {
+            let action = overrides
+                .iter()
+                .find(|entry| entry.source == *source)
+                .map(|entry| &entry.action);
+            let result = match action {
+                Some(ConflictAction::Rename(target)) => {
+                    rename_path(source, target).map(|target| (target, None))
+                }
+                Some(ConflictAction::Replace) => resolve_move_target(source, &dest_path)
+                    .and_then(|target| move_replacing(source, &target, self.trash_store.as_ref()))
+                    .map(|(target, replaced)| (target, Some(replaced))),
+                None => move_entry(source, &dest_path).map(|target| (target, None)),
+            };
+            match result {
+                Ok((target, replaced)) => moved.push((source.clone(), target, replaced)),
+                Err(FileOpError::CrossesDevices(target)) => {
+                    crossing.push((source.clone(), target, action.cloned()));
+                }
+                Err(error) => {
+                    if targets.len() > 1 {
+                        self.push_user_notice(UserNotice::new(
+                            UserNoticeLevel::Error,
+                            format!("{}: {error}", source.display()),
+                            "move",
+                        ));
+                    }
+                    last_error = Some(error);
+                }
+            }
+        }
+        let crossing_count = crossing.len();
+        if let Some((_, target, _)) = crossing.first() {
+            let dest_dir = target
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| dest_path.clone());
+            let mut sources = Vec::new();
+            let mut overrides = Vec::new();
+            for (source, target, action) in crossing {
+                if action == Some(ConflictAction::Replace) {
+                    overrides.push(ConflictOverride {
+                        source: source.clone(),
+                        action: ConflictAction::Replace,
+                    });
+                }
+                overrides.push(ConflictOverride {
+                    source: source.clone(),
+                    action: ConflictAction::Rename(target),
+                });
+                sources.push(source);
+            }
+            self.start_copy(sources, dest_dir, overrides, true);
+        }
+        if moved.is_empty()
+            && crossing_count == 0
+            && let Some(error) = last_error
+        {
+            return self.file_op_error_feedback("move", error);
+        }
+        if !moved.is_empty() {
+            let items = moved
+                .iter()
+                .map(|(source, target, replaced)| {
+                    JournalPath::replacing(source.clone(), target.clone(), replaced.clone())
+                })
+                .collect::<Vec<_>>();
+            self.record_journal(
+                format!("move {} items", items.len()),
+                JournalOp::Move { items },
+            );
+        }
+        let focus = match moved.as_slice() {
+            [(_, target, _)] if target.parent() == Some(self.current_dir.as_path()) => target
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| selected.clone()),
+            _ => selected.clone(),
+        };
+        self.prune_cut_register();
+        self.clear_marks();
+        if let Err(error) = self.reload_entries() {
+            return self.timed_feedback(format!("move: {error}"), FeedbackStatus::Error);
+        }
+        self.restore_cursor(Some(&focus), selected_index);
+        if crossing_count > 0 {
+            let failed = targets.len() - moved.len() - crossing_count;
+            let text = match (moved.len(), failed) {
+                (0, 0) => format!("move: moving {crossing_count} items across devices"),
+                (done, 0) => {
+                    format!("move: moved {done} items, moving {crossing_count} across devices")
+                }
+                (done, failed) => format!(
+                    "move: moved {done} items, moving {crossing_count} across devices, {failed} failed"
+                ),
+            };
+            let status = if failed == 0 {
+                FeedbackStatus::Success
+            } else {
+                FeedbackStatus::Error
+            };
+            return self.timed_feedback(text, status);
+        }
+        match (moved.as_slice(), last_error) {
+            ([(source, target, _)], None) => self.timed_feedback(
+                format!(
+                    "move: {} -> {}",
+                    source
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    target.display()
+                ),
+                FeedbackStatus::Success,
+            ),
+            (_, None) => self.timed_feedback(
+                format!("move: moved {} items", moved.len()),
+                FeedbackStatus::Success,
+            ),
+            (_, Some(_)) => self.timed_feedback(
+                format!(
+                    "move: moved {} items, {} failed",
+                    moved.len(),
+                    targets.len() - moved.len()
+                ),
+                FeedbackStatus::Error,
+            ),
+        }
+    }
+
+    fn prune_cut_register(&mut self) {
+        if let Some(register) = self.register.as_mut()
+            && register.kind == RegisterKind::Cut
+        {
+            register
+                .paths
+                .retain(|path| path.symlink_metadata().is_ok());
+            if register.paths.is_empty() {
+                self.register = None;
+            }
+        }
+    }
+
+    fn handle_mkdir_command(&mut self, args: &[String]) -> SlashFeedback {
+        let raw = args.join(" ");
+        if raw.is_empty() {
+            return self.timed_feedback("mkdir: missing path".to_string(), FeedbackStatus::Error);
+        }
+        let path = self.current_dir.join(&raw);
+        if path.symlink_metadata().is_ok() {
+            return self.timed_feedback(
+                format!("mkdir: already exists: {}", path.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        let parents = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.symlink_metadata().is_err())
+            .count();
+        if let Err(error) = std::fs::create_dir_all(&path) {
+            return self.timed_feedback(format!("mkdir: {error}"), FeedbackStatus::Error);
+        }
+        self.record_journal(
+            format!("mkdir {raw}"),
+            JournalOp::Mkdir {
+                path: path.clone(),
+                parents,
+            },
+        );
+        self.focus_created(&path);
+        self.timed_feedback(format!("mkdir: created {raw}"), FeedbackStatus::Success)
+    }
+
+    fn handle_touch_command(&mut self, args: &[String]) -> SlashFeedback {
+        let raw = args.join(" ");
+        if raw.is_empty() {
+            return self.timed_feedback("touch: missing path".to_string(), FeedbackStatus::Error);
+        }
+        let path = self.current_dir.join(&raw);
+        let existed = path.symlink_metadata().is_ok();
+        let result = std::fs::File::options()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(std::time::SystemTime::now()));
+        if let Err(error) = result {
+            return self.timed_feedback(format!("touch: {error}"), FeedbackStatus::Error);
+        }
+        self.focus_created(&path);
+        let verb = if existed { "updated" } else { "created" };
+        self.timed_feedback(format!("touch: {verb} {raw}"), FeedbackStatus::Success)
+    }
+
+    fn focus_created(&mut self, path: &Path) {
+        let focus = path
+            .strip_prefix(&self.current_dir)
+            .ok()
+            .and_then(|relative| relative.components().next())
+            .map(|component| component.as_os_str().to_string_lossy().to_string());
+        match focus {
+            Some(name) => {
+                let _ = self.refresh_with_selection(Some(&name));
+            }
+            None => self.reload_keeping_selection(),
+        }
+    }
+
+    fn handle_copy_command(&mut self, args: &[String]) -> SlashFeedback {
+        let dest = args.join(" ");
+        if dest.is_empty() {
+            return self.timed_feedback("copy: missing dest".to_string(), FeedbackStatus::Error);
+        }
+        let targets = self.action_targets();
+        if targets.is_empty() {
+            return self.timed_feedback("copy: no selection".to_string(), FeedbackStatus::Warn);
+        }
+        let dest_dir = self.current_dir.join(&dest);
+        if !dest_dir.is_dir() {
+            return self.timed_feedback(
+                format!("copy: not a directory: {}", dest_dir.display()),
+                FeedbackStatus::Error,
+            );
+        }
+        self.clear_marks();
+        self.begin_transfer(TransferKind::Copy, targets, dest_dir)
+    }
+
+    fn handle_select_command(&mut self, args: &[String]) -> SlashFeedback {
+        match args {
+            [arg] if arg == "all" => self.select_all(),
+            [arg] if arg == "none" => self.clear_marks(),
+            [arg] if arg == "invert" => self.invert_marks(),
+            [] => {
+                return self
+                    .timed_feedback("select: missing pattern".to_string(), FeedbackStatus::Error);
+            }
+            _ => {
+                let pattern = args.join(" ");
+                if self.select_glob(&pattern) == 0 {
+                    return self.timed_feedback(
+                        format!("select: no match for {pattern}"),
+                        FeedbackStatus::Warn,
+                    );
+                }
+            }
+        }
+        self.timed_feedback(
+            format!("select: {} marked", self.mark_count()),
+            FeedbackStatus::Success,
+        )
+    }
+
+    fn handle_delete_command(&mut self, args: &[String]) -> SlashFeedback {
+        match args {
+            [] => self.trash_targets(),
+            [arg] if arg == "permanent" => {
+                self.request_permanent_delete();
+                self.timed_feedback("delete: confirm (y/N)".to_string(), FeedbackStatus::Warn)
+            }
+            _ => self.timed_feedback("delete: invalid args".to_string(), FeedbackStatus::Error),
+        }
+    }
+
+    fn handle_undo_command(&mut self, args: &[String], undo: bool) -> SlashFeedback {
+        let label = if undo { "undo" } else { "redo" };
+        if !args.is_empty() {
+            return self.timed_feedback(format!("{label}: invalid args"), FeedbackStatus::Error);
+        }
+        let trash = self.trash_store.clone();
+        let result = if undo {
+            self.journal.undo(trash.as_ref())
+        } else {
+            self.journal.redo(trash.as_ref())
+        };
+        match result {
+            Ok(entry) => {
+                self.reload_keeping_selection();
+                self.timed_feedback(format!("{label}: {entry}"), FeedbackStatus::Success)
+            }
+            Err(JournalError::Empty(_)) => {
+                self.timed_feedback(format!("{label}: nothing to {label}"), FeedbackStatus::Warn)
+            }
+            Err(error) => {
+                self.reload_keeping_selection();
+                self.timed_feedback(format!("{label}: {error}"), FeedbackStatus::Error)
+            }
+        }
+    }
+
+    fn record_journal(&mut self, label: String, op: JournalOp) {
+        if let Err(error) = self.journal.record(label, op) {
+            self.push_user_notice(UserNotice::new(
+                UserNoticeLevel::Warn,
+                format!("journal save failed: {error}"),
+                "journal",
+            ));
+        }
+    }
+
+    fn handle_trash_command(&mut self, args: &[String]) -> SlashFeedback {
+        if !args.is_empty() {
+            return self.timed_feedback("trash: invalid args".to_string(), FeedbackStatus::Error);
+        }
+        let Some(store) = self.trash_store.clone() else {
+            return self.timed_feedback("trash: unavailable".to_string(), FeedbackStatus::Error);
+        };
+        self.stop_results_search();
+        self.results_view = None;
+        self.open_trash_view_with(&store);
+        let count = self
+            .trash_view
+            .as_ref()
+            .map(|view| view.items.len())
+            .unwrap_or(0);
+        self.timed_feedback(format!("trash: {count} items"), FeedbackStatus::Success)
+    }
+
+    fn open_trash_view_with(&mut self, store: &TrashStore) {
+        let cursor = self
+            .trash_view
+            .as_ref()
+            .map(|view| view.cursor)
+            .unwrap_or(0);
+        let items = store.list();
+        let cursor = cursor.min(items.len().saturating_sub(1));
+        self.trash_view = Some(TrashView { items, cursor });
+    }
+
+    fn trash_targets(&mut self) -> SlashFeedback {
+        let Some(store) = self.trash_store.clone() else {
+            return self.timed_feedback("trash: unavailable".to_string(), FeedbackStatus::Error);
+        };
+        let targets = self.action_targets();
+        if targets.is_empty() {
+            return self.timed_feedback("delete: no selection".to_string(), FeedbackStatus::Warn);
+        }
+        let mut trashed = Vec::new();
+        let mut last_error = None;
+        for target in &targets {
+            match store.trash(target) {
+                Ok(item) => trashed.push(item),
+                Err(error) => {
+                    self.push_user_notice(UserNotice::new(
+                        UserNoticeLevel::Error,
+                        format!("{}: {error}", target.display()),
+                        "trash",
+                    ));
+                    last_error = Some(error);
+                }
+            }
+        }
+        let count = trashed.len();
+        if count > 0 {
+            self.record_journal(
+                format!("trash {count} items"),
+                JournalOp::Trash { items: trashed },
+            );
+        }
+        self.clear_marks();
+        self.reload_keeping_selection();
+        match last_error {
+            Some(error) if count == 0 => self.file_op_error_feedback("trash", error),
+            _ => self.timed_feedback(
+                format!("trash: moved {count} items"),
+                FeedbackStatus::Success,
+            ),
+        }
+    }
+
+    fn delete_permanently(&mut self, paths: &[PathBuf]) -> SlashFeedback {
+        let mut deleted = 0usize;
+        let mut failed = 0usize;
+        for path in paths {
+            match remove_path(path) {
+                Ok(()) => deleted += 1,
+                Err(error) => {
+                    failed += 1;
+                    self.push_user_notice(UserNotice::new(
+                        UserNoticeLevel::Error,
+                        format!("{}: {error}", path.display()),
+                        "delete",
+                    ));
+                }
+            }
+        }
+        self.clear_marks();
+        self.reload_keeping_selection();
+        if failed == 0 {
+            self.timed_feedback(
+                format!("delete: removed {deleted} items"),
+                FeedbackStatus::Success,
+            )
+        } else {
+            self.timed_feedback(
+                format!("delete: removed {deleted} items, {failed} failed"),
+                FeedbackStatus::Error,
+            )
+        }
+    }
+
+    fn action_targets(&self) -> Vec<PathBuf> {
+        let marked = self
+            .entries
+            .iter()
+            .filter(|entry| self.is_marked(&entry.name))
+            .map(|entry| self.current_dir.join(&entry.name))
+            .collect::<Vec<_>>();
+        if marked.is_empty() {
+            self.selected_entry_path().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    fn action_target_names(&self) -> Vec<String> {
+        self.action_targets()
+            .iter()
+            .filter_map(|path| {
+                path.strip_prefix(&self.current_dir)
+                    .ok()
+                    .filter(|relative| !relative.as_os_str().is_empty())
+                    .or_else(|| path.file_name().map(Path::new))
+            })
+            .map(|name| name.to_string_lossy().to_string())
+            .collect()
+    }
+
+    fn set_marks(&mut self, names: BTreeSet<String>) {
+        self.visual_range = None;
+        if let Some(marks) = self.tabs.active_marks_mut() {
+            *marks = names;
+        }
+    }
+
+    fn extend_selection(&mut self, step: fn(&mut Self)) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        let range = match self.visual_range.take() {
+            Some(range) if range.cursor == cursor => range,
+            _ => VisualRange {
+                anchor: cursor,
+                cursor,
+                base: self.marked_names().cloned().unwrap_or_default(),
+            },
+        };
+        step(self);
+        let next = self.cursor.unwrap_or(cursor);
+        let (start, end) = (range.anchor.min(next), range.anchor.max(next));
+        let mut marks = range.base.clone();
+        marks.extend(
+            self.entries
+                .iter()
+                .skip(start)
+                .take(end + 1 - start)
+                .map(|entry| entry.name.clone()),
+        );
+        if let Some(slot) = self.tabs.active_marks_mut() {
+            *slot = marks;
+        }
+        self.visual_range = Some(VisualRange {
+            cursor: next,
+            ..range
+        });
+    }
+
+    fn prune_marks(&mut self) {
+        let names = self
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<BTreeSet<_>>();
+        if let Some(marks) = self.tabs.active_marks_mut() {
+            marks.retain(|name| names.contains(name.as_str()));
+        }
+        self.visual_range = None;
+    }
+
+    fn start_copy(
+        &mut self,
+        sources: Vec<PathBuf>,
+        dest_dir: PathBuf,
+        overrides: Vec<ConflictOverride>,
+        remove_sources: bool,
+    ) {
+        self.copy_request_id = self.copy_request_id.wrapping_add(1);
+        self.copy_worker.request(CopyRequest {
+            id: self.copy_request_id,
+            sources,
+            dest_dir,
+            overrides,
+            trash: self.trash_store.clone(),
+            remove_sources,
+        });
+    }
+
+    fn begin_transfer(
+        &mut self,
+        kind: TransferKind,
+        sources: Vec<PathBuf>,
+        dest: PathBuf,
+    ) -> SlashFeedback {
+        let queue = sources
+            .iter()
+            .filter_map(|source| {
+                let target = match kind {
+                    TransferKind::Move => resolve_move_target(source, &dest).ok()?,
+                    TransferKind::Copy | TransferKind::Paste => dest.join(source.file_name()?),
+                };
+                let moving_in_place = kind == TransferKind::Move && target == *source;
+                (target.symlink_metadata().is_ok() && !moving_in_place)
+                    .then(|| (source.clone(), target))
+            })
+            .collect::<VecDeque<_>>();
+        let mut pending = PendingConflicts {
+            kind,
+            sources,
+            dest,
+            total: queue.len(),
+            queue,
+            overrides: Vec::new(),
+            apply_to_all: false,
+        };
+        if let Some(choice) = self.conflict_policy.choice() {
+            while let Some((source, target)) = pending.queue.pop_front() {
+                pending.decide(choice, source, &target);
+            }
+        }
+        if pending.queue.is_empty() {
+            return self.run_transfer(
+                pending.kind,
+                pending.sources,
+                pending.dest,
+                pending.overrides,
+            );
+        }
+        let label = pending.kind.label();
+        let count = pending.total;
+        self.pending_conflicts = Some(pending);
+        self.timed_feedback(
+            format!("{label}: {count} conflicts to resolve"),
+            FeedbackStatus::Warn,
+        )
+    }
+
+    fn run_transfer(
+        &mut self,
+        kind: TransferKind,
+        sources: Vec<PathBuf>,
+        dest: PathBuf,
+        overrides: Vec<ConflictOverride>,
+    ) -> SlashFeedback {
+        if kind == TransferKind::Move {
+            return self.execute_move(sources, dest, overrides);
+        }
+        let label = kind.label();
+        if sources.is_empty() {
+            return self.timed_feedback(format!("{label}: nothing to copy"), FeedbackStatus::Warn);
+        }
+        let count = sources.len();
+        self.start_copy(sources, dest, overrides, false);
+        self.timed_feedback(
+            format!("{label}: copying {count} items"),
+            FeedbackStatus::Success,
+        )
+    }
+
+    fn tab_list_feedback(&self) -> SlashFeedback {
+        let summaries = self.tabs.summaries();
+        SlashFeedback {
+            text: String::new(),
+            status: FeedbackStatus::Success,
+            tabs: Some(summaries),
+            expires_at: self.clock.now() + SLASH_FEEDBACK_TTL,
+        }
+    }
+
+    fn handle_tab_events(&mut self) {
+        let events = self.tabs.take_events();
+        if events.is_empty() {
+            return;
+        }
+        if events.iter().any(should_save_session) {
+            self.session_save_pending = true;
+            self.session_save_deadline = Some(self.clock.now() + SESSION_SAVE_DEBOUNCE);
+        }
+    }
+
+    fn slash_command_prefix(&self) -> Option<&str> {
+        let mut parts = self.slash_input_buffer.split_whitespace();
+        let command = parts.next()?;
+        command.strip_prefix('/')
+    }
+
+    fn color_candidate_prefix(&self) -> Option<&str> {
+        let trimmed = self.slash_input_buffer.trim_end();
+        let stripped = trimmed.strip_prefix('/')?;
+        let rest = stripped.strip_prefix("color")?;
+        if rest.is_empty() {
+            return Some("");
+        }
+        if rest.starts_with(char::is_whitespace) {
+            return Some(rest.trim_start());
+        }
+        None
+    }
+
+    fn sort_candidate_target(&self) -> Option<(Vec<String>, &str)> {
+        let rest = self.slash_input_buffer.strip_prefix("/sort")?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let mut words = rest.split_whitespace().collect::<Vec<_>>();
+        let prefix = if rest.ends_with(char::is_whitespace) {
+            ""
+        } else {
+            words.pop().unwrap_or("")
+        };
+        Some((words.into_iter().map(str::to_string).collect(), prefix))
+    }
+
+    fn bookmark_candidate_target(&self) -> Option<(&'static str, &str)> {
+        BOOKMARK_COMPLETION_COMMANDS.iter().find_map(|command| {
+            let rest = self.slash_input_buffer.strip_prefix(command)?;
+            (!rest.contains(char::is_whitespace)).then_some((*command, rest))
+        })
+    }
+
+    fn bookmark_candidates(&self, prefix: &str) -> Vec<SlashCandidate> {
+        self.bookmarks
+            .iter()
+            .filter(|(name, _)| name.starts_with(prefix))
+            .map(|(name, path)| SlashCandidate {
+                text: name.to_string(),
+                description: Some(path.display().to_string()),
+            })
+            .collect()
+    }
+
+    fn z_candidate_fragments(&self) -> Option<Vec<String>> {
+        let rest = self.slash_input_buffer.strip_prefix("/z")?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        Some(rest.split_whitespace().map(str::to_string).collect())
+    }
+
+    fn z_candidates(&self, fragments: &[String]) -> Vec<SlashCandidate> {
+        self.frecency
+            .matches(fragments, &self.current_dir, unix_now())
+            .into_iter()
+            .take(MAX_Z_CANDIDATES)
+            .map(|path| SlashCandidate {
+                text: path.display().to_string(),
+                description: None,
+            })
+            .collect()
+    }
+
+    fn shell_completion_target(&self) -> Option<ShellCompletionTarget> {
+        let input = self.slash_input_buffer.as_str();
+        let (command, after) = PATH_COMPLETION_COMMANDS
+            .iter()
+            .find_map(|command| input.strip_prefix(command).map(|after| (*command, after)))?;
+        if after.is_empty() {
+            return None;
+        }
+        if !after.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let has_trailing_space = input
+            .chars()
+            .last()
+            .map(|ch| ch.is_whitespace())
+            .unwrap_or(false);
+        let (start, end) = if has_trailing_space {
+            (input.len(), input.len())
+        } else {
+            let trimmed = input.trim_end();
+            let end = trimmed.len();
+            let start = trimmed
+                .char_indices()
+                .rev()
+                .find(|(_, ch)| ch.is_whitespace())
+                .map(|(idx, ch)| idx + ch.len_utf8())
+                .unwrap_or(0);
+            (start, end)
+        };
+        let prefix = if start == end {
+            String::new()
+        } else {
+            input[start..end].to_string()
+        };
+        Some(ShellCompletionTarget {
+            range: start..end,
+            prefix,
+            dirs_only: command == "/cwd",
+        })
+    }
+
+    fn shell_path_candidates(&self, prefix: &str, dirs_only: bool) -> Vec<SlashCandidate> {
+        if prefix.contains('"') || prefix.contains('\'') {
+            return Vec::new();
+        }
+        let (dir_prefix, name_prefix) = split_path_prefix(prefix);
+        let Ok(base_dir) = expand_path(&dir_prefix, &self.current_dir) else {
+            return Vec::new();
+        };
+        let entries = match list_entries(&base_dir, self.show_hidden) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        entries
+            .into_iter()
+            .filter(|entry| entry.name.starts_with(&name_prefix))
+            .filter(|entry| entry.is_dir || !dirs_only)
+            .map(|entry| {
+                let mut text = String::new();
+                text.push_str(&dir_prefix);
+                text.push_str(&entry.name);
+                if entry.is_dir {
+                    text.push('/');
+                }
+                SlashCandidate {
+                    text,
+                    description: None,
+                }
+            })
+            .collect()
+    }
+
+    fn timed_feedback(&self, text: String, status: FeedbackStatus) -> SlashFeedback {
+        timed_feedback_with(self.clock.as_ref(), text, status)
+    }
+
+    fn preview_feedback(&self, enabled: bool) -> SlashFeedback {
+        self.timed_feedback(
+            format!("preview: {}", if enabled { "on" } else { "off" }),
+            FeedbackStatus::Success,
+        )
+    }
+
+    fn opener_disabled_feedback(&self) -> SlashFeedback {
+        self.timed_feedback(
+            "opener is disabled. Set allow_opener = true to open with external apps.".to_string(),
+            FeedbackStatus::Warn,
+        )
+    }
+
+    fn tab_error_feedback(&self, error: AppError) -> SlashFeedback {
+        self.timed_feedback(format!("tab: {}", error), FeedbackStatus::Error)
+    }
+
+    fn file_op_error_feedback(&self, label: &str, error: FileOpError) -> SlashFeedback {
+        self.timed_feedback(format!("{label}: {error}"), FeedbackStatus::Error)
+    }
+
+    fn shell_error_feedback(&self, error: ShellCommandError) -> SlashFeedback {
+        self.timed_feedback(error.to_string(), FeedbackStatus::Error)
+    }
+
+    fn announce_active_tab_color(&mut self) {
+        let theme_id = self.tabs.active_theme_id();
+        self.tab_color_changed = Some(TabColorChanged {
+            tab_id: self.tabs.active_tab_id(),
+            theme: theme_id.theme(),
+        });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PendingConfirmation {
+    PermanentDelete { paths: Vec<PathBuf> },
+    BulkRename { plan: BulkRenamePlan },
+    DropCopy { paths: Vec<PathBuf> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictPrompt {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    pub source_metadata: Option<EntryMetadata>,
+    pub target_metadata: Option<EntryMetadata>,
+    pub index: usize,
+    pub total: usize,
+    pub apply_to_all: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RegisterKind {
+    Yank,
+    Cut,
+}
+
+impl RegisterKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Yank => "yank",
+            Self::Cut => "cut",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Register {
+    kind: RegisterKind,
+    paths: Vec<PathBuf>,
+}
+
+impl Register {
+    fn summary(&self) -> String {
+        let names = self
+            .paths
+            .iter()
+            .take(REGISTER_HINT_NAMES)
+            .map(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let more = self.paths.len().saturating_sub(REGISTER_HINT_NAMES);
+        let suffix = if more > 0 {
+            format!(" +{more}")
+        } else {
+            String::new()
+        };
+        format!("{}: {names}{suffix}", self.kind.label())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransferKind {
+    Paste,
+    Copy,
+    Move,
+}
+
+impl TransferKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Paste => "paste",
+            Self::Copy => "copy",
+            Self::Move => "move",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingConflicts {
+    kind: TransferKind,
+    sources: Vec<PathBuf>,
+    dest: PathBuf,
+    queue: VecDeque<(PathBuf, PathBuf)>,
+    total: usize,
+    overrides: Vec<ConflictOverride>,
+    apply_to_all: bool,
+}
+
+impl PendingConflicts {
+    fn decide(&mut self, choice: ConflictChoice, source: PathBuf, target: &Path) {
+        match resolve_conflict(choice, &source, target) {
+            Some(action) => self.overrides.push(ConflictOverride { source, action }),
+            None => self.sources.retain(|path| *path != source),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BulkRenameSession {
+    dir: PathBuf,
+    originals: Vec<String>,
+    buffer_path: PathBuf,
+    launched: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VisualRange {
+    anchor: usize,
+    cursor: usize,
+    base: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingListing {
+    id: u64,
+    dir: PathBuf,
+    loaded: usize,
+    last_progress: Instant,
+    focus: Option<String>,
+    retried: bool,
+    /// 一覧ごとに一度だけ読んだ `.gitignore`。チャンクごとに読み直さない。
+    ignore: Option<GitIgnore>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ResultsView {
+    id: u64,
+    kind: ResultsKind,
+    query: String,
+    root: PathBuf,
+    items: Vec<ResultItem>,
+    cursor: usize,
+    running: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResultsKind {
+    Where,
+    Grep,
+    Bookmarks,
+}
+
+impl ResultsKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Where => "where",
+            Self::Grep => "grep",
+            Self::Bookmarks => "bookmarks",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ResultItem {
+    path: PathBuf,
+    line: Option<usize>,
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TrashView {
+    items: Vec<TrashedItem>,
+    cursor: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TabColorChanged {
     pub tab_id: u64,
     pub theme: ColorTheme,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CommandContext {
-    Tab(u64),
-}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommandContext {
+    Tab(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ShellCompletionTarget {
+    range: std::ops::Range<usize>,
+    prefix: String,
+    dirs_only: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlashCandidate {
+    pub text: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SlashCandidates {
+    pub items: Vec<SlashCandidate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlashFeedback {
+    pub text: String,
+    pub status: FeedbackStatus,
+    pub tabs: Option<Vec<TabSummary>>,
+    pub(crate) expires_at: Instant,
+}
+
+impl SlashFeedback {
+    fn is_expired_with(&self, clock: &dyn AppClock) -> bool {
+        clock.now() > self.expires_at
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackStatus {
+    Success,
+    Error,
+    Warn,
+}
+
+fn format_slash_error(error: SlashCommandError) -> &'static str {
+    match error {
+        SlashCommandError::MissingSlash => "missing '/'",
+        SlashCommandError::MissingName => "missing command",
+    }
+}
+
+fn should_save_session(event: &TabsEvent) -> bool {
+    match event {
+        TabsEvent::ActivePathChanged { .. }
+        | TabsEvent::ActiveThemeChanged { .. }
+        | TabsEvent::TabAdded { .. }
+        | TabsEvent::ActiveSortChanged { .. } => true,
+    }
+}
+
+fn timed_feedback_with(
+    clock: &dyn AppClock,
+    text: String,
+    status: FeedbackStatus,
+) -> SlashFeedback {
+    SlashFeedback {
+        text,
+        status,
+        tabs: None,
+        expires_at: clock.now() + SLASH_FEEDBACK_TTL,
+    }
+}
+
+struct SlashCommandSpec {
+    name: &'static str,
+    description: &'static str,
+    options: &'static [&'static str],
+}
+
+fn slash_command_specs() -> &'static [SlashCommandSpec] {
+    &[
+        SlashCommandSpec {
+            name: "tab",
+            description: "tabs",
+            options: &["new", "next", "prev", "<number>"],
+        },
+        SlashCommandSpec {
+            name: "preview",
+            description: "toggle preview",
+            options: &["show", "hide"],
+        },
+        SlashCommandSpec {
+            name: "shell",
+            description: "run shell command",
+            options: &["<command>"],
+        },
+        SlashCommandSpec {
+            name: "cwd",
+            description: "change directory (~ and $VAR are expanded)",
+            options: &["<path>"],
+        },
+        SlashCommandSpec {
+            name: "bookmark",
+            description: "save, remove or pick bookmarked directories",
+            options: &["add <name>", "rm [name]"],
+        },
+        SlashCommandSpec {
+            name: "go",
+            description: "jump to a bookmarked directory",
+            options: &["<name>"],
+        },
+        SlashCommandSpec {
+            name: "z",
+            description: "jump to a frequently visited directory",
+            options: &["<fragments>"],
+        },
+        SlashCommandSpec {
+            name: "tree",
+            description: "expand folders in place",
+            options: &["on", "off"],
+        },
+        SlashCommandSpec {
+            name: "where",
+            description: "find entries by name below this folder",
+            options: &["<name>", "<glob>"],
+        },
+        SlashCommandSpec {
+            name: "grep",
+            description: "search file contents below this folder",
+            options: &["<pattern>"],
+        },
+        SlashCommandSpec {
+            name: "filter",
+            description: "hide entries not matching a pattern",
+            options: &["<glob>", "regex <pattern>", "clear"],
+        },
+        SlashCommandSpec {
+            name: "sort",
+            description: "sort entries in this tab",
+            options: &[
+                "name",
+                "natural",
+                "size",
+                "mtime",
+                "ext",
+                "type",
+                "reverse",
+                "dirs-first",
+                "ignore-case",
+            ],
+        },
+        SlashCommandSpec {
+            name: "color",
+            description: "set tab theme",
+            options: &["<theme>"],
+        },
+        SlashCommandSpec {
+            name: "rename",
+            description: "rename selected entry",
+            options: &["<new-name>"],
+        },
+        SlashCommandSpec {
+            name: "bulkrename",
+            description: "rename marked entries in $EDITOR",
+            options: &[],
+        },
+        SlashCommandSpec {
+            name: "move",
+            description: "move selected entry",
+            options: &["<dest>"],
+        },
+        SlashCommandSpec {
+            name: "mkdir",
+            description: "create a directory (with parents)",
+            options: &["<path>"],
+        },
+        SlashCommandSpec {
+            name: "touch",
+            description: "create a file or update its mtime",
+            options: &["<path>"],
+        },
+        SlashCommandSpec {
+            name: "copy",
+            description: "copy marked entries into a directory",
+            options: &["<dest>"],
+        },
+        SlashCommandSpec {
+            name: "select",
+            description: "mark entries",
+            options: &["all", "none", "invert", "<glob>"],
+        },
+        SlashCommandSpec {
+            name: "delete",
+            description: "move selected entry to trash",
+            options: &["permanent"],
+        },
+        SlashCommandSpec {
+            name: "trash",
+            description: "list and restore trashed entries",
+            options: &[],
+        },
+        SlashCommandSpec {
+            name: "undo",
+            description: "undo the last file operation",
+            options: &[],
+        },
+        SlashCommandSpec {
+            name: "redo",
+            description: "redo the last undone file operation",
+            options: &[],
+        },
+        SlashCommandSpec {
+            name: "paste",
+            description: "copy paths (or the register) into current dir",
+            options: &["<path>..."],
+        },
+        SlashCommandSpec {
+            name: "path",
+            description: "copy selected paths to the terminal clipboard",
+            options: &["relative", "lines"],
+        },
+        SlashCommandSpec {
+            name: "clip",
+            description: "copy file contents to the terminal clipboard",
+            options: &[],
+        },
+        SlashCommandSpec {
+            name: "yank",
+            description: "copy marked entries into the register",
+            options: &[],
+        },
+        SlashCommandSpec {
+            name: "cut",
+            description: "cut marked entries into the register",
+            options: &[],
+        },
+    ]
+}
+
+fn slash_command_spec(name: &str) -> Option<&'static SlashCommandSpec> {
+    slash_command_specs().iter().find(|spec| spec.name == name)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn sort_candidates(previous: &[String], prefix: &str) -> Vec<SlashCandidate> {
+    let words: Vec<&str> = if previous.is_empty() {
+        SortKey::all().iter().map(|key| key.name()).collect()
+    } else {
+        SORT_FLAGS
+            .iter()
+            .copied()
+            .filter(|flag| !previous.iter().any(|word| word == flag))
+            .collect()
+    };
+    words
+        .into_iter()
+        .filter(|word| word.starts_with(prefix))
+        .map(|word| SlashCandidate {
+            text: word.to_string(),
+            description: None,
+        })
+        .collect()
+}
+
+fn color_theme_candidates(prefix: &str) -> Vec<SlashCandidate> {
+    let normalized = prefix.trim().to_ascii_lowercase();
+    ColorThemeId::all()
+        .iter()
+        .copied()
+        .map(ColorThemeId::theme)
+        .filter(|theme| {
+            if normalized.is_empty() {
+                true
+            } else {
+                theme.name.to_ascii_lowercase().starts_with(&normalized)
+            }
+        })
+        .map(|theme| SlashCandidate {
+            text: theme.name.to_string(),
+            description: Some(format!(
+                "success {} warn {} error {} info {}",
+                hex_color(theme.semantic.success),
+                hex_color(theme.semantic.warn),
+                hex_color(theme.semantic.error),
+                hex_color(theme.semantic.info)
+            )),
+        })
+        .collect()
+}
+
+fn hex_color(color: crate::core::ColorRgb) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b)
+}
+
+fn split_path_prefix(raw: &str) -> (String, String) {
+    if raw.is_empty() {
+        return (String::new(), String::new());
+    }
+    let separator = std::path::MAIN_SEPARATOR;
+    if raw.ends_with('/') || raw.ends_with(separator) {
+        return (raw.to_string(), String::new());
+    }
+    let separators = ['/', separator];
+    if let Some(index) = raw.rfind(|ch| separators.contains(&ch)) {
+        let (dir, file) = raw.split_at(index + 1);
+        return (dir.to_string(), file.to_string());
+    }
+    (String::new(), raw.to_string())
+}
+
+#[derive(Debug, Clone)]
+struct ShellOutputView {
+    lines: VecDeque<String>,
+    total_bytes: usize,
+    max_bytes: usize,
+    max_lines: usize,
+    scroll_offset: usize,
+    horiz_offset: usize,
+    stdout_opened: bool,
+    stderr_opened: bool,
+}
+
+impl ShellOutputView {
+    fn new() -> Self {
+        Self {
+            lines: VecDeque::new(),
+            total_bytes: 0,
+            max_bytes: 2_097_152,
+            max_lines: 4_000,
+            scroll_offset: 0,
+            horiz_offset: 0,
+            stdout_opened: false,
+            stderr_opened: false,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    #[cfg(test)]
+    fn reset(&mut self, request: &ShellCommandRequest, result: &ShellExecutionResult) {
+        self.lines.clear();
+        self.total_bytes = 0;
+        self.scroll_offset = 0;
+        self.horiz_offset = 0;
+        self.stdout_opened = false;
+        self.stderr_opened = false;
+        self.push_line(format!("$ {}", request.raw_command));
+        self.push_line(format!("cwd: {}", request.working_dir.display()));
+        let exit = result.status_code.unwrap_or(-1);
+        self.push_line(format!(
+            "exit: {} duration: {}ms timestamp: {}",
+            exit, result.duration_ms, result.timestamp_ms
+        ));
+        if !result.stdout.trim().is_empty() {
+            self.open_stdout();
+            self.push_text(&result.stdout);
+        }
+        if !result.stderr.trim().is_empty() {
+            self.open_stderr();
+            self.push_text(&result.stderr);
+        }
+        self.shrink_to_limits();
+    }
+
+    fn start(&mut self, request: &ShellCommandRequest) {
+        self.lines.clear();
+        self.total_bytes = 0;
+        self.scroll_offset = 0;
+        self.horiz_offset = 0;
+        self.stdout_opened = false;
+        self.stderr_opened = false;
+        self.push_line(format!("$ {}", request.raw_command));
+        self.push_line(format!("cwd: {}", request.working_dir.display()));
+        self.push_line("status: running".to_string());
+    }
+
+    fn append_stdout_line(&mut self, line: &str) {
+        self.open_stdout();
+        self.push_line(line.to_string());
+    }
+
+    fn append_stderr_line(&mut self, line: &str) {
+        self.open_stderr();
+        self.push_line(line.to_string());
+    }
+
+    fn finish(&mut self, result: &ShellExecutionResult) {
+        let exit = result.status_code.unwrap_or(-1);
+        self.push_line(format!(
+            "exit: {} duration: {}ms timestamp: {}",
+            exit, result.duration_ms, result.timestamp_ms
+        ));
+        if !self.stdout_opened && !result.stdout.trim().is_empty() {
+            self.open_stdout();
+            self.push_text(&result.stdout);
+        }
+        if !self.stderr_opened && !result.stderr.trim().is_empty() {
+            self.open_stderr();
+            self.push_text(&result.stderr);
+        }
+        self.shrink_to_limits();
+    }
+
+    fn scroll_up(&mut self) {
+        if self.scroll_offset > 0 {
+            self.scroll_offset -= 1;
+        }
+    }
+
+    fn scroll_down(&mut self) {
+        let max_offset = self.lines.len().saturating_sub(1);
+        if self.scroll_offset < max_offset {
+            self.scroll_offset += 1;
+        }
+    }
+
+    fn scroll_left(&mut self) {
+        self.horiz_offset = self.horiz_offset.saturating_sub(self.horiz_step());
+    }
+
+    fn scroll_right(&mut self) {
+        let max_offset = self.max_horiz_offset();
+        let next = self.horiz_offset + self.horiz_step();
+        self.horiz_offset = next.min(max_offset);
+    }
+
+    fn page_up(&mut self) {
+        let step = self.page_step();
+        self.scroll_offset = self.scroll_offset.saturating_sub(step);
+    }
+
+    fn page_down(&mut self) {
+        let step = self.page_step();
+        let max_offset = self.lines.len().saturating_sub(1);
+        self.scroll_offset = (self.scroll_offset + step).min(max_offset);
+    }
+
+    fn home(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    fn end(&mut self) {
+        self.scroll_offset = self.lines.len().saturating_sub(1);
+    }
+
+    fn view_text(&self, height: usize, width: usize) -> String {
+        if self.lines.is_empty() {
+            return "shell output: empty".to_string();
+        }
+        let start = self.scroll_offset.min(self.lines.len());
+        let end = (start + height).min(self.lines.len());
+        self.lines
+            .iter()
+            .skip(start)
+            .take(end.saturating_sub(start))
+            .map(|line| slice_line(line, self.horiz_offset, width))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for line in text.lines() {
+            self.push_line(line.to_string());
+        }
+    }
+
+    fn push_line(&mut self, line: String) {
+        self.total_bytes += line.len() + 1;
+        self.lines.push_back(line);
+        self.shrink_to_limits();
+    }
+
+    fn open_stdout(&mut self) {
+        if !self.stdout_opened {
+            self.push_line("stdout:".to_string());
+            self.stdout_opened = true;
+        }
+    }
+
+    fn open_stderr(&mut self) {
+        if !self.stderr_opened {
+            self.push_line("stderr:".to_string());
+            self.stderr_opened = true;
+        }
+    }
+
+    fn shrink_to_limits(&mut self) {
+        while self.lines.len() > self.max_lines || self.total_bytes > self.max_bytes {
+            if let Some(line) = self.lines.pop_front() {
+                self.total_bytes = self.total_bytes.saturating_sub(line.len() + 1);
+            } else {
+                break;
+            }
+        }
+        if self.scroll_offset >= self.lines.len() {
+            self.scroll_offset = self.lines.len().saturating_sub(1);
+        }
+        if self.horiz_offset > self.max_horiz_offset() {
+            self.horiz_offset = self.max_horiz_offset();
+        }
+    }
+
+    fn page_step(&self) -> usize {
+        20
+    }
+
+    fn horiz_step(&self) -> usize {
+        4
+    }
+
+    fn max_horiz_offset(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn slice_line(line: &str, offset: usize, width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    line.chars().skip(offset).take(width).collect()
+}
+
+fn format_shell_start_notice(raw: &str) -> String {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return "started".to_string();
+    }
+    let compact = compact_output(trimmed, 40).unwrap_or_else(|| trimmed.to_string());
+    format!("{compact}; started")
+}
+
+fn shell_result_notice_level(result: &ShellExecutionResult) -> UserNoticeLevel {
+    match result.status_code {
+        Some(0) => UserNoticeLevel::Success,
+        _ => UserNoticeLevel::Error,
+    }
+}
+
+fn format_shell_notice(result: &ShellExecutionResult) -> String {
+    let exit = result.status_code.unwrap_or(-1);
+    let mut parts = vec![format!("exit={exit}"), format!("{}ms", result.duration_ms)];
+    if let Some(stdout) = compact_output(&result.stdout, 80) {
+        parts.push(format!("stdout={stdout}"));
+    }
+    if let Some(stderr) = compact_output(&result.stderr, 80) {
+        parts.push(format!("stderr={stderr}"));
+    }
+    parts.join(" ")
+}
+
+fn compact_output(output: &str, max_len: usize) -> Option<String> {
+    let trimmed = output.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let mut flat = trimmed.replace('\n', "\\n");
+    if flat.len() > max_len {
+        flat.truncate(max_len);
+        flat.push_str("...");
+    }
+    Some(flat)
+}
+
+#[cfg(test)]
+mod slash_tests {
+    use super::*;
+    use crate::tabs::TabSummary;
+
+    #[derive(Debug)]
+    struct FixedClock {
+        now: Instant,
+    }
+
+    impl AppClock for FixedClock {
+        fn now(&self) -> Instant {
+            self.now
+        }
+    }
+
+    fn empty_app() -> App {
+        App::new(PathBuf::from("."), Vec::new(), Vec::new(), None, false)
+    }
+
+    #[derive(Clone)]
+    struct FixedConfigLoader {
+        config: Config,
+    }
+
+    impl ConfigLoader for FixedConfigLoader {
+        fn load(&self) -> Config {
+            self.config.clone()
+        }
+    }
+
+    #[derive(Clone)]
+    struct FixedSessionLoader {
+        tabs: Vec<SessionTab>,
+    }
+
+    impl SessionLoader for FixedSessionLoader {
+        fn load_tabs(&self) -> Vec<SessionTab> {
+            self.tabs.clone()
+        }
+    }
+
+    fn load_app_with(current_dir: PathBuf, config: Config, tabs: Vec<SessionTab>) -> App {
+        let config_loader = FixedConfigLoader { config };
+        let session_loader = FixedSessionLoader { tabs };
+        App::load_with(current_dir, &config_loader, &session_loader).unwrap()
+    }
+
+    fn load_app(current_dir: PathBuf) -> App {
+        load_app_with(current_dir, Config::default(), Vec::new())
+    }
+
+    const EVENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// ワーカーのイベントをブロッキングで受け取り、`done` が真になるまで反映する。
+    fn drain_until<E>(
+        app: &mut App,
+        next: impl Fn(&App) -> Option<E>,
+        handle: impl Fn(&mut App, E),
+        done: impl Fn(&App) -> bool,
+    ) {
+        while !done(app) {
+            let Some(event) = next(app) else {
+                panic!("no worker event within {EVENT_TIMEOUT:?}");
+            };
+            handle(app, event);
+        }
+    }
+
+    fn app_with_two_tabs() -> (tempfile::TempDir, App, PathBuf, PathBuf) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir_one = temp_dir.path().join("one");
+        let dir_two = temp_dir.path().join("two");
+        std::fs::create_dir(&dir_one).unwrap();
+        std::fs::create_dir(&dir_two).unwrap();
+
+        let mut app = load_app(dir_one.clone());
+        app.new_tab().unwrap();
+        app.change_dir(dir_two.clone());
+
+        (temp_dir, app, dir_one, dir_two)
+    }
+
+    #[test]
+    fn activate_slash_input_sets_prompt() {
+        let mut app = empty_app();
+
+        app.activate_slash_input();
+
+        assert!(app.slash_input_active());
+        assert_eq!(app.slash_input_text(), "/");
+    }
+
+    #[test]
+    fn cancel_slash_input_clears_buffer() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+
+        app.cancel_slash_input();
+
+        assert!(!app.slash_input_active());
+        assert_eq!(app.slash_input_text(), "");
+    }
+
+    #[test]
+    fn backspace_slash_input_exits_when_empty() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+
+        app.backspace_slash_char();
+
+        assert!(!app.slash_input_active());
+        assert_eq!(app.slash_input_text(), "");
+    }
+
+    #[test]
+    fn submit_slash_command_parses_and_deactivates() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+        app.append_slash_char('r');
+        app.append_slash_char('e');
+        app.append_slash_char('v');
+        app.append_slash_char('i');
+        app.append_slash_char('e');
+        app.append_slash_char('w');
+
+        let command = app.submit_slash_command().unwrap();
+
+        assert_eq!(command.name, "preview");
+        assert_eq!(command.args.len(), 0);
+        assert!(!app.slash_input_active());
+        assert_eq!(app.slash_input_text(), "");
+        assert_eq!(
+            app.slash_feedback().unwrap().status,
+            FeedbackStatus::Success
+        );
+    }
+
+    #[test]
+    fn preview_toggle_turns_off_when_visible() {
+        let mut app = empty_app();
+        app.preview_visible = true;
+        app.preview_paused = false;
+
+        let feedback = app.handle_slash_command(&SlashCommand {
+            name: "preview".to_string(),
+            args: Vec::new(),
+            raw: "/preview".to_string(),
+        });
+
+        assert!(!app.preview_visible());
+        assert!(app.preview_paused);
+        assert_eq!(feedback.text, "preview: off");
+    }
+
+    #[test]
+    fn preview_show_turns_on_and_resumes() {
+        let mut app = empty_app();
+        app.preview_visible = false;
+        app.preview_paused = true;
+
+        let feedback = app.handle_slash_command(&SlashCommand {
+            name: "preview".to_string(),
+            args: vec!["show".to_string()],
+            raw: "/preview show".to_string(),
+        });
+
+        assert!(app.preview_visible());
+        assert!(!app.preview_paused);
+        assert_eq!(feedback.text, "preview: on");
+    }
+
+    #[test]
+    fn preview_hide_turns_off_and_pauses() {
+        let mut app = empty_app();
+        app.preview_visible = true;
+        app.preview_paused = false;
+
+        let feedback = app.handle_slash_command(&SlashCommand {
+            name: "preview".to_string(),
+            args: vec!["hide".to_string()],
+            raw: "/preview hide".to_string(),
+        });
+
+        assert!(!app.preview_visible());
+        assert!(app.preview_paused);
+        assert_eq!(feedback.text, "preview: off");
+    }
+
+    #[test]
+    fn slash_history_moves_through_entries() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+        app.append_slash_char('r');
+        app.append_slash_char('e');
+        app.append_slash_char('v');
+        app.append_slash_char('i');
+        app.append_slash_char('e');
+        app.append_slash_char('w');
+        app.submit_slash_command();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+        app.append_slash_char('r');
+        app.append_slash_char('e');
+        app.append_slash_char('v');
+        app.append_slash_char('i');
+        app.append_slash_char('e');
+        app.append_slash_char('w');
+        app.append_slash_char(' ');
+        app.append_slash_char('s');
+        app.append_slash_char('h');
+        app.append_slash_char('o');
+        app.append_slash_char('w');
+        app.submit_slash_command();
+
+        app.activate_slash_input();
+        app.slash_history_prev();
+        assert_eq!(app.slash_input_text(), "/preview show");
+        app.slash_history_prev();
+        assert_eq!(app.slash_input_text(), "/preview");
+        app.slash_history_next();
+        assert_eq!(app.slash_input_text(), "/preview show");
+    }
+
+    #[test]
+    fn slash_candidates_filter_by_prefix() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+
+        let candidates = app.slash_candidates();
+        let texts = candidates
+            .items
+            .into_iter()
+            .map(|candidate| candidate.text)
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            texts,
+            vec![
+                "/preview".to_string(),
+                "/paste".to_string(),
+                "/path".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn slash_completion_uses_first_candidate() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+
+        app.complete_slash_candidate();
+
+        assert_eq!(app.slash_input_text(), "/preview ");
+    }
+
+    #[test]
+    fn slash_completion_skips_when_args_present() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+        app.append_slash_char('r');
+        app.append_slash_char('e');
+        app.append_slash_char('v');
+        app.append_slash_char('i');
+        app.append_slash_char('e');
+        app.append_slash_char('w');
+        app.append_slash_char(' ');
+        app.append_slash_char('h');
+
+        app.complete_slash_candidate();
+
+        assert_eq!(app.slash_input_text(), "/preview h");
+    }
+
+    #[test]
+    fn shell_candidates_target_last_token_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("build.sh"), "echo").unwrap();
+        std::fs::create_dir(temp_dir.path().join("dir")).unwrap();
+        std::fs::write(temp_dir.path().join("diary.txt"), "note").unwrap();
+
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.slash_input_active = true;
+        app.slash_input_buffer = "/shell ./build.sh ./di".to_string();
+
+        let candidates = app.slash_candidates();
+        let texts = candidates
+            .items
+            .into_iter()
+            .map(|candidate| candidate.text)
+            .collect::<Vec<String>>();
+
+        assert_eq!(texts, vec!["./diary.txt".to_string(), "./dir/".to_string()]);
+    }
+
+    #[test]
+    fn shell_completion_replaces_last_token_and_appends_space() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("dir")).unwrap();
+        std::fs::write(temp_dir.path().join("diary.txt"), "note").unwrap();
+
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.slash_input_active = true;
+        app.slash_input_buffer = "/shell ./build.sh ./di".to_string();
+
+        app.complete_slash_candidate();
+
+        assert_eq!(app.slash_input_text(), "/shell ./build.sh ./diary.txt ");
+    }
+
+    #[test]
+    fn shell_completion_skips_space_for_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.slash_input_active = true;
+        app.slash_input_buffer = "/shell ./sr".to_string();
+
+        app.complete_slash_candidate();
+
+        assert_eq!(app.slash_input_text(), "/shell ./src/");
+    }
+
+    #[test]
+    fn shell_completion_keeps_existing_shell_output() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("diary.txt"), "note").unwrap();
+        let request = ShellCommandRequest::new(temp_dir.path().to_path_buf(), "echo hi").unwrap();
+        let result = ShellExecutionResult {
+            status_code: Some(0),
+            stdout: "ok".to_string(),
+            stderr: String::new(),
+            duration_ms: 12,
+            timestamp_ms: 1,
+        };
+
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.shell_output_view.reset(&request, &result);
+        let before = app.shell_output_view.lines.clone();
+        app.slash_input_active = true;
+        app.slash_input_buffer = "/shell ./di".to_string();
+
+        app.complete_slash_candidate();
+
+        assert_eq!(app.shell_output_view.lines, before);
+    }
+
+    #[test]
+    fn load_restores_multiple_tabs_from_session() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir_one = temp_dir.path().join("one");
+        let dir_two = temp_dir.path().join("two");
+        std::fs::create_dir(&dir_one).unwrap();
+        std::fs::create_dir(&dir_two).unwrap();
+        std::fs::write(dir_two.join("a.txt"), "").unwrap();
+        std::fs::write(dir_two.join("b.txt"), "").unwrap();
+
+        let session_tabs = vec![
+            SessionTab {
+                tab_id: 4,
+                path: dir_one.clone(),
+                theme_name: "Night Harbor".to_string(),
+                sort: SortMode::default(),
+                history: crate::core::NavHistory {
+                    back: vec![HistoryEntry {
+                        path: dir_two.clone(),
+                        focus: Some("b.txt".to_string()),
+                    }],
+                    forward: Vec::new(),
+                },
+            },
+            SessionTab {
+                tab_id: 8,
+                path: dir_two.clone(),
+                theme_name: "Glacier Coast".to_string(),
+                sort: SortMode::default(),
+                history: crate::core::NavHistory::default(),
+            },
+        ];
+        let mut app = load_app_with(PathBuf::from("/fallback"), Config::default(), session_tabs);
+
+        assert_eq!(app.current_dir, dir_one);
+        assert_eq!(app.tabs.count(), 2);
+        let restored_tabs = app.tabs.session_tabs();
+        assert_eq!(restored_tabs[0].path, dir_one);
+        assert_eq!(restored_tabs[1].path, dir_two);
+        assert_eq!(restored_tabs[0].theme_name, "Night Harbor");
+        assert_eq!(restored_tabs[1].theme_name, "Glacier Coast");
+
+        app.history_back().unwrap();
+        assert_eq!(app.current_dir, dir_two);
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("b.txt")
+        );
+    }
+
+    #[test]
+    fn history_back_and_forward_restore_cursor_per_tab() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let child = temp_dir.path().join("child");
+        std::fs::create_dir(&child).unwrap();
+        std::fs::write(child.join("a.txt"), "").unwrap();
+        std::fs::write(child.join("b.txt"), "").unwrap();
+        std::fs::write(temp_dir.path().join("z.txt"), "").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+
+        app.enter_selected_dir().unwrap();
+        assert_eq!(app.current_dir, child);
+        app.move_cursor_down();
+        app.move_to_parent().unwrap();
+        app.move_cursor_down();
+
+        app.history_back().unwrap();
+        assert_eq!(app.current_dir, child);
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("b.txt")
+        );
+        app.history_back().unwrap();
+        assert_eq!(app.current_dir, temp_dir.path());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("child")
+        );
+        app.history_back().unwrap();
+        assert_eq!(app.current_dir, temp_dir.path());
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct ShellCompletionTarget {
-    range: std::ops::Range<usize>,
-    prefix: String,
-}
+        app.history_forward().unwrap();
+        app.history_forward().unwrap();
+        assert_eq!(app.current_dir, temp_dir.path());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("z.txt")
+        );
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct SlashCandidate {
-    pub text: String,
-    pub description: Option<String>,
-}
+        app.new_tab().unwrap();
+        app.history_back().unwrap();
+        assert_eq!(app.current_dir, temp_dir.path());
+    }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
-pub struct SlashCandidates {
-    pub items: Vec<SlashCandidate>,
-}
+    #[test]
+    fn color_command_sets_theme_for_active_tab() {
+        let mut app = empty_app();
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct SlashFeedback {
-    pub text: String,
-    pub status: FeedbackStatus,
-    pub tabs: Option<Vec<TabSummary>>,
-    pub(crate) expires_at: Instant,
-}
+        let feedback = app.handle_slash_command(&SlashCommand {
+            name: "color".to_string(),
+            args: vec!["Glacier".to_string(), "Coast".to_string()],
+            raw: "/color Glacier Coast".to_string(),
+        });
 
-impl SlashFeedback {
-    fn is_expired_with(&self, clock: &dyn AppClock) -> bool {
-        clock.now() > self.expires_at
+        assert_eq!(feedback.status, FeedbackStatus::Success);
+        assert_eq!(feedback.text, "theme: Glacier Coast");
+        assert_eq!(app.tabs.active_theme_id(), ColorThemeId::GlacierCoast);
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum FeedbackStatus {
-    Success,
-    Error,
-    Warn,
-}
+    #[test]
+    fn color_command_rejects_unknown_theme() {
+        let mut app = empty_app();
 
-fn format_slash_error(error: SlashCommandError) -> &'static str {
-    match error {
-        SlashCommandError::MissingSlash => "missing '/'",
-        SlashCommandError::MissingName => "missing command",
-    }
-}
+        let feedback = app.handle_slash_command(&SlashCommand {
+            name: "color".to_string(),
+            args: vec!["Unknown".to_string()],
+            raw: "/color Unknown".to_string(),
+        });
 
-fn should_save_session(event: &TabsEvent) -> bool {
-    match event {
-        TabsEvent::ActivePathChanged { .. }
-        | TabsEvent::ActiveThemeChanged { .. }
-        | TabsEvent::TabAdded { .. } => true,
+        assert_eq!(feedback.status, FeedbackStatus::Error);
+        assert!(feedback.text.contains("unknown"));
     }
-}
 
-fn timed_feedback_with(
-    clock: &dyn AppClock,
-    text: String,
-    status: FeedbackStatus,
-) -> SlashFeedback {
-    SlashFeedback {
-        text,
-        status,
-        tabs: None,
-        expires_at: clock.now() + SLASH_FEEDBACK_TTL,
+    #[test]
+    fn slash_candidates_show_color_themes_with_descriptions() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('c');
+        app.append_slash_char('o');
+        app.append_slash_char('l');
+        app.append_slash_char('o');
+        app.append_slash_char('r');
+        app.append_slash_char(' ');
+
+        let candidates = app.slash_candidates();
+        let first = candidates.items.first().unwrap();
+
+        assert!(first.text.contains("Glacier"));
+        assert!(
+            first
+                .description
+                .as_deref()
+                .unwrap_or("")
+                .contains("success")
+        );
     }
-}
 
-struct SlashCommandSpec {
-    name: &'static str,
-    description: &'static str,
-    options: &'static [&'static str],
-}
+    #[test]
+    fn slash_hint_shows_description_and_options() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+        app.append_slash_char('r');
+        app.append_slash_char('e');
+        app.append_slash_char('v');
+        app.append_slash_char('i');
+        app.append_slash_char('e');
+        app.append_slash_char('w');
 
-fn slash_command_specs() -> &'static [SlashCommandSpec] {
-    &[
-        SlashCommandSpec {
-            name: "tab",
-            description: "tabs",
-            options: &["new", "next", "prev", "<number>"],
-        },
-        SlashCommandSpec {
-            name: "preview",
-            description: "toggle preview",
-            options: &["show", "hide"],
-        },
-        SlashCommandSpec {
-            name: "shell",
-            description: "run shell command",
-            options: &["<command>"],
-        },
-        SlashCommandSpec {
-            name: "color",
-            description: "set tab theme",
-            options: &["<theme>"],
-        },
-        SlashCommandSpec {
-            name: "paste",
-            description: "paste from clipboard",
-            options: &[],
-        },
-    ]
-}
+        let hint = app.slash_hint().unwrap();
 
-fn slash_command_spec(name: &str) -> Option<&'static SlashCommandSpec> {
-    slash_command_specs().iter().find(|spec| spec.name == name)
-}
+        assert_eq!(hint, "toggle preview | options: show, hide");
+    }
 
-fn color_theme_candidates(prefix: &str) -> Vec<SlashCandidate> {
-    let normalized = prefix.trim().to_ascii_lowercase();
-    ColorThemeId::all()
-        .iter()
-        .copied()
-        .map(ColorThemeId::theme)
-        .filter(|theme| {
-            if normalized.is_empty() {
-                true
-            } else {
-                theme.name.to_ascii_lowercase().starts_with(&normalized)
-            }
-        })
-        .map(|theme| SlashCandidate {
-            text: theme.name.to_string(),
-            description: Some(format!(
-                "success {} warn {} error {} info {}",
-                hex_color(theme.semantic.success),
-                hex_color(theme.semantic.warn),
-                hex_color(theme.semantic.error),
-                hex_color(theme.semantic.info)
-            )),
-        })
-        .collect()
-}
+    #[test]
+    fn slash_hint_hides_when_args_present() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('p');
+        app.append_slash_char('r');
+        app.append_slash_char('e');
+        app.append_slash_char('v');
+        app.append_slash_char('i');
+        app.append_slash_char('e');
+        app.append_slash_char('w');
+        app.append_slash_char(' ');
+        app.append_slash_char('s');
 
-fn hex_color(color: crate::core::ColorRgb) -> String {
-    format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b)
-}
+        let hint = app.slash_hint();
 
-fn split_path_prefix(raw: &str) -> (String, String) {
-    if raw.is_empty() {
-        return (String::new(), String::new());
-    }
-    let separator = std::path::MAIN_SEPARATOR;
-    if raw.ends_with('/') || raw.ends_with(separator) {
-        return (raw.to_string(), String::new());
-    }
-    let separators = ['/', separator];
-    if let Some(index) = raw.rfind(|ch| separators.contains(&ch)) {
-        let (dir, file) = raw.split_at(index + 1);
-        return (dir.to_string(), file.to_string());
+        assert!(hint.is_none());
     }
-    (String::new(), raw.to_string())
-}
 
-#[derive(Debug, Clone)]
-struct ShellOutputView {
-    lines: VecDeque<String>,
-    total_bytes: usize,
-    max_bytes: usize,
-    max_lines: usize,
-    scroll_offset: usize,
-    horiz_offset: usize,
-    stdout_opened: bool,
-    stderr_opened: bool,
-}
+    #[test]
+    fn slash_feedback_expires_after_ttl() {
+        let mut app = empty_app();
+        app.slash_feedback = Some(SlashFeedback {
+            text: "preview: on".to_string(),
+            status: FeedbackStatus::Success,
+            tabs: None,
+            expires_at: std::time::Instant::now() - std::time::Duration::from_secs(1),
+        });
 
-impl ShellOutputView {
-    fn new() -> Self {
-        Self {
-            lines: VecDeque::new(),
-            total_bytes: 0,
-            max_bytes: 2_097_152,
-            max_lines: 4_000,
-            scroll_offset: 0,
-            horiz_offset: 0,
-            stdout_opened: false,
-            stderr_opened: false,
-        }
+        assert!(app.slash_feedback().is_none());
     }
 
-    fn is_empty(&self) -> bool {
-        self.lines.is_empty()
+    #[test]
+    fn slash_feedback_uses_injected_clock() {
+        let now = Instant::now();
+        let feedback = SlashFeedback {
+            text: "preview: on".to_string(),
+            status: FeedbackStatus::Success,
+            tabs: None,
+            expires_at: now + std::time::Duration::from_secs(1),
+        };
+        let clock = FixedClock {
+            now: now + std::time::Duration::from_secs(2),
+        };
+
+        assert!(feedback.is_expired_with(&clock));
     }
 
-    #[cfg(test)]
-    fn reset(&mut self, request: &ShellCommandRequest, result: &ShellExecutionResult) {
-        self.lines.clear();
-        self.total_bytes = 0;
-        self.scroll_offset = 0;
-        self.horiz_offset = 0;
-        self.stdout_opened = false;
-        self.stderr_opened = false;
-        self.push_line(format!("$ {}", request.raw_command));
-        self.push_line(format!("cwd: {}", request.working_dir.display()));
-        let exit = result.status_code.unwrap_or(-1);
-        self.push_line(format!(
-            "exit: {} duration: {}ms timestamp: {}",
-            exit, result.duration_ms, result.timestamp_ms
-        ));
-        if !result.stdout.trim().is_empty() {
-            self.open_stdout();
-            self.push_text(&result.stdout);
-        }
-        if !result.stderr.trim().is_empty() {
-            self.open_stderr();
-            self.push_text(&result.stderr);
-        }
-        self.shrink_to_limits();
-    }
+    #[test]
+    fn slash_hint_shows_tab_options() {
+        let mut app = empty_app();
+        app.activate_slash_input();
+        app.append_slash_char('t');
+        app.append_slash_char('a');
+        app.append_slash_char('b');
 
-    fn start(&mut self, request: &ShellCommandRequest) {
-        self.lines.clear();
-        self.total_bytes = 0;
-        self.scroll_offset = 0;
-        self.horiz_offset = 0;
-        self.stdout_opened = false;
-        self.stderr_opened = false;
-        self.push_line(format!("$ {}", request.raw_command));
-        self.push_line(format!("cwd: {}", request.working_dir.display()));
-        self.push_line("status: running".to_string());
-    }
+        let hint = app.slash_hint().unwrap();
 
-    fn append_stdout_line(&mut self, line: &str) {
-        self.open_stdout();
-        self.push_line(line.to_string());
+        assert_eq!(hint, "tabs | options: new, next, prev, <number>");
     }
 
-    fn append_stderr_line(&mut self, line: &str) {
-        self.open_stderr();
-        self.push_line(line.to_string());
+    #[test]
+    fn cross_device_move_removes_sources_and_journals_a_move() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest_dir).unwrap();
+        let source = temp_dir.path().join("note.txt");
+        std::fs::write(&source, "hello").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+
+        app.start_copy(
+            vec![source.clone()],
+            dest_dir.clone(),
+            vec![ConflictOverride {
+                source: source.clone(),
+                action: ConflictAction::Rename(dest_dir.join("moved.txt")),
+            }],
+            true,
+        );
+        let finished = std::cell::Cell::new(false);
+        drain_until(
+            &mut app,
+            |app| app.copy_worker.recv_timeout(EVENT_TIMEOUT),
+            |app, event| {
+                finished.set(matches!(event, CopyEvent::Finished(_)));
+                app.handle_copy_event(event);
+            },
+            |_| finished.get(),
+        );
+
+        assert!(!source.exists());
+        assert!(dest_dir.join("moved.txt").exists());
+        assert_eq!(
+            app.user_notice().map(|notice| notice.text),
+            Some("moved 1 files".to_string())
+        );
+        run_slash(&mut app, "undo");
+        assert!(source.exists());
+        assert!(!dest_dir.join("moved.txt").exists());
     }
 
-    fn finish(&mut self, result: &ShellExecutionResult) {
-        let exit = result.status_code.unwrap_or(-1);
-        self.push_line(format!(
-            "exit: {} duration: {}ms timestamp: {}",
-            exit, result.duration_ms, result.timestamp_ms
-        ));
-        if !self.stdout_opened && !result.stdout.trim().is_empty() {
-            self.open_stdout();
-            self.push_text(&result.stdout);
-        }
-        if !self.stderr_opened && !result.stderr.trim().is_empty() {
-            self.open_stderr();
-            self.push_text(&result.stderr);
+    #[test]
+    fn paste_command_copies_paths_into_current_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        std::fs::create_dir(&source_dir).unwrap();
+        std::fs::create_dir(&dest_dir).unwrap();
+        let source = source_dir.join("note.txt");
+        std::fs::write(&source, "hello").unwrap();
+        let mut app = load_app(dest_dir.clone());
+
+        app.activate_slash_input();
+        for ch in format!("paste {}", source.display()).chars() {
+            app.append_slash_char(ch);
         }
-        self.shrink_to_limits();
+        app.submit_slash_command();
+
+        drain_until(
+            &mut app,
+            |app| app.copy_worker.recv_timeout(EVENT_TIMEOUT),
+            App::handle_copy_event,
+            |app| !app.entries.is_empty(),
+        );
+        assert!(dest_dir.join("note.txt").exists());
+        assert_eq!(app.entries.len(), 1);
+        assert!(app.copy_progress().is_none());
+        assert_eq!(
+            app.user_notice().map(|notice| notice.source),
+            Some("copy".to_string())
+        );
     }
 
-    fn scroll_up(&mut self) {
-        if self.scroll_offset > 0 {
-            self.scroll_offset -= 1;
+    #[test]
+    fn paste_command_rejects_missing_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+
+        app.activate_slash_input();
+        for ch in "paste missing.txt".chars() {
+            app.append_slash_char(ch);
         }
+        app.submit_slash_command();
+
+        let feedback = app.slash_feedback().expect("feedback");
+        assert_eq!(feedback.status, FeedbackStatus::Error);
+        assert!(feedback.text.starts_with("paste: not found"));
     }
 
-    fn scroll_down(&mut self) {
-        let max_offset = self.lines.len().saturating_sub(1);
-        if self.scroll_offset < max_offset {
-            self.scroll_offset += 1;
+    fn run_slash(app: &mut App, input: &str) {
+        app.activate_slash_input();
+        for ch in input.chars() {
+            app.append_slash_char(ch);
         }
+        app.submit_slash_command();
     }
 
-    fn scroll_left(&mut self) {
-        self.horiz_offset = self.horiz_offset.saturating_sub(self.horiz_step());
-    }
+    #[test]
+    fn rename_command_renames_and_keeps_cursor_on_entry() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
 
-    fn scroll_right(&mut self) {
-        let max_offset = self.max_horiz_offset();
-        let next = self.horiz_offset + self.horiz_step();
-        self.horiz_offset = next.min(max_offset);
-    }
+        run_slash(&mut app, "rename z.txt");
 
-    fn page_up(&mut self) {
-        let step = self.page_step();
-        self.scroll_offset = self.scroll_offset.saturating_sub(step);
+        assert!(temp_dir.path().join("z.txt").exists());
+        assert!(!temp_dir.path().join("a.txt").exists());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("z.txt")
+        );
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.status),
+            Some(FeedbackStatus::Success)
+        );
     }
 
-    fn page_down(&mut self) {
-        let step = self.page_step();
-        let max_offset = self.lines.len().saturating_sub(1);
-        self.scroll_offset = (self.scroll_offset + step).min(max_offset);
-    }
+    #[test]
+    fn rename_command_reports_existing_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
 
-    fn home(&mut self) {
-        self.scroll_offset = 0;
+        run_slash(&mut app, "rename b.txt");
+
+        let feedback = app.slash_feedback().expect("feedback");
+        assert_eq!(feedback.status, FeedbackStatus::Error);
+        assert!(feedback.text.starts_with("rename: already exists"));
     }
 
-    fn end(&mut self) {
-        self.scroll_offset = self.lines.len().saturating_sub(1);
+    #[test]
+    fn move_command_moves_selected_entry_into_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        std::fs::create_dir(temp_dir.path().join("dest")).unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+
+        run_slash(&mut app, "move dest");
+
+        assert!(temp_dir.path().join("dest").join("a.txt").exists());
+        assert_eq!(
+            app.entries
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["dest"]
+        );
     }
 
-    fn view_text(&self, height: usize, width: usize) -> String {
-        if self.lines.is_empty() {
-            return "shell output: empty".to_string();
-        }
-        let start = self.scroll_offset.min(self.lines.len());
-        let end = (start + height).min(self.lines.len());
-        self.lines
-            .iter()
-            .skip(start)
-            .take(end.saturating_sub(start))
-            .map(|line| slice_line(line, self.horiz_offset, width))
-            .collect::<Vec<String>>()
-            .join("\n")
+    #[test]
+    fn delete_command_moves_entry_to_trash_and_restores_it() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let trash_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.trash_store = Some(TrashStore::new(trash_dir.path().to_path_buf()));
+
+        run_slash(&mut app, "delete");
+
+        assert!(!temp_dir.path().join("a.txt").exists());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("b.txt")
+        );
+
+        run_slash(&mut app, "trash");
+        assert_eq!(
+            app.trash_view_items().map(|(items, _)| items.len()),
+            Some(1)
+        );
+        app.restore_selected_trash_item();
+
+        assert!(temp_dir.path().join("a.txt").exists());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("a.txt")
+        );
+        assert_eq!(
+            app.trash_view_items().map(|(items, _)| items.len()),
+            Some(0)
+        );
     }
 
-    fn push_text(&mut self, text: &str) {
-        for line in text.lines() {
-            self.push_line(line.to_string());
-        }
+    #[test]
+    fn permanent_delete_requires_confirmation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+
+        run_slash(&mut app, "delete permanent");
+        assert_eq!(
+            app.confirmation_prompt().as_deref(),
+            Some("delete a.txt permanently? (y/N)")
+        );
+        app.cancel_pending();
+        assert!(temp_dir.path().join("a.txt").exists());
+
+        app.request_permanent_delete();
+        app.confirm_pending();
+
+        assert!(!temp_dir.path().join("a.txt").exists());
+        assert!(app.confirmation_prompt().is_none());
+        assert!(app.entries.is_empty());
     }
 
-    fn push_line(&mut self, line: String) {
-        self.total_bytes += line.len() + 1;
-        self.lines.push_back(line);
-        self.shrink_to_limits();
+    fn marked(app: &App) -> Vec<String> {
+        app.marked_names()
+            .map(|marks| marks.iter().cloned().collect())
+            .unwrap_or_default()
     }
 
-    fn open_stdout(&mut self) {
-        if !self.stdout_opened {
-            self.push_line("stdout:".to_string());
-            self.stdout_opened = true;
+    #[test]
+    fn selection_supports_toggle_range_invert_and_glob() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.rs", "c.rs", "d.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
         }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+
+        app.toggle_mark();
+        assert_eq!(marked(&app), vec!["a.txt"]);
+        assert_eq!(app.cursor, Some(1));
+
+        app.extend_selection_down();
+        app.extend_selection_down();
+        assert_eq!(marked(&app), vec!["a.txt", "b.rs", "c.rs", "d.txt"]);
+        app.extend_selection_up();
+        assert_eq!(marked(&app), vec!["a.txt", "b.rs", "c.rs"]);
+
+        app.invert_marks();
+        assert_eq!(marked(&app), vec!["d.txt"]);
+
+        run_slash(&mut app, "select *.rs");
+        assert_eq!(marked(&app), vec!["b.rs", "c.rs", "d.txt"]);
+
+        run_slash(&mut app, "select none");
+        assert_eq!(app.mark_count(), 0);
     }
 
-    fn open_stderr(&mut self) {
-        if !self.stderr_opened {
-            self.push_line("stderr:".to_string());
-            self.stderr_opened = true;
+    #[test]
+    fn move_command_moves_all_marked_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
         }
+        std::fs::create_dir(temp_dir.path().join("dest")).unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        run_slash(&mut app, "select [ab].txt");
+
+        run_slash(&mut app, "move dest");
+
+        assert!(temp_dir.path().join("dest/a.txt").exists());
+        assert!(temp_dir.path().join("dest/b.txt").exists());
+        assert!(temp_dir.path().join("c.txt").exists());
+        assert_eq!(app.mark_count(), 0);
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("move: moved 2 items")
+        );
     }
 
-    fn shrink_to_limits(&mut self) {
-        while self.lines.len() > self.max_lines || self.total_bytes > self.max_bytes {
-            if let Some(line) = self.lines.pop_front() {
-                self.total_bytes = self.total_bytes.saturating_sub(line.len() + 1);
-            } else {
-                break;
-            }
-        }
-        if self.scroll_offset >= self.lines.len() {
-            self.scroll_offset = self.lines.len().saturating_sub(1);
-        }
-        if self.horiz_offset > self.max_horiz_offset() {
-            self.horiz_offset = self.max_horiz_offset();
+    #[test]
+    fn move_conflicts_prompt_and_keep_both_applies_to_all() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        for name in ["a.txt", "b.txt"] {
+            std::fs::write(temp_dir.path().join(name), "new").unwrap();
+            std::fs::write(dest.join(name), "old").unwrap();
         }
-    }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        run_slash(&mut app, "select *.txt");
 
-    fn page_step(&self) -> usize {
-        20
-    }
+        run_slash(&mut app, "move dest");
 
-    fn horiz_step(&self) -> usize {
-        4
+        let prompt = app.conflict_prompt().unwrap();
+        assert_eq!(prompt.target, dest.join("a.txt"));
+        assert_eq!((prompt.index, prompt.total), (1, 2));
+        assert_eq!(
+            prompt.source_metadata.map(|metadata| metadata.size),
+            Some(3)
+        );
+        app.toggle_conflict_apply_to_all();
+        app.resolve_pending_conflict(ConflictChoice::KeepBoth);
+
+        assert!(!app.conflict_active());
+        assert_eq!(std::fs::read_to_string(dest.join("a.txt")).unwrap(), "old");
+        assert_eq!(
+            std::fs::read_to_string(dest.join("a (1).txt")).unwrap(),
+            "new"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dest.join("b (1).txt")).unwrap(),
+            "new"
+        );
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("move: moved 2 items")
+        );
     }
 
-    fn max_horiz_offset(&self) -> usize {
-        self.lines
-            .iter()
-            .map(|line| line.chars().count())
-            .max()
-            .unwrap_or(0)
-    }
-}
+    #[test]
+    fn move_conflict_overwrite_and_cancel() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "new").unwrap();
+        std::fs::write(dest.join("a.txt"), "old").unwrap();
+        let trash_dir = tempfile::tempdir().unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
 
-fn slice_line(line: &str, offset: usize, width: usize) -> String {
-    if width == 0 {
-        return String::new();
-    }
-    line.chars().skip(offset).take(width).collect()
-}
+        run_slash(&mut app, "move dest");
+        app.cancel_conflicts();
+        assert!(temp_dir.path().join("a.txt").exists());
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("cancelled")
+        );
 
-fn format_shell_start_notice(raw: &str) -> String {
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return "started".to_string();
-    }
-    let compact = compact_output(trimmed, 40).unwrap_or_else(|| trimmed.to_string());
-    format!("{compact}; started")
-}
+        run_slash(&mut app, "move dest");
+        app.resolve_pending_conflict(ConflictChoice::Overwrite);
+        assert!(temp_dir.path().join("a.txt").exists());
+        assert_eq!(std::fs::read_to_string(dest.join("a.txt")).unwrap(), "old");
 
-fn shell_result_notice_level(result: &ShellExecutionResult) -> UserNoticeLevel {
-    match result.status_code {
-        Some(0) => UserNoticeLevel::Success,
-        _ => UserNoticeLevel::Error,
-    }
-}
+        app.trash_store = Some(TrashStore::new(trash_dir.path().to_path_buf()));
+        run_slash(&mut app, "move dest");
+        app.resolve_pending_conflict(ConflictChoice::Overwrite);
+        assert!(!temp_dir.path().join("a.txt").exists());
+        assert_eq!(std::fs::read_to_string(dest.join("a.txt")).unwrap(), "new");
 
-fn format_shell_notice(result: &ShellExecutionResult) -> String {
-    let exit = result.status_code.unwrap_or(-1);
-    let mut parts = vec![format!("exit={exit}"), format!("{}ms", result.duration_ms)];
-    if let Some(stdout) = compact_output(&result.stdout, 80) {
-        parts.push(format!("stdout={stdout}"));
-    }
-    if let Some(stderr) = compact_output(&result.stderr, 80) {
-        parts.push(format!("stderr={stderr}"));
+        run_slash(&mut app, "undo");
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+            "new"
+        );
+        assert_eq!(std::fs::read_to_string(dest.join("a.txt")).unwrap(), "old");
+        run_slash(&mut app, "redo");
+        assert_eq!(std::fs::read_to_string(dest.join("a.txt")).unwrap(), "new");
+        assert_eq!(
+            TrashStore::new(trash_dir.path().to_path_buf()).list().len(),
+            1
+        );
     }
-    parts.join(" ")
-}
 
-fn compact_output(output: &str, max_len: usize) -> Option<String> {
-    let trimmed = output.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
-    let mut flat = trimmed.replace('\n', "\\n");
-    if flat.len() > max_len {
-        flat.truncate(max_len);
-        flat.push_str("...");
-    }
-    Some(flat)
-}
+    #[test]
+    fn copy_conflicts_follow_configured_policy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dest = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "new").unwrap();
+        std::fs::write(dest.join("a.txt"), "old").unwrap();
+        let config = Config {
+            conflict_policy: ConflictPolicy::Skip,
+            ..Config::default()
+        };
+        let mut app = load_app_with(temp_dir.path().to_path_buf(), config, Vec::new());
 
-#[cfg(test)]
-mod slash_tests {
-    use super::*;
-    use crate::tabs::TabSummary;
+        run_slash(&mut app, "copy dest");
 
-    #[derive(Debug)]
-    struct FixedClock {
-        now: Instant,
+        assert!(!app.conflict_active());
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("copy: nothing to copy")
+        );
+        assert_eq!(std::fs::read_to_string(dest.join("a.txt")).unwrap(), "old");
     }
 
-    impl AppClock for FixedClock {
-        fn now(&self) -> Instant {
-            self.now
+    #[test]
+    fn delete_command_trashes_all_marked_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let trash_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
         }
-    }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.trash_store = Some(TrashStore::new(trash_dir.path().to_path_buf()));
+        app.select_all();
+        app.toggle_mark();
 
-    fn empty_app() -> App {
-        App::new(PathBuf::from("."), Vec::new(), Vec::new(), None, false)
-    }
+        run_slash(&mut app, "delete");
 
-    #[derive(Clone)]
-    struct FixedConfigLoader {
-        config: Config,
+        assert!(temp_dir.path().join("a.txt").exists());
+        assert!(!temp_dir.path().join("b.txt").exists());
+        assert!(!temp_dir.path().join("c.txt").exists());
     }
 
-    impl ConfigLoader for FixedConfigLoader {
-        fn load(&self) -> Config {
-            self.config.clone()
+    #[test]
+    fn bulkrename_command_previews_and_applies_edited_names() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
         }
-    }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        run_slash(&mut app, "select [ab].txt");
 
-    #[derive(Clone)]
-    struct FixedSessionLoader {
-        tabs: Vec<SessionTab>,
+        run_slash(&mut app, "bulkrename");
+        let buffer_path = app.take_editor_request().expect("editor request");
+        assert_eq!(
+            std::fs::read_to_string(&buffer_path).unwrap(),
+            "a.txt\nb.txt\n"
+        );
+        assert!(app.take_editor_request().is_none());
+        std::fs::write(&buffer_path, "b.txt\na.txt\n").unwrap();
+        app.finish_bulk_rename_edit(Ok(()));
+
+        assert!(!buffer_path.exists());
+        assert_eq!(
+            app.bulk_rename_preview(),
+            Some(vec![
+                "a.txt -> b.txt  (cycle)".to_string(),
+                "b.txt -> a.txt  (cycle)".to_string()
+            ])
+        );
+        assert_eq!(
+            app.confirmation_prompt().as_deref(),
+            Some("rename 2 entries? (y/N)")
+        );
+        app.confirm_pending();
+
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+            "b.txt"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("b.txt")).unwrap(),
+            "a.txt"
+        );
+        assert_eq!(app.entries.len(), 3);
     }
 
-    impl SessionLoader for FixedSessionLoader {
-        fn load_tabs(&self) -> Vec<SessionTab> {
-            self.tabs.clone()
+    #[test]
+    fn bulkrename_failing_partway_journals_the_applied_renames() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
         }
-    }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        run_slash(&mut app, "select [ab].txt");
+        run_slash(&mut app, "bulkrename");
+        let buffer_path = app.take_editor_request().expect("editor request");
+        std::fs::write(&buffer_path, "x.txt\ny.txt\n").unwrap();
+        app.finish_bulk_rename_edit(Ok(()));
+        std::fs::remove_file(temp_dir.path().join("b.txt")).unwrap();
+
+        app.confirm_pending();
+        assert!(temp_dir.path().join("x.txt").exists());
+        assert!(
+            app.slash_feedback()
+                .is_some_and(|feedback| feedback.text.starts_with("bulkrename: renamed 1 entries"))
+        );
 
-    fn load_app_with(current_dir: PathBuf, config: Config, tabs: Vec<SessionTab>) -> App {
-        let config_loader = FixedConfigLoader { config };
-        let session_loader = FixedSessionLoader { tabs };
-        App::load_with(current_dir, &config_loader, &session_loader).unwrap()
+        run_slash(&mut app, "undo");
+        assert!(temp_dir.path().join("a.txt").exists());
+        assert!(!temp_dir.path().join("x.txt").exists());
     }
 
-    fn load_app(current_dir: PathBuf) -> App {
-        load_app_with(current_dir, Config::default(), Vec::new())
+    #[test]
+    fn undo_and_redo_commands_reverse_rename() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        run_slash(&mut app, "rename b.txt");
+
+        run_slash(&mut app, "undo");
+
+        assert!(temp_dir.path().join("a.txt").exists());
+        assert!(!temp_dir.path().join("b.txt").exists());
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("undo: rename a.txt -> b.txt")
+        );
+
+        run_slash(&mut app, "redo");
+        assert!(temp_dir.path().join("b.txt").exists());
+
+        run_slash(&mut app, "redo");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("redo: nothing to redo")
+        );
     }
 
-    fn app_with_two_tabs() -> (tempfile::TempDir, App, PathBuf, PathBuf) {
+    #[test]
+    fn mkdir_command_creates_parents_focuses_and_undoes() {
         let temp_dir = tempfile::tempdir().unwrap();
-        let dir_one = temp_dir.path().join("one");
-        let dir_two = temp_dir.path().join("two");
-        std::fs::create_dir(&dir_one).unwrap();
-        std::fs::create_dir(&dir_two).unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
 
-        let mut app = load_app(dir_one.clone());
-        app.new_tab().unwrap();
-        app.change_dir(dir_two.clone());
+        run_slash(&mut app, "mkdir new/inner");
 
-        (temp_dir, app, dir_one, dir_two)
+        assert!(temp_dir.path().join("new/inner").is_dir());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("new")
+        );
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("mkdir: created new/inner")
+        );
+
+        run_slash(&mut app, "mkdir new/inner");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.status),
+            Some(FeedbackStatus::Error)
+        );
+
+        run_slash(&mut app, "undo");
+        assert!(!temp_dir.path().join("new").exists());
     }
 
     #[test]
-    fn activate_slash_input_sets_prompt() {
-        let mut app = empty_app();
+    fn touch_command_creates_file_and_focuses_it() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
 
-        app.activate_slash_input();
+        run_slash(&mut app, "touch b.txt");
 
-        assert!(app.slash_input_active());
-        assert_eq!(app.slash_input_text(), "/");
+        assert!(temp_dir.path().join("b.txt").is_file());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("b.txt")
+        );
+
+        run_slash(&mut app, "touch a.txt");
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+            "a"
+        );
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("touch: updated a.txt")
+        );
     }
 
     #[test]
-    fn cancel_slash_input_clears_buffer() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
+    fn mkdir_completion_reuses_path_candidates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.slash_input_active = true;
+        app.slash_input_buffer = "/mkdir sr".to_string();
 
-        app.cancel_slash_input();
+        app.complete_slash_candidate();
 
-        assert!(!app.slash_input_active());
-        assert_eq!(app.slash_input_text(), "");
+        assert_eq!(app.slash_input_text(), "/mkdir src/");
     }
 
     #[test]
-    fn backspace_slash_input_exits_when_empty() {
-        let mut app = empty_app();
-        app.activate_slash_input();
+    fn cwd_command_completes_directories_and_expands_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src = temp_dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(temp_dir.path().join("setup.txt"), "").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.slash_input_active = true;
+        app.slash_input_buffer = "/cwd s".to_string();
+        assert_eq!(
+            app.slash_candidates()
+                .items
+                .into_iter()
+                .map(|candidate| candidate.text)
+                .collect::<Vec<_>>(),
+            vec!["src/".to_string()]
+        );
+        app.complete_slash_candidate();
+        app.submit_slash_command();
+        assert_eq!(app.current_dir, src);
 
-        app.backspace_slash_char();
+        run_slash(&mut app, "cwd ../setup.txt");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.clone()),
+            Some(format!(
+                "cwd: not a directory: {}",
+                temp_dir.path().join("setup.txt").display()
+            ))
+        );
+        run_slash(&mut app, "cwd $OX_SURELY_UNDEFINED_VAR/x");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("cwd: undefined variable: $OX_SURELY_UNDEFINED_VAR")
+        );
+        assert_eq!(app.current_dir, src);
 
-        assert!(!app.slash_input_active());
-        assert_eq!(app.slash_input_text(), "");
+        run_slash(&mut app, "cwd ..");
+        assert_eq!(app.current_dir, temp_dir.path());
+        if let Some(home) = std::env::var_os("HOME").map(PathBuf::from)
+            && home.is_dir()
+        {
+            run_slash(&mut app, "cwd ~");
+            assert_eq!(app.current_dir, home);
+        }
     }
 
     #[test]
-    fn submit_slash_command_parses_and_deactivates() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
-        app.append_slash_char('r');
-        app.append_slash_char('e');
-        app.append_slash_char('v');
-        app.append_slash_char('i');
-        app.append_slash_char('e');
-        app.append_slash_char('w');
+    fn cut_register_pastes_into_another_tab() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("dest")).unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.cursor = app.entries.iter().position(|entry| entry.name == "a.txt");
 
-        let command = app.submit_slash_command().unwrap();
+        run_slash(&mut app, "cut");
+        run_slash(&mut app, "tab new");
+        app.cursor = app.entries.iter().position(|entry| entry.name == "dest");
+        app.enter_selected_dir().unwrap();
+        app.activate_slash_input();
+        for ch in "paste".chars() {
+            app.append_slash_char(ch);
+        }
+        assert!(app.slash_hint().unwrap().ends_with("| cut: a.txt"));
+        app.submit_slash_command();
 
-        assert_eq!(command.name, "preview");
-        assert_eq!(command.args.len(), 0);
-        assert!(!app.slash_input_active());
-        assert_eq!(app.slash_input_text(), "");
+        assert!(temp_dir.path().join("dest/a.txt").exists());
+        assert!(!temp_dir.path().join("a.txt").exists());
+        assert!(app.register.is_none());
+        run_slash(&mut app, "paste");
         assert_eq!(
-            app.slash_feedback().unwrap().status,
-            FeedbackStatus::Success
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("paste: nothing to paste")
         );
     }
 
     #[test]
-    fn preview_toggle_turns_off_when_visible() {
-        let mut app = empty_app();
-        app.preview_visible = true;
-        app.preview_paused = false;
+    fn yank_register_keeps_contents_after_paste() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
+        }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        run_slash(&mut app, "select all");
 
-        let feedback = app.handle_slash_command(&SlashCommand {
-            name: "preview".to_string(),
-            args: Vec::new(),
-            raw: "/preview".to_string(),
-        });
+        run_slash(&mut app, "yank");
+
+        assert_eq!(app.mark_count(), 0);
+        assert_eq!(
+            app.register.as_ref().map(Register::summary),
+            Some("yank: a.txt, b.txt, c.txt +1".to_string())
+        );
+        run_slash(&mut app, "paste");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("paste: 4 conflicts to resolve")
+        );
+        app.cancel_conflicts();
+        assert!(app.register.is_some());
+    }
+
+    #[test]
+    fn path_command_supports_relative_and_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt"] {
+            std::fs::write(temp_dir.path().join(name), name).unwrap();
+        }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.cursor = Some(0);
 
-        assert!(!app.preview_visible());
-        assert!(app.preview_paused);
-        assert_eq!(feedback.text, "preview: off");
+        run_slash(&mut app, "path");
+        assert_eq!(
+            app.take_clipboard_request(),
+            Some(temp_dir.path().join("a.txt").display().to_string())
+        );
+
+        run_slash(&mut app, "select all");
+        run_slash(&mut app, "path relative lines");
+        assert_eq!(
+            app.take_clipboard_request(),
+            Some("a.txt\nb.txt".to_string())
+        );
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("path: copied 2 paths")
+        );
     }
 
     #[test]
-    fn preview_show_turns_on_and_resumes() {
-        let mut app = empty_app();
-        app.preview_visible = false;
-        app.preview_paused = true;
+    fn clip_command_copies_text_contents_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "hello").unwrap();
+        std::fs::write(temp_dir.path().join("b.bin"), [0xff, 0xfe]).unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.cursor = app.entries.iter().position(|entry| entry.name == "a.txt");
 
-        let feedback = app.handle_slash_command(&SlashCommand {
-            name: "preview".to_string(),
-            args: vec!["show".to_string()],
-            raw: "/preview show".to_string(),
-        });
+        run_slash(&mut app, "clip");
+        assert_eq!(app.take_clipboard_request(), Some("hello".to_string()));
 
-        assert!(app.preview_visible());
-        assert!(!app.preview_paused);
-        assert_eq!(feedback.text, "preview: on");
+        app.cursor = app.entries.iter().position(|entry| entry.name == "b.bin");
+        run_slash(&mut app, "clip");
+        assert_eq!(app.take_clipboard_request(), None);
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("clip: not a text file")
+        );
     }
 
     #[test]
-    fn preview_hide_turns_off_and_pauses() {
-        let mut app = empty_app();
-        app.preview_visible = true;
-        app.preview_paused = false;
+    fn drop_copy_requires_existing_paths_and_confirmation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        std::fs::create_dir(&source_dir).unwrap();
+        std::fs::create_dir(&dest_dir).unwrap();
+        let source = source_dir.join("note.txt");
+        std::fs::write(&source, "hello").unwrap();
+        let mut app = load_app(dest_dir.clone());
+
+        app.request_drop_copy(vec![source.clone(), source_dir.join("missing.txt")]);
+        assert!(app.confirmation_prompt().is_none());
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.status),
+            Some(FeedbackStatus::Error)
+        );
 
-        let feedback = app.handle_slash_command(&SlashCommand {
-            name: "preview".to_string(),
-            args: vec!["hide".to_string()],
-            raw: "/preview hide".to_string(),
-        });
+        app.request_drop_copy(vec![source.clone()]);
+        assert_eq!(
+            app.confirmation_prompt().as_deref(),
+            Some("copy 1 items here? (y/N)")
+        );
+        assert_eq!(
+            app.drop_copy_preview(),
+            Some(vec![source.display().to_string()])
+        );
+        app.confirm_pending();
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("paste: copying 1 items")
+        );
 
-        assert!(!app.preview_visible());
-        assert!(app.preview_paused);
-        assert_eq!(feedback.text, "preview: off");
+        drain_until(
+            &mut app,
+            |app| app.copy_worker.recv_timeout(EVENT_TIMEOUT),
+            App::handle_copy_event,
+            |app| !app.entries.is_empty(),
+        );
+        assert!(dest_dir.join("note.txt").exists());
     }
 
     #[test]
-    fn slash_history_moves_through_entries() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
-        app.append_slash_char('r');
-        app.append_slash_char('e');
-        app.append_slash_char('v');
-        app.append_slash_char('i');
-        app.append_slash_char('e');
-        app.append_slash_char('w');
-        app.submit_slash_command();
-        app.activate_slash_input();
-        app.append_slash_char('p');
-        app.append_slash_char('r');
-        app.append_slash_char('e');
-        app.append_slash_char('v');
-        app.append_slash_char('i');
-        app.append_slash_char('e');
-        app.append_slash_char('w');
-        app.append_slash_char(' ');
-        app.append_slash_char('s');
-        app.append_slash_char('h');
-        app.append_slash_char('o');
-        app.append_slash_char('w');
-        app.submit_slash_command();
+    fn sort_command_reorders_entries_per_tab() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for (name, body) in [("file10", "a"), ("file2", "bbb"), ("file1", "cc")] {
+            std::fs::write(temp_dir.path().join(name), body).unwrap();
+        }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        let names = |app: &App| {
+            app.entries
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>()
+        };
 
-        app.activate_slash_input();
-        app.slash_history_prev();
-        assert_eq!(app.slash_input_text(), "/preview show");
-        app.slash_history_prev();
-        assert_eq!(app.slash_input_text(), "/preview");
-        app.slash_history_next();
-        assert_eq!(app.slash_input_text(), "/preview show");
+        run_slash(&mut app, "sort natural");
+        assert_eq!(names(&app), vec!["file1", "file2", "file10"]);
+
+        run_slash(&mut app, "tab new");
+        run_slash(&mut app, "sort size reverse");
+        assert_eq!(names(&app), vec!["file2", "file1", "file10"]);
+        assert_eq!(app.tabs.session_tabs()[1].sort.label(), "size reverse");
+
+        run_slash(&mut app, "tab 1");
+        assert_eq!(names(&app), vec!["file1", "file2", "file10"]);
+        run_slash(&mut app, "sort sideways");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("sort: invalid args")
+        );
     }
 
     #[test]
-    fn slash_candidates_filter_by_prefix() {
+    fn sort_completion_suggests_keys_then_unused_flags() {
         let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
+        app.slash_input_active = true;
+        app.slash_input_buffer = "/sort na".to_string();
 
-        let candidates = app.slash_candidates();
-        let texts = candidates
+        app.complete_slash_candidate();
+        assert_eq!(app.slash_input_text(), "/sort name ");
+
+        app.slash_input_buffer = "/sort name reverse ".to_string();
+        let texts = app
+            .slash_candidates()
             .items
             .into_iter()
             .map(|candidate| candidate.text)
-            .collect::<Vec<String>>();
-
-        assert_eq!(texts, vec!["/preview".to_string(), "/paste".to_string()]);
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["dirs-first", "ignore-case"]);
     }
 
     #[test]
-    fn slash_completion_uses_first_candidate() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
+    fn filter_command_hides_entries_until_cleared() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a.rs", "b.rs", "c.txt", "gen_01.txt"] {
+            std::fs::write(temp_dir.path().join(name), "x").unwrap();
+        }
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src").join("lib.rs"), "x").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        let names = |app: &App| {
+            app.entries
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>()
+        };
 
-        app.complete_slash_candidate();
+        run_slash(&mut app, "filter *.rs");
+        assert_eq!(names(&app), vec!["a.rs", "b.rs"]);
+        assert_eq!(app.filter_label().as_deref(), Some("*.rs"));
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("filter: *.rs (2 shown)")
+        );
 
-        assert_eq!(app.slash_input_text(), "/preview ");
+        app.change_dir(temp_dir.path().join("src"));
+        app.refresh().unwrap();
+        assert_eq!(names(&app), vec!["lib.rs"]);
+
+        run_slash(&mut app, "tab new");
+        assert_eq!(app.filter_label(), None);
+        run_slash(&mut app, "filter regex ^gen_\\d+");
+        assert_eq!(names(&app), Vec::<String>::new());
+        run_slash(&mut app, "filter regex (");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("filter: unclosed group")
+        );
+
+        run_slash(&mut app, "tab 1");
+        assert_eq!(names(&app), vec!["lib.rs"]);
+        run_slash(&mut app, "filter clear");
+        assert_eq!(app.filter_label(), None);
+        assert_eq!(names(&app), vec!["lib.rs"]);
     }
 
     #[test]
-    fn slash_completion_skips_when_args_present() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
-        app.append_slash_char('r');
-        app.append_slash_char('e');
-        app.append_slash_char('v');
-        app.append_slash_char('i');
-        app.append_slash_char('e');
-        app.append_slash_char('w');
-        app.append_slash_char(' ');
-        app.append_slash_char('h');
+    fn where_command_streams_results_and_jumps_to_selection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let nested = temp_dir.path().join("src").join("ui");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("main_pane.rs"), "").unwrap();
+        std::fs::write(nested.join("mod.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("main.rs"), "").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
 
-        app.complete_slash_candidate();
+        run_slash(&mut app, "where main");
+        drain_until(
+            &mut app,
+            |app| app.finder_worker.recv_timeout(EVENT_TIMEOUT),
+            App::handle_find_event,
+            |app| !app.results_view.as_ref().is_some_and(|view| view.running),
+        );
+        let (title, lines, cursor) = app.results_view_lines().unwrap();
+        assert_eq!(title, "where main (2 found)");
+        assert_eq!(
+            lines,
+            vec![
+                "main.rs".to_string(),
+                Path::new("src")
+                    .join("ui")
+                    .join("main_pane.rs")
+                    .display()
+                    .to_string(),
+            ]
+        );
+        assert_eq!(cursor, Some(0));
 
-        assert_eq!(app.slash_input_text(), "/preview h");
+        app.move_results_cursor_down();
+        app.open_selected_result().unwrap();
+        assert!(!app.results_view_active());
+        assert_eq!(app.current_dir, nested);
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("main_pane.rs")
+        );
+
+        run_slash(&mut app, "where mod");
+        app.cancel_or_close_results();
+        assert!(app.results_view.as_ref().is_some_and(|view| !view.running));
+        app.cancel_or_close_results();
+        assert!(!app.results_view_active());
     }
 
     #[test]
-    fn shell_candidates_target_last_token_only() {
+    fn grep_command_lists_hits_and_focuses_preview_line() {
         let temp_dir = tempfile::tempdir().unwrap();
-        std::fs::write(temp_dir.path().join("build.sh"), "echo").unwrap();
-        std::fs::create_dir(temp_dir.path().join("dir")).unwrap();
-        std::fs::write(temp_dir.path().join("diary.txt"), "note").unwrap();
-
+        let src = temp_dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("lib.rs"), "mod a;\n// TODO: b\n").unwrap();
+        std::fs::write(temp_dir.path().join("blob.bin"), b"TODO\0").unwrap();
         let mut app = load_app(temp_dir.path().to_path_buf());
-        app.slash_input_active = true;
-        app.slash_input_buffer = "/shell ./build.sh ./di".to_string();
 
-        let candidates = app.slash_candidates();
-        let texts = candidates
-            .items
-            .into_iter()
-            .map(|candidate| candidate.text)
-            .collect::<Vec<String>>();
+        run_slash(&mut app, "grep todo");
+        drain_until(
+            &mut app,
+            |app| app.grep_worker.recv_timeout(EVENT_TIMEOUT),
+            App::handle_grep_event,
+            |app| !app.results_view.as_ref().is_some_and(|view| view.running),
+        );
+        let (title, lines, _) = app.results_view_lines().unwrap();
+        assert_eq!(title, "grep todo (1 found)");
+        assert_eq!(
+            lines,
+            vec![format!(
+                "{}:2: // TODO: b",
+                Path::new("src").join("lib.rs").display()
+            )]
+        );
 
-        assert_eq!(texts, vec!["./diary.txt".to_string(), "./dir/".to_string()]);
+        app.open_selected_result().unwrap();
+        assert_eq!(app.current_dir, src);
+        assert!(app.preview_visible());
+        assert_eq!(app.preview_focus_line(&src.join("lib.rs")), Some(2));
+        assert_eq!(app.preview_focus_line(&src.join("other.rs")), None);
+
+        run_slash(&mut app, "grep (");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("grep: unclosed group")
+        );
     }
 
     #[test]
-    fn shell_completion_replaces_last_token_and_appends_space() {
+    fn bookmark_commands_save_complete_and_jump() {
         let temp_dir = tempfile::tempdir().unwrap();
-        std::fs::create_dir(temp_dir.path().join("dir")).unwrap();
-        std::fs::write(temp_dir.path().join("diary.txt"), "note").unwrap();
-
+        let deep = temp_dir.path().join("a").join("b");
+        std::fs::create_dir_all(&deep).unwrap();
         let mut app = load_app(temp_dir.path().to_path_buf());
-        app.slash_input_active = true;
-        app.slash_input_buffer = "/shell ./build.sh ./di".to_string();
 
+        app.open_bookmark_picker();
+        assert!(!app.results_view_active());
+        run_slash(&mut app, "bookmark add my docs");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.status),
+            Some(FeedbackStatus::Error)
+        );
+
+        app.change_dir(deep.clone());
+        app.refresh().unwrap();
+        run_slash(&mut app, "bookmark add deep");
+        run_slash(&mut app, "bookmark add deeper");
+        app.change_dir(temp_dir.path().to_path_buf());
+        app.refresh().unwrap();
+        run_slash(&mut app, "bookmark add root");
+
+        app.activate_slash_input();
+        for ch in "go dee".chars() {
+            app.append_slash_char(ch);
+        }
+        let candidates = app.slash_candidates();
+        assert_eq!(
+            candidates
+                .items
+                .iter()
+                .map(|candidate| candidate.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["deep", "deeper"]
+        );
         app.complete_slash_candidate();
+        assert_eq!(app.slash_input_text(), "/go deep");
+        app.submit_slash_command();
+        assert_eq!(app.current_dir, deep);
 
-        assert_eq!(app.slash_input_text(), "/shell ./build.sh ./diary.txt ");
+        run_slash(&mut app, "bookmark rm");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.text.as_str()),
+            Some("bookmark: removed deep, deeper")
+        );
+        run_slash(&mut app, "go deep");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.status),
+            Some(FeedbackStatus::Error)
+        );
+
+        app.open_bookmark_picker();
+        let (title, lines, cursor) = app.results_view_lines().unwrap();
+        assert_eq!(title, "bookmarks (1)");
+        assert_eq!(lines, vec![format!("root  {}", temp_dir.path().display())]);
+        assert_eq!(cursor, Some(0));
+        app.change_dir(deep.clone());
+        app.refresh().unwrap();
+        app.open_selected_result().unwrap();
+        assert!(!app.results_view_active());
+        assert_eq!(app.current_dir, temp_dir.path());
     }
 
     #[test]
-    fn shell_completion_skips_space_for_directory() {
+    fn z_command_jumps_to_most_frecent_match() {
         let temp_dir = tempfile::tempdir().unwrap();
-        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
-
+        let often = temp_dir.path().join("alpha").join("project");
+        let rarely = temp_dir.path().join("beta").join("project-x");
+        std::fs::create_dir_all(&often).unwrap();
+        std::fs::create_dir_all(&rarely).unwrap();
         let mut app = load_app(temp_dir.path().to_path_buf());
-        app.slash_input_active = true;
-        app.slash_input_buffer = "/shell ./sr".to_string();
+        for path in [&often, &rarely, &often] {
+            app.change_dir(path.clone());
+            app.change_dir(temp_dir.path().to_path_buf());
+        }
+        app.refresh().unwrap();
 
-        app.complete_slash_candidate();
+        app.activate_slash_input();
+        for ch in "z proj".chars() {
+            app.append_slash_char(ch);
+        }
+        assert_eq!(
+            app.slash_candidates()
+                .items
+                .into_iter()
+                .map(|candidate| candidate.text)
+                .collect::<Vec<_>>(),
+            vec![often.display().to_string(), rarely.display().to_string()]
+        );
+        app.cancel_slash_input();
 
-        assert_eq!(app.slash_input_text(), "/shell ./src/");
+        run_slash(&mut app, "z proj");
+        assert_eq!(app.current_dir, often);
+        run_slash(&mut app, "z beta proj");
+        assert_eq!(app.current_dir, rarely);
+
+        std::fs::remove_dir(&often).unwrap();
+        run_slash(&mut app, "z alpha proj");
+        assert_eq!(
+            app.slash_feedback().map(|feedback| feedback.status),
+            Some(FeedbackStatus::Error)
+        );
+        assert_eq!(app.current_dir, rarely);
     }
 
     #[test]
-    fn shell_completion_keeps_existing_shell_output() {
+    fn tree_command_expands_and_collapses_directories_in_place() {
         let temp_dir = tempfile::tempdir().unwrap();
-        std::fs::write(temp_dir.path().join("diary.txt"), "note").unwrap();
-        let request = ShellCommandRequest::new(temp_dir.path().to_path_buf(), "echo hi").unwrap();
-        let result = ShellExecutionResult {
-            status_code: Some(0),
-            stdout: "ok".to_string(),
-            stderr: String::new(),
-            duration_ms: 12,
-            timestamp_ms: 1,
+        let nested = temp_dir.path().join("src").join("ui");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("mod.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("src").join("main.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("z.txt"), "").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        let names = |app: &App| {
+            app.entries
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>()
+        };
+        let wait_for_tree = |app: &mut App| {
+            drain_until(
+                app,
+                |app| app.tree_worker.recv_timeout(EVENT_TIMEOUT),
+                |app, event| {
+                    app.handle_tree_event(event);
+                    app.rebuild_tree();
+                },
+                |app| app.tree_pending.is_empty(),
+            );
         };
 
-        let mut app = load_app(temp_dir.path().to_path_buf());
-        app.shell_output_view.reset(&request, &result);
-        let before = app.shell_output_view.lines.clone();
-        app.slash_input_active = true;
-        app.slash_input_buffer = "/shell ./di".to_string();
+        run_slash(&mut app, "tree");
+        assert!(app.tree_enabled());
+        app.cursor = Some(0);
+        app.expand_selected();
+        wait_for_tree(&mut app);
+        assert_eq!(names(&app), vec!["src", "src/main.rs", "src/ui", "z.txt"]);
 
-        app.complete_slash_candidate();
+        app.expand_selected();
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("src/main.rs")
+        );
+        app.cursor = Some(2);
+        app.expand_selected();
+        wait_for_tree(&mut app);
+        assert_eq!(
+            names(&app),
+            vec!["src", "src/main.rs", "src/ui", "src/ui/mod.rs", "z.txt"]
+        );
+        assert_eq!(
+            app.selected_entry_path(),
+            Some(temp_dir.path().join("src").join("ui"))
+        );
 
-        assert_eq!(app.shell_output_view.lines, before);
-    }
+        app.cursor = Some(3);
+        app.collapse_selected().unwrap();
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("src/ui")
+        );
+        assert_eq!(names(&app), vec!["src", "src/main.rs", "src/ui", "z.txt"]);
 
-    #[test]
-    fn load_restores_multiple_tabs_from_session() {
-        let temp_dir = tempfile::tempdir().unwrap();
-        let dir_one = temp_dir.path().join("one");
-        let dir_two = temp_dir.path().join("two");
-        std::fs::create_dir(&dir_one).unwrap();
-        std::fs::create_dir(&dir_two).unwrap();
+        run_slash(&mut app, "tree off");
+        assert!(!app.tree_enabled());
+        assert_eq!(names(&app), vec!["src", "z.txt"]);
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("src")
+        );
+    }
 
-        let session_tabs = vec![
-            SessionTab {
-                tab_id: 4,
-                path: dir_one.clone(),
-                theme_name: "Night Harbor".to_string(),
-            },
-            SessionTab {
-                tab_id: 8,
-                path: dir_two.clone(),
-                theme_name: "Glacier Coast".to_string(),
+    fn wait_for_tree(app: &mut App) {
+        drain_until(
+            app,
+            |app| app.tree_worker.recv_timeout(EVENT_TIMEOUT),
+            |app, event| {
+                app.handle_tree_event(event);
+                app.rebuild_tree();
             },
-        ];
-        let app = load_app_with(PathBuf::from("/fallback"), Config::default(), session_tabs);
+            |app| app.tree_pending.is_empty(),
+        );
+    }
 
-        assert_eq!(app.current_dir, dir_one);
-        assert_eq!(app.tabs.count(), 2);
-        let restored_tabs = app.tabs.session_tabs();
-        assert_eq!(restored_tabs[0].path, dir_one);
-        assert_eq!(restored_tabs[1].path, dir_two);
-        assert_eq!(restored_tabs[0].theme_name, "Night Harbor");
-        assert_eq!(restored_tabs[1].theme_name, "Glacier Coast");
+    fn load_tree_app(current_dir: PathBuf) -> App {
+        let mut app = load_app(current_dir);
+        run_slash(&mut app, "tree");
+        app.cursor = app.entries.iter().position(|entry| entry.name == "src");
+        app.expand_selected();
+        wait_for_tree(&mut app);
+        app
+    }
+
+    fn select_row(app: &mut App, name: &str) {
+        app.cursor = app.entries.iter().position(|entry| entry.name == name);
+        assert!(app.cursor.is_some(), "missing row {name}");
     }
 
     #[test]
-    fn color_command_sets_theme_for_active_tab() {
-        let mut app = empty_app();
+    fn tree_rows_expand_to_paths_relative_to_current_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src").join("main.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("z.txt"), "").unwrap();
+        let mut app = load_tree_app(temp_dir.path().to_path_buf());
 
-        let feedback = app.handle_slash_command(&SlashCommand {
-            name: "color".to_string(),
-            args: vec!["Glacier".to_string(), "Coast".to_string()],
-            raw: "/color Glacier Coast".to_string(),
-        });
+        select_row(&mut app, "src/main.rs");
+        assert_eq!(app.action_target_names(), vec!["src/main.rs"]);
 
-        assert_eq!(feedback.status, FeedbackStatus::Success);
-        assert_eq!(feedback.text, "theme: Glacier Coast");
-        assert_eq!(app.tabs.active_theme_id(), ColorThemeId::GlacierCoast);
+        run_slash(&mut app, "select *");
+        assert_eq!(
+            app.action_target_names(),
+            vec!["src", "src/main.rs", "z.txt"]
+        );
     }
 
     #[test]
-    fn color_command_rejects_unknown_theme() {
-        let mut app = empty_app();
+    fn rename_on_tree_row_stays_in_its_parent_and_keeps_focus() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let src = temp_dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("main.rs"), "").unwrap();
+        let mut app = load_tree_app(temp_dir.path().to_path_buf());
+        select_row(&mut app, "src/main.rs");
 
-        let feedback = app.handle_slash_command(&SlashCommand {
-            name: "color".to_string(),
-            args: vec!["Unknown".to_string()],
-            raw: "/color Unknown".to_string(),
-        });
+        run_slash(&mut app, "rename lib.rs");
+        wait_for_tree(&mut app);
 
-        assert_eq!(feedback.status, FeedbackStatus::Error);
-        assert!(feedback.text.contains("unknown"));
+        assert!(src.join("lib.rs").exists());
+        assert!(!temp_dir.path().join("lib.rs").exists());
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("src/lib.rs")
+        );
     }
 
     #[test]
-    fn slash_candidates_show_color_themes_with_descriptions() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('c');
-        app.append_slash_char('o');
-        app.append_slash_char('l');
-        app.append_slash_char('o');
-        app.append_slash_char('r');
-        app.append_slash_char(' ');
+    fn bulkrename_rejects_nested_tree_rows() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src").join("main.rs"), "").unwrap();
+        let mut app = load_tree_app(temp_dir.path().to_path_buf());
 
-        let candidates = app.slash_candidates();
-        let first = candidates.items.first().unwrap();
+        run_slash(&mut app, "bulkrename");
 
-        assert!(first.text.contains("Glacier"));
-        assert!(
-            first
-                .description
-                .as_deref()
-                .unwrap_or("")
-                .contains("success")
+        let feedback = app.slash_feedback().expect("feedback");
+        assert_eq!(feedback.status, FeedbackStatus::Error);
+        assert_eq!(
+            feedback.text,
+            "bulkrename: nested tree rows are not supported"
         );
+        assert!(app.take_editor_request().is_none());
     }
 
     #[test]
-    fn slash_hint_shows_description_and_options() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
-        app.append_slash_char('r');
-        app.append_slash_char('e');
-        app.append_slash_char('v');
-        app.append_slash_char('i');
-        app.append_slash_char('e');
-        app.append_slash_char('w');
+    fn toggle_ignored_hides_gitignored_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("target")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join(".gitignore"), "target/\n").unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.cursor = app.entries.iter().position(|entry| entry.name == "target");
 
-        let hint = app.slash_hint().unwrap();
+        app.toggle_ignored().unwrap();
+        assert!(app.hide_ignored);
+        assert_eq!(
+            app.entries
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["src"]
+        );
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("src")
+        );
 
-        assert_eq!(hint, "toggle preview | options: show, hide");
+        app.toggle_ignored().unwrap();
+        assert_eq!(app.entries.len(), 2);
     }
 
     #[test]
-    fn slash_hint_hides_when_args_present() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('p');
-        app.append_slash_char('r');
-        app.append_slash_char('e');
-        app.append_slash_char('v');
-        app.append_slash_char('i');
-        app.append_slash_char('e');
-        app.append_slash_char('w');
-        app.append_slash_char(' ');
-        app.append_slash_char('s');
+    fn streamed_listing_reads_gitignore_once_when_it_starts() {
+        use std::sync::{Mutex, mpsc};
 
-        let hint = app.slash_hint();
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("target")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join(".gitignore"), "target/\n").unwrap();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.hide_ignored = true;
+        app.listing_budget = Duration::ZERO;
+        app.listing_worker =
+            ListingWorker::with_lister(Arc::new(move |request, is_cancelled, emit| {
+                let _ = release_rx.lock().unwrap().recv();
+                crate::core::list_entries_chunked(
+                    &request.dir,
+                    request.show_hidden,
+                    &request.sort,
+                    is_cancelled,
+                    emit,
+                )
+            }));
+
+        app.refresh().unwrap();
+        assert!(app.listing_progress().is_some());
+        std::fs::write(temp_dir.path().join(".gitignore"), "").unwrap();
+        release_tx.send(()).unwrap();
+        drain_until(
+            &mut app,
+            |app| app.listing_worker.recv_timeout(EVENT_TIMEOUT),
+            App::handle_listing_event,
+            |app| app.listing_progress().is_none(),
+        );
 
-        assert!(hint.is_none());
+        assert_eq!(
+            app.entries
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["src"]
+        );
     }
 
+    #[cfg(unix)]
     #[test]
-    fn slash_feedback_expires_after_ttl() {
-        let mut app = empty_app();
-        app.slash_feedback = Some(SlashFeedback {
-            text: "preview: on".to_string(),
-            status: FeedbackStatus::Success,
-            tabs: None,
-            expires_at: std::time::Instant::now() - std::time::Duration::from_secs(1),
-        });
+    fn hidden_ignored_entries_keep_directory_symlinks_enterable() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::create_dir(root.join("real")).unwrap();
+        std::os::unix::fs::symlink("real", root.join("dir-link")).unwrap();
+        std::fs::write(root.join(".gitignore"), "dir-link/\nreal/\n").unwrap();
+        let mut app = load_app(root.to_path_buf());
+
+        app.toggle_ignored().unwrap();
+        assert_eq!(
+            app.entries
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["dir-link"]
+        );
+        app.cursor = Some(0);
+        app.enter_selected_dir().unwrap();
+        assert_eq!(app.current_dir, root.join("dir-link"));
+    }
 
-        assert!(app.slash_feedback().is_none());
+    #[test]
+    fn watcher_reloads_entries_keeping_the_selection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "").unwrap();
+        let config = Config {
+            watch_interval_ms: 10,
+            ..Config::default()
+        };
+        let mut app = load_app_with(temp_dir.path().to_path_buf(), config, Vec::new());
+        app.poll_watch_events();
+        assert!(
+            app.fs_watcher
+                .as_ref()
+                .is_some_and(|watcher| watcher.wait_watched(EVENT_TIMEOUT))
+        );
+
+        std::fs::write(temp_dir.path().join("a.txt"), "").unwrap();
+        drain_until(
+            &mut app,
+            |app| app.fs_watcher.as_ref()?.recv_timeout(EVENT_TIMEOUT),
+            App::handle_watch_event,
+            |app| app.watch_refresh_pending,
+        );
+        app.poll_watch_events();
+
+        assert_eq!(
+            app.entries
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a.txt", "b.txt"]
+        );
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("b.txt")
+        );
+        assert!(app.take_fs_changed());
+        assert!(!app.take_fs_changed());
     }
 
     #[test]
-    fn slash_feedback_uses_injected_clock() {
-        let now = Instant::now();
-        let feedback = SlashFeedback {
-            text: "preview: on".to_string(),
-            status: FeedbackStatus::Success,
-            tabs: None,
-            expires_at: now + std::time::Duration::from_secs(1),
+    fn slow_listing_streams_entries_and_keeps_the_focus() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let big = temp_dir.path().join("big");
+        std::fs::create_dir(&big).unwrap();
+        for index in 0..3000 {
+            std::fs::write(big.join(format!("{index:04}.txt")), "").unwrap();
+        }
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.listing_budget = Duration::ZERO;
+        let wait_for_listing = |app: &mut App| {
+            drain_until(
+                app,
+                |app| app.listing_worker.recv_timeout(EVENT_TIMEOUT),
+                App::handle_listing_event,
+                |app| app.listing_progress().is_none(),
+            );
         };
-        let clock = FixedClock {
-            now: now + std::time::Duration::from_secs(2),
+
+        app.enter_selected_dir().unwrap();
+        assert_eq!(app.current_dir, big);
+        assert!(app.listing_progress().is_some());
+        wait_for_listing(&mut app);
+        assert_eq!(app.listing_progress(), None);
+        assert_eq!(app.entries.len(), 3000);
+        assert_eq!(app.entries[0].name, "0000.txt");
+
+        app.refresh_with_selection(Some("2500.txt")).unwrap();
+        assert!(app.listing_progress().is_some());
+        wait_for_listing(&mut app);
+        assert_eq!(
+            app.selected_entry().map(|entry| entry.name.as_str()),
+            Some("2500.txt")
+        );
+    }
+
+    #[test]
+    fn stalled_listing_is_abandoned_and_retried() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::{Mutex, mpsc};
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let stalled = temp_dir.path().join("stalled");
+        let healthy = temp_dir.path().join("healthy");
+        std::fs::create_dir(&stalled).unwrap();
+        std::fs::create_dir(&healthy).unwrap();
+        std::fs::write(stalled.join("b.txt"), "").unwrap();
+        std::fs::write(healthy.join("a.txt"), "").unwrap();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let blocking = Arc::new(AtomicBool::new(true));
+        let lister_blocking = Arc::clone(&blocking);
+        let blocked_dir = stalled.clone();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.listing_budget = Duration::ZERO;
+        app.listing_worker =
+            ListingWorker::with_lister(Arc::new(move |request, is_cancelled, emit| {
+                if request.dir == blocked_dir && lister_blocking.load(Ordering::Relaxed) {
+                    let _ = release_rx.lock().unwrap().recv();
+                }
+                crate::core::list_entries_chunked(
+                    &request.dir,
+                    request.show_hidden,
+                    &request.sort,
+                    is_cancelled,
+                    emit,
+                )
+            }));
+        let wait_for_listing = |app: &mut App| {
+            drain_until(
+                app,
+                |app| app.listing_worker.recv_timeout(EVENT_TIMEOUT),
+                App::handle_listing_event,
+                |app| app.listing_progress().is_none(),
+            );
+        };
+        let names = |app: &App| {
+            app.entries
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>()
         };
 
-        assert!(feedback.is_expired_with(&clock));
+        app.change_dir(stalled.clone());
+        app.refresh().unwrap();
+        assert!(app.listing_progress().is_some());
+        app.change_dir(healthy.clone());
+        app.refresh().unwrap();
+        wait_for_listing(&mut app);
+        assert_eq!(names(&app), vec!["a.txt"]);
+
+        app.change_dir(stalled.clone());
+        app.refresh().unwrap();
+        assert!(app.listing_progress().is_some());
+        blocking.store(false, Ordering::Relaxed);
+        app.clock = Arc::new(FixedClock {
+            now: Instant::now() + LISTING_TIMEOUT,
+        });
+        app.poll_listing_events();
+        assert!(
+            app.user_notice()
+                .is_some_and(|notice| notice.text.ends_with("retrying"))
+        );
+        wait_for_listing(&mut app);
+        assert_eq!(app.current_dir, stalled);
+        assert_eq!(names(&app), vec!["b.txt"]);
+        drop(release_tx);
     }
 
     #[test]
-    fn slash_hint_shows_tab_options() {
-        let mut app = empty_app();
-        app.activate_slash_input();
-        app.append_slash_char('t');
-        app.append_slash_char('a');
-        app.append_slash_char('b');
+    fn parent_listing_arrives_from_the_worker() {
+        use std::sync::{Mutex, mpsc};
 
-        let hint = app.slash_hint().unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let nested = temp_dir.path().join("src");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), "").unwrap();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.listing_budget = Duration::ZERO;
+        app.parent_listing_worker =
+            ListingWorker::with_lister(Arc::new(move |request, is_cancelled, emit| {
+                let _ = release_rx.lock().unwrap().recv();
+                crate::core::list_entries_chunked(
+                    &request.dir,
+                    request.show_hidden,
+                    &request.sort,
+                    is_cancelled,
+                    emit,
+                )
+            }));
+        app.cursor = app.entries.iter().position(|entry| entry.name == "src");
 
-        assert_eq!(hint, "tabs | options: new, next, prev, <number>");
+        app.enter_selected_dir().unwrap();
+        assert_eq!(app.current_dir, nested);
+        assert!(app.parent_entries.is_empty());
+        assert!(app.parent_listing.is_some());
+        release_tx.send(()).unwrap();
+        drain_until(
+            &mut app,
+            |app| app.parent_listing_worker.recv_timeout(EVENT_TIMEOUT),
+            App::handle_parent_listing_event,
+            |app| app.parent_listing.is_none(),
+        );
+        assert_eq!(
+            app.parent_entries
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["README.md", "src"]
+        );
     }
 
     #[test]
@@ -2043,6 +6541,39 @@ mod slash_tests {
         assert!(notice.text.contains("started"));
     }
 
+    #[test]
+    fn frecency_is_saved_off_thread_and_failures_emit_user_notice() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let visited = temp_dir.path().join("visited");
+        std::fs::create_dir(&visited).unwrap();
+        let mut app = load_app(temp_dir.path().to_path_buf());
+        app.frecency = Frecency::load(temp_dir.path());
+
+        app.change_dir(visited.clone());
+        assert!(app.frecency_save_deadline.is_some());
+        assert!(!temp_dir.path().join("frecency.json").exists());
+        app.force_frecency_save();
+        drain_until(
+            &mut app,
+            |app| app.frecency_worker.recv_timeout(EVENT_TIMEOUT),
+            App::handle_frecency_event,
+            |_| temp_dir.path().join("frecency.json").exists(),
+        );
+        assert_eq!(Frecency::load(temp_dir.path()), app.frecency);
+        assert!(app.user_notice().is_none());
+
+        let blocked = temp_dir.path().join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        app.frecency = Frecency::load(&blocked);
+        app.change_dir(temp_dir.path().to_path_buf());
+        app.force_frecency_save();
+        let event = app.frecency_worker.recv_timeout(EVENT_TIMEOUT).unwrap();
+        app.handle_frecency_event(event);
+        let notice = app.user_notice().expect("notice");
+        assert_eq!(notice.source, "frecency");
+        assert_eq!(notice.level, UserNoticeLevel::Warn);
+    }
+
     #[test]
     fn session_save_failure_emits_user_notice() {
         let mut app = empty_app();
@@ -2183,11 +6714,28 @@ mod slash_tests {
     }
 }
 
-fn list_parent_entries(current_dir: &Path, show_hidden: bool) -> AppResult<Vec<Entry>> {
+fn list_parent_entries(
+    current_dir: &Path,
+    show_hidden: bool,
+    sort: &SortMode,
+) -> AppResult<Vec<Entry>> {
     let Some(parent) = current_dir.parent() else {
         return Ok(Vec::new());
     };
-    list_entries(parent, show_hidden)
+    list_entries_sorted(parent, show_hidden, sort)
+}
+
+fn retain_unignored(dir: &Path, ignore: &GitIgnore, entries: &mut Vec<Entry>) {
+    entries.retain(|entry| !ignore.is_ignored(&dir.join(&entry.name), entry.is_plain_dir()));
+}
+
+fn listing_timeout_text(listing: &PendingListing) -> String {
+    let retry = if listing.retried { "" } else { "; retrying" };
+    format!(
+        "{}: no response after {}s{retry}",
+        listing.dir.display(),
+        LISTING_TIMEOUT.as_secs()
+    )
 }
 
 fn resolve_cursor(
@@ -2257,16 +6805,7 @@ mod tests {
     fn move_cursor_up_stops_at_top() {
         let mut app = App::new(
             PathBuf::from("."),
-            vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("a.txt"), Entry::file("b.txt")],
             Vec::new(),
             Some(0),
             false,
@@ -2281,16 +6820,7 @@ mod tests {
     fn move_cursor_up_moves_one_step() {
         let mut app = App::new(
             PathBuf::from("."),
-            vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("a.txt"), Entry::file("b.txt")],
             Vec::new(),
             Some(1),
             false,
@@ -2305,16 +6835,7 @@ mod tests {
     fn move_cursor_down_stops_at_bottom() {
         let mut app = App::new(
             PathBuf::from("."),
-            vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("a.txt"), Entry::file("b.txt")],
             Vec::new(),
             Some(1),
             false,
@@ -2329,16 +6850,7 @@ mod tests {
     fn move_cursor_down_moves_one_step() {
         let mut app = App::new(
             PathBuf::from("."),
-            vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("a.txt"), Entry::file("b.txt")],
             Vec::new(),
             Some(0),
             false,
@@ -2354,18 +6866,9 @@ mod tests {
         let mut app = App::new(
             PathBuf::from("."),
             vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "c.txt".to_string(),
-                    is_dir: false,
-                },
+                Entry::file("a.txt"),
+                Entry::file("b.txt"),
+                Entry::file("c.txt"),
             ],
             Vec::new(),
             Some(1),
@@ -2382,18 +6885,9 @@ mod tests {
         let mut app = App::new(
             PathBuf::from("."),
             vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "c.txt".to_string(),
-                    is_dir: false,
-                },
+                Entry::file("a.txt"),
+                Entry::file("b.txt"),
+                Entry::file("c.txt"),
             ],
             Vec::new(),
             Some(1),
@@ -2409,16 +6903,7 @@ mod tests {
     fn move_cursor_home_moves_to_first_entry() {
         let mut app = App::new(
             PathBuf::from("."),
-            vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("a.txt"), Entry::file("b.txt")],
             Vec::new(),
             Some(1),
             false,
@@ -2433,16 +6918,7 @@ mod tests {
     fn move_cursor_end_moves_to_last_entry() {
         let mut app = App::new(
             PathBuf::from("."),
-            vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("a.txt"), Entry::file("b.txt")],
             Vec::new(),
             Some(0),
             false,
@@ -2459,10 +6935,7 @@ mod tests {
         let child_dir = temp_dir.path().join("child");
         std::fs::create_dir(&child_dir).unwrap();
 
-        let entries = vec![Entry {
-            name: "child".to_string(),
-            is_dir: true,
-        }];
+        let entries = vec![Entry::dir("child")];
         let mut app = App::new(
             temp_dir.path().to_path_buf(),
             entries,
@@ -2484,14 +6957,14 @@ mod tests {
         let file = temp_dir.path().join("note.txt");
         std::fs::write(&file, "hi").unwrap();
 
-        let entries = vec![Entry {
-            name: "note.txt".to_string(),
-            is_dir: false,
-        }];
+        let entries = vec![Entry::file("note.txt")];
         let config = Config {
             default_theme: None,
             allow_shell: false,
             allow_opener: true,
+            conflict_policy: ConflictPolicy::Ask,
+            search_mode: SearchMode::Prefix,
+            watch_interval_ms: 0,
         };
         let mut app = App::new_with_config(
             temp_dir.path().to_path_buf(),
@@ -2517,14 +6990,14 @@ mod tests {
         let file = temp_dir.path().join("note.txt");
         std::fs::write(&file, "hi").unwrap();
 
-        let entries = vec![Entry {
-            name: "note.txt".to_string(),
-            is_dir: false,
-        }];
+        let entries = vec![Entry::file("note.txt")];
         let config = Config {
             default_theme: None,
             allow_shell: false,
             allow_opener: false,
+            conflict_policy: ConflictPolicy::Ask,
+            search_mode: SearchMode::Prefix,
+            watch_interval_ms: 0,
         };
         let mut app = App::new_with_config(
             temp_dir.path().to_path_buf(),
@@ -2553,10 +7026,7 @@ mod tests {
         let file = temp_dir.path().join("note.txt");
         std::fs::write(&file, "hi").unwrap();
 
-        let entries = vec![Entry {
-            name: "note.txt".to_string(),
-            is_dir: false,
-        }];
+        let entries = vec![Entry::file("note.txt")];
         let mut app = App::new(
             temp_dir.path().to_path_buf(),
             entries,
@@ -2615,13 +7085,7 @@ mod tests {
 
         app.toggle_hidden().unwrap();
 
-        assert_eq!(
-            app.entries,
-            vec![Entry {
-                name: ".secret".to_string(),
-                is_dir: false,
-            }]
-        );
+        assert_eq!(app.entries, vec![Entry::file(".secret")]);
     }
 
     #[test]
@@ -2666,18 +7130,9 @@ mod tests {
         let mut app = App::new(
             PathBuf::from("."),
             vec![
-                Entry {
-                    name: "alpha.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "beta.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "bravo.txt".to_string(),
-                    is_dir: false,
-                },
+                Entry::file("alpha.txt"),
+                Entry::file("beta.txt"),
+                Entry::file("bravo.txt"),
             ],
             Vec::new(),
             Some(0),
@@ -2698,16 +7153,7 @@ mod tests {
     fn incremental_search_keeps_cursor_when_no_match() {
         let mut app = App::new(
             PathBuf::from("."),
-            vec![
-                Entry {
-                    name: "alpha.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "beta.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("alpha.txt"), Entry::file("beta.txt")],
             Vec::new(),
             Some(1),
             false,
@@ -2723,18 +7169,9 @@ mod tests {
         let mut app = App::new(
             PathBuf::from("."),
             vec![
-                Entry {
-                    name: "alpha.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "beta.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "bravo.txt".to_string(),
-                    is_dir: false,
-                },
+                Entry::file("alpha.txt"),
+                Entry::file("beta.txt"),
+                Entry::file("bravo.txt"),
             ],
             Vec::new(),
             Some(0),
@@ -2752,6 +7189,51 @@ mod tests {
         assert_eq!(app.cursor, Some(0));
     }
 
+    #[test]
+    fn search_modes_match_and_cycle_through_hits() {
+        let entries = ["alpha.txt", "Beta.rs", "gamma.rs", "delta.md"]
+            .into_iter()
+            .map(Entry::file)
+            .collect::<Vec<_>>();
+        let config = Config {
+            search_mode: SearchMode::Fuzzy,
+            ..Config::default()
+        };
+        let mut app = App::new_with_config(
+            PathBuf::from("."),
+            entries,
+            Vec::new(),
+            Some(0),
+            false,
+            config,
+        );
+
+        for ch in "brs".chars() {
+            app.append_search_char(ch);
+        }
+        assert_eq!(app.cursor, Some(1));
+        app.reset_search();
+        for ch in ".rs".chars() {
+            app.append_search_char(ch);
+        }
+        assert_eq!(app.cursor, Some(1));
+        app.search_next();
+        assert_eq!(app.cursor, Some(2));
+        app.search_next();
+        assert_eq!(app.cursor, Some(1));
+        app.search_prev();
+        assert_eq!(app.cursor, Some(2));
+
+        app.reset_search();
+        app.search_mode = SearchMode::Regex;
+        for ch in "a\\.(md|txt)$".chars() {
+            app.append_search_char(ch);
+        }
+        assert_eq!(app.cursor, Some(0));
+        app.search_prev();
+        assert_eq!(app.cursor, Some(3));
+    }
+
     #[test]
     fn new_tab_adds_and_selects() {
         let mut app = App::new(PathBuf::from("/tmp"), Vec::new(), Vec::new(), None, false);
@@ -2,7 +2,9 @@ use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock, mpsc};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::core::ColorThemeId;
+use crate::core::{ColorThemeId, ConflictPolicy, SearchMode};
+
+const DEFAULT_WATCH_INTERVAL_MS: u64 = 1000;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfigEvent {
@@ -14,6 +16,9 @@ pub struct Config {
     pub default_theme: Option<ColorThemeId>,
     pub allow_shell: bool,
     pub allow_opener: bool,
+    pub conflict_policy: ConflictPolicy,
+    pub search_mode: SearchMode,
+    pub watch_interval_ms: u64,
 }
 
 impl Default for Config {
@@ -22,6 +27,9 @@ impl Default for Config {
             default_theme: None,
             allow_shell: false,
             allow_opener: default_allow_opener(),
+            conflict_policy: ConflictPolicy::default(),
+            search_mode: SearchMode::default(),
+            watch_interval_ms: DEFAULT_WATCH_INTERVAL_MS,
         }
     }
 }
@@ -42,6 +50,9 @@ fn parse_config(content: &str) -> Config {
     let mut default_theme = None;
     let mut allow_shell = false;
     let mut allow_opener = default_allow_opener();
+    let mut conflict_policy = ConflictPolicy::default();
+    let mut search_mode = SearchMode::default();
+    let mut watch_interval_ms = DEFAULT_WATCH_INTERVAL_MS;
     for line in content.lines() {
         let trimmed = line.trim();
         if trimmed.is_empty() || trimmed.starts_with('#') {
@@ -62,6 +73,21 @@ fn parse_config(content: &str) -> Config {
             "allow_opener" => {
                 allow_opener = parse_bool_value(value).unwrap_or(true);
             }
+            "conflict_policy" => {
+                conflict_policy = parse_string_value(value)
+                    .and_then(|name| ConflictPolicy::from_name(&name))
+                    .unwrap_or_default();
+            }
+            "search_mode" => {
+                search_mode = parse_string_value(value)
+                    .and_then(|name| SearchMode::from_name(&name))
+                    .unwrap_or_default();
+            }
+            "watch_interval_ms" => {
+                watch_interval_ms = parse_string_value(value)
+                    .and_then(|raw| raw.parse().ok())
+                    .unwrap_or(DEFAULT_WATCH_INTERVAL_MS);
+            }
             _ => continue,
         }
     }
@@ -69,6 +95,9 @@ fn parse_config(content: &str) -> Config {
         default_theme,
         allow_shell,
         allow_opener,
+        conflict_policy,
+        search_mode,
+        watch_interval_ms,
     }
 }
 
@@ -210,6 +239,42 @@ mod tests {
         assert!(!config.allow_opener);
     }
 
+    #[test]
+    fn parse_config_reads_conflict_policy() {
+        let config = parse_config("conflict_policy = \"keep_both\"");
+
+        assert_eq!(config.conflict_policy, ConflictPolicy::KeepBoth);
+        assert_eq!(
+            parse_config("conflict_policy = \"merge\"").conflict_policy,
+            ConflictPolicy::Ask
+        );
+    }
+
+    #[test]
+    fn parse_config_reads_search_mode() {
+        assert_eq!(
+            parse_config("search_mode = \"fuzzy\"").search_mode,
+            SearchMode::Fuzzy
+        );
+        assert_eq!(
+            parse_config("search_mode = \"glob\"").search_mode,
+            SearchMode::Prefix
+        );
+    }
+
+    #[test]
+    fn parse_config_reads_watch_interval() {
+        assert_eq!(
+            parse_config("watch_interval_ms = 250").watch_interval_ms,
+            250
+        );
+        assert_eq!(parse_config("watch_interval_ms = 0").watch_interval_ms, 0);
+        assert_eq!(
+            parse_config("watch_interval_ms = \"soon\"").watch_interval_ms,
+            DEFAULT_WATCH_INTERVAL_MS
+        );
+    }
+
     #[test]
     #[cfg(target_os = "linux")]
     fn default_allow_opener_is_false_on_linux() {
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::config::config_root;
+
+const BOOKMARKS_FILE: &str = "bookmarks.json";
+
+#[derive(Debug, Error)]
+pub enum BookmarkError {
+    #[error("invalid name: {0}")]
+    InvalidName(String),
+    #[error("no bookmark: {0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmarks {
+    path: Option<PathBuf>,
+    items: BTreeMap<String, PathBuf>,
+}
+
+impl Bookmarks {
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            items: BTreeMap::new(),
+        }
+    }
+
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(BOOKMARKS_FILE);
+        let items = std::fs::read_to_string(&path)
+            .ok()
+            .map(|content| parse_bookmarks(&content))
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            items,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        config_root()
+            .map(|root| Self::load(&root))
+            .unwrap_or_else(Self::in_memory)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Path> {
+        self.items.get(name).map(PathBuf::as_path)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.items
+            .iter()
+            .map(|(name, path)| (name.as_str(), path.as_path()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn add(&mut self, name: &str, path: &Path) -> Result<(), BookmarkError> {
+        if name.is_empty() || name.contains(|ch: char| ch.is_whitespace() || ch == '/') {
+            return Err(BookmarkError::InvalidName(name.to_string()));
+        }
+        self.items.insert(name.to_string(), path.to_path_buf());
+        self.save()?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<PathBuf, BookmarkError> {
+        let path = self
+            .items
+            .remove(name)
+            .ok_or_else(|| BookmarkError::NotFound(name.to_string()))?;
+        self.save()?;
+        Ok(path)
+    }
+
+    pub fn remove_path(&mut self, path: &Path) -> Result<Vec<String>, BookmarkError> {
+        let names = self
+            .iter()
+            .filter(|(_, target)| *target == path)
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return Err(BookmarkError::NotFound(path.display().to_string()));
+        }
+        for name in &names {
+            self.items.remove(name);
+        }
+        self.save()?;
+        Ok(names)
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, build_bookmarks_payload(&self.items))?;
+        std::fs::rename(&tmp_path, path)
+    }
+}
+
+fn build_bookmarks_payload(items: &BTreeMap<String, PathBuf>) -> String {
+    serde_json::json!({
+        "version": 1,
+        "bookmarks": items
+            .iter()
+            .map(|(name, path)| serde_json::json!({
+                "name": name,
+                "path": path.to_string_lossy(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+    .to_string()
+}
+
+fn parse_bookmarks(content: &str) -> BTreeMap<String, PathBuf> {
+    let Ok(value) = serde_json::from_str::<Value>(content) else {
+        return BTreeMap::new();
+    };
+    value
+        .get("bookmarks")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let name = item.get("name")?.as_str()?;
+                    let path = item.get("path")?.as_str()?;
+                    Some((name.to_string(), PathBuf::from(path)))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bookmarks_round_trip_through_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut bookmarks = Bookmarks::load(temp_dir.path());
+        bookmarks.add("src", Path::new("/work/src")).unwrap();
+        bookmarks.add("docs", Path::new("/work/docs")).unwrap();
+        bookmarks.add("docs", Path::new("/work/book")).unwrap();
+
+        let loaded = Bookmarks::load(temp_dir.path());
+        assert_eq!(
+            loaded.iter().collect::<Vec<_>>(),
+            vec![
+                ("docs", Path::new("/work/book")),
+                ("src", Path::new("/work/src"))
+            ]
+        );
+
+        bookmarks.remove("src").unwrap();
+        assert!(Bookmarks::load(temp_dir.path()).get("src").is_none());
+    }
+
+    #[test]
+    fn bookmarks_reject_invalid_names_and_missing_entries() {
+        let mut bookmarks = Bookmarks::in_memory();
+        assert!(matches!(
+            bookmarks.add("my docs", Path::new("/docs")),
+            Err(BookmarkError::InvalidName(_))
+        ));
+        assert!(matches!(
+            bookmarks.remove("docs"),
+            Err(BookmarkError::NotFound(_))
+        ));
+
+        bookmarks.add("a", Path::new("/docs")).unwrap();
+        bookmarks.add("b", Path::new("/docs")).unwrap();
+        bookmarks.add("c", Path::new("/src")).unwrap();
+        assert_eq!(
+            bookmarks.remove_path(Path::new("/docs")).unwrap(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert_eq!(bookmarks.iter().count(), 1);
+    }
+}
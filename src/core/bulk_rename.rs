@@ -0,0 +1,336 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::core::file_ops::validate_entry_name;
+use crate::core::{FileOpError, move_path};
+
+const CYCLE_TEMP_PREFIX: &str = ".ox-bulkrename-";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BulkRenameError {
+    #[error("expected {expected} lines, got {actual}")]
+    LineCountMismatch { expected: usize, actual: usize },
+    #[error("invalid name: {0}")]
+    InvalidName(String),
+    #[error("duplicate target: {0}")]
+    DuplicateTarget(String),
+    #[error("target already exists: {0}")]
+    TargetExists(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkRename {
+    pub from: String,
+    pub to: String,
+    pub in_cycle: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkRenamePlan {
+    pub dir: PathBuf,
+    pub renames: Vec<BulkRename>,
+    steps: Vec<(PathBuf, PathBuf)>,
+}
+
+impl BulkRenamePlan {
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty()
+    }
+
+    pub fn preview_lines(&self) -> Vec<String> {
+        self.renames
+            .iter()
+            .map(|rename| {
+                let suffix = if rename.in_cycle { "  (cycle)" } else { "" };
+                format!("{} -> {}{suffix}", rename.from, rename.to)
+            })
+            .collect()
+    }
+}
+
+pub fn build_bulk_rename_buffer(names: &[String]) -> String {
+    let mut buffer = names.join("\n");
+    buffer.push('\n');
+    buffer
+}
+
+pub fn plan_bulk_rename(
+    dir: &Path,
+    originals: &[String],
+    edited: &str,
+) -> Result<BulkRenamePlan, BulkRenameError> {
+    let mut lines = edited
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect::<Vec<_>>();
+    while lines.last().is_some_and(|line| line.is_empty()) && lines.len() > originals.len() {
+        lines.pop();
+    }
+    if lines.len() != originals.len() {
+        return Err(BulkRenameError::LineCountMismatch {
+            expected: originals.len(),
+            actual: lines.len(),
+        });
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut changes = Vec::new();
+    for (from, to) in originals.iter().zip(lines) {
+        if validate_entry_name(to).is_err() {
+            return Err(BulkRenameError::InvalidName(to.to_string()));
+        }
+        if !seen.insert(to.to_string()) {
+            return Err(BulkRenameError::DuplicateTarget(to.to_string()));
+        }
+        if from != to {
+            changes.push((from.clone(), to.to_string()));
+        }
+    }
+
+    let sources = changes
+        .iter()
+        .map(|(from, _)| from.as_str())
+        .collect::<BTreeSet<_>>();
+    if let Some((_, to)) = changes
+        .iter()
+        .find(|(_, to)| !sources.contains(to.as_str()) && dir.join(to).symlink_metadata().is_ok())
+    {
+        return Err(BulkRenameError::TargetExists(to.clone()));
+    }
+
+    let moves = changes
+        .iter()
+        .map(|(from, to)| (dir.join(from), dir.join(to)))
+        .collect::<Vec<_>>();
+    let (steps, cycle_members) = order_moves(&moves);
+    let renames = changes
+        .into_iter()
+        .map(|(from, to)| BulkRename {
+            in_cycle: cycle_members.contains(&dir.join(&from)),
+            from,
+            to,
+        })
+        .collect();
+    Ok(BulkRenamePlan {
+        dir: dir.to_path_buf(),
+        renames,
+        steps,
+    })
+}
+
+/// 途中で止まった一括リネーム。`moved` は動かし終えた項目の (元の場所, 今の場所) で、
+/// 循環を崩すための一時名に退避したままの項目も含む。
+#[derive(Debug)]
+pub struct BulkRenameFailure {
+    pub renamed: usize,
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    pub error: FileOpError,
+}
+
+pub fn apply_bulk_rename(plan: &BulkRenamePlan) -> Result<usize, BulkRenameFailure> {
+    let originals = plan
+        .renames
+        .iter()
+        .map(|rename| plan.dir.join(&rename.from))
+        .collect::<Vec<_>>();
+    let mut locations = originals.clone();
+    let mut applied = 0;
+    for (from, to) in &plan.steps {
+        if let Err(error) = move_path(from, to) {
+            let moved = originals
+                .into_iter()
+                .zip(locations)
+                .filter(|(original, location)| original != location)
+                .collect();
+            return Err(BulkRenameFailure {
+                renamed: applied,
+                moved,
+                error,
+            });
+        }
+        if let Some(location) = locations.iter_mut().find(|location| *location == from) {
+            *location = to.clone();
+        }
+        if !is_cycle_temp(to) {
+            applied += 1;
+        }
+    }
+    Ok(applied)
+}
+
+pub(crate) fn order_moves(
+    moves: &[(PathBuf, PathBuf)],
+) -> (Vec<(PathBuf, PathBuf)>, BTreeSet<PathBuf>) {
+    let mut pending = moves.iter().cloned().collect::<BTreeMap<_, _>>();
+    let mut steps = Vec::new();
+    let mut cycle_members = BTreeSet::new();
+    let mut temp_counter = 0usize;
+    while !pending.is_empty() {
+        let ready = pending
+            .iter()
+            .filter(|(_, to)| !pending.contains_key(*to))
+            .map(|(from, _)| from.clone())
+            .collect::<Vec<_>>();
+        if !ready.is_empty() {
+            for from in ready {
+                if let Some(to) = pending.remove(&from) {
+                    steps.push((from, to));
+                }
+            }
+            continue;
+        }
+        let Some(cycle) = pending
+            .keys()
+            .next()
+            .and_then(|start| find_cycle(&pending, start))
+        else {
+            break;
+        };
+        let Some(breaker) = cycle.first().cloned() else {
+            break;
+        };
+        cycle_members.extend(cycle);
+        temp_counter += 1;
+        let name = breaker
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let temp = breaker.with_file_name(format!("{CYCLE_TEMP_PREFIX}{temp_counter}-{name}"));
+        if let Some(to) = pending.remove(&breaker) {
+            steps.push((breaker, temp.clone()));
+            pending.insert(temp, to);
+        }
+    }
+    (steps, cycle_members)
+}
+
+pub(crate) fn is_cycle_temp(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with(CYCLE_TEMP_PREFIX))
+}
+
+fn find_cycle(pending: &BTreeMap<PathBuf, PathBuf>, start: &Path) -> Option<Vec<PathBuf>> {
+    let mut path: Vec<PathBuf> = Vec::new();
+    let mut current = start.to_path_buf();
+    loop {
+        if let Some(position) = path.iter().position(|name| *name == current) {
+            return Some(path.split_off(position));
+        }
+        let next = pending.get(&current)?.clone();
+        path.push(current);
+        current = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn plan_skips_unchanged_lines_and_applies_renames() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        let originals = names(&["a.txt", "b.txt"]);
+
+        let plan = plan_bulk_rename(temp_dir.path(), &originals, "a.txt\nc.txt\n").unwrap();
+
+        assert_eq!(plan.preview_lines(), vec!["b.txt -> c.txt"]);
+        assert_eq!(apply_bulk_rename(&plan).ok(), Some(1));
+        assert!(temp_dir.path().join("c.txt").exists());
+        assert!(!temp_dir.path().join("b.txt").exists());
+    }
+
+    #[test]
+    fn plan_rejects_line_count_mismatch_and_duplicates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let originals = names(&["a", "b"]);
+
+        assert_eq!(
+            plan_bulk_rename(temp_dir.path(), &originals, "a\n"),
+            Err(BulkRenameError::LineCountMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            plan_bulk_rename(temp_dir.path(), &originals, "c\nc\n"),
+            Err(BulkRenameError::DuplicateTarget("c".to_string()))
+        );
+        assert_eq!(
+            plan_bulk_rename(temp_dir.path(), &originals, "a\nx/y\n"),
+            Err(BulkRenameError::InvalidName("x/y".to_string()))
+        );
+    }
+
+    #[test]
+    fn plan_rejects_target_outside_selection_that_exists() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("a"), "a").unwrap();
+        fs::write(temp_dir.path().join("other"), "o").unwrap();
+
+        let result = plan_bulk_rename(temp_dir.path(), &names(&["a"]), "other\n");
+
+        assert_eq!(
+            result,
+            Err(BulkRenameError::TargetExists("other".to_string()))
+        );
+    }
+
+    #[test]
+    fn plan_orders_chains_and_breaks_cycles() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(temp_dir.path().join(name), name).unwrap();
+        }
+        let originals = names(&["a", "b", "c"]);
+
+        let plan = plan_bulk_rename(temp_dir.path(), &originals, "b\na\nd\n").unwrap();
+        let feeding = plan_bulk_rename(temp_dir.path(), &originals, "c\na\nb\n").unwrap();
+        assert!(feeding.renames.iter().all(|rename| rename.in_cycle));
+
+        assert!(
+            plan.renames
+                .iter()
+                .any(|rename| rename.from == "a" && rename.in_cycle)
+        );
+        assert!(
+            plan.renames
+                .iter()
+                .any(|rename| rename.from == "c" && !rename.in_cycle)
+        );
+        assert!(apply_bulk_rename(&plan).is_ok());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("a")).unwrap(), "b");
+        assert_eq!(fs::read_to_string(temp_dir.path().join("b")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(temp_dir.path().join("d")).unwrap(), "c");
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn failed_apply_reports_moved_entries_including_parked_ones() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["a", "b"] {
+            fs::write(temp_dir.path().join(name), name).unwrap();
+        }
+        let plan = plan_bulk_rename(temp_dir.path(), &names(&["a", "b"]), "b\na\n").unwrap();
+        let (parked, _) = plan.steps[0].clone();
+        let vacated = plan.steps[1].1.clone();
+        fs::remove_file(&plan.steps[1].0).unwrap();
+
+        let failure = apply_bulk_rename(&plan).unwrap_err();
+
+        assert_eq!(failure.renamed, 0);
+        assert_eq!(failure.moved.len(), 1);
+        assert_eq!(failure.moved[0].0, parked);
+        assert!(is_cycle_temp(&failure.moved[0].1));
+        assert!(failure.moved[0].1.exists());
+        assert!(!vacated.exists());
+    }
+}
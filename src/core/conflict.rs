@@ -0,0 +1,160 @@
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    #[default]
+    Ask,
+    Overwrite,
+    Skip,
+    KeepBoth,
+    OverwriteIfNewer,
+}
+
+impl ConflictPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "ask" => Some(Self::Ask),
+            "overwrite" => Some(Self::Overwrite),
+            "skip" => Some(Self::Skip),
+            "keep_both" => Some(Self::KeepBoth),
+            "overwrite_if_newer" => Some(Self::OverwriteIfNewer),
+            _ => None,
+        }
+    }
+
+    pub fn choice(self) -> Option<ConflictChoice> {
+        match self {
+            Self::Ask => None,
+            Self::Overwrite => Some(ConflictChoice::Overwrite),
+            Self::Skip => Some(ConflictChoice::Skip),
+            Self::KeepBoth => Some(ConflictChoice::KeepBoth),
+            Self::OverwriteIfNewer => Some(ConflictChoice::OverwriteIfNewer),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictChoice {
+    Overwrite,
+    Skip,
+    KeepBoth,
+    OverwriteIfNewer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictAction {
+    Replace,
+    Rename(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictOverride {
+    pub source: PathBuf,
+    pub action: ConflictAction,
+}
+
+pub fn resolve_conflict(
+    choice: ConflictChoice,
+    source: &Path,
+    dest: &Path,
+) -> Option<ConflictAction> {
+    match choice {
+        ConflictChoice::Overwrite => Some(ConflictAction::Replace),
+        ConflictChoice::Skip => None,
+        ConflictChoice::KeepBoth => Some(ConflictAction::Rename(keep_both_path(dest))),
+        ConflictChoice::OverwriteIfNewer => {
+            is_newer(source, dest).then_some(ConflictAction::Replace)
+        }
+    }
+}
+
+pub fn keep_both_path(dest: &Path) -> PathBuf {
+    let stem = dest
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = dest
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    let mut counter = 1usize;
+    loop {
+        let candidate = dest.with_file_name(format!("{stem} ({counter}){extension}"));
+        if candidate.symlink_metadata().is_err() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+fn is_newer(source: &Path, dest: &Path) -> bool {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|metadata| metadata.modified());
+    match (modified(source), modified(dest)) {
+        (Ok(source), Ok(dest)) => source > dest,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn keep_both_path_appends_counter_before_extension() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dest = temp_dir.path().join("report.txt");
+        fs::write(&dest, "a").unwrap();
+        fs::write(temp_dir.path().join("report (1).txt"), "b").unwrap();
+
+        assert_eq!(
+            keep_both_path(&dest),
+            temp_dir.path().join("report (2).txt")
+        );
+        assert_eq!(
+            keep_both_path(&temp_dir.path().join("docs")),
+            temp_dir.path().join("docs (1)")
+        );
+    }
+
+    #[test]
+    fn overwrite_if_newer_compares_mtime() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("source.txt");
+        let dest = temp_dir.path().join("dest.txt");
+        fs::write(&source, "new").unwrap();
+        fs::write(&dest, "old").unwrap();
+        let past = SystemTime::now() - Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&dest)
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+
+        assert_eq!(
+            resolve_conflict(ConflictChoice::OverwriteIfNewer, &source, &dest),
+            Some(ConflictAction::Replace)
+        );
+        assert_eq!(
+            resolve_conflict(ConflictChoice::OverwriteIfNewer, &dest, &source),
+            None
+        );
+        assert_eq!(resolve_conflict(ConflictChoice::Skip, &source, &dest), None);
+    }
+
+    #[test]
+    fn conflict_policy_parses_names() {
+        assert_eq!(
+            ConflictPolicy::from_name("keep-both"),
+            Some(ConflictPolicy::KeepBoth)
+        );
+        assert_eq!(
+            ConflictPolicy::from_name("overwrite_if_newer"),
+            Some(ConflictPolicy::OverwriteIfNewer)
+        );
+        assert_eq!(ConflictPolicy::from_name("merge"), None);
+        assert_eq!(ConflictPolicy::Ask.choice(), None);
+    }
+}
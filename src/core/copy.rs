@@ -0,0 +1,929 @@
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::core::conflict::{ConflictAction, ConflictOverride};
+use crate::core::{FileOpError, TrashStore, TrashedItem};
+
+const COPY_CHUNK_BYTES: usize = 64 * 1024;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyRequest {
+    pub id: u64,
+    pub sources: Vec<PathBuf>,
+    pub dest_dir: PathBuf,
+    pub overrides: Vec<ConflictOverride>,
+    pub trash: Option<TrashStore>,
+    /// デバイスをまたぐ移動。各ルートを最後まで写せたときだけ元を消す。
+    pub remove_sources: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyProgress {
+    pub id: u64,
+    pub done_files: usize,
+    pub total_files: usize,
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+    pub current: Option<PathBuf>,
+}
+
+impl CopyProgress {
+    pub fn percent(&self) -> u8 {
+        if self.total_bytes == 0 {
+            if self.total_files == 0 {
+                return 100;
+            }
+            return ((self.done_files * 100) / self.total_files).min(100) as u8;
+        }
+        ((self.done_bytes.saturating_mul(100)) / self.total_bytes).min(100) as u8
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopiedItem {
+    pub source: PathBuf,
+    pub dest: PathBuf,
+    pub replaced: Option<TrashedItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopySummary {
+    pub id: u64,
+    pub dest_dir: PathBuf,
+    pub copied: Vec<CopiedItem>,
+    pub copied_files: usize,
+    pub failed: usize,
+    pub removed_sources: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyEvent {
+    Started {
+        id: u64,
+        total_files: usize,
+        total_bytes: u64,
+    },
+    Progress(CopyProgress),
+    ItemFailed {
+        id: u64,
+        path: PathBuf,
+        message: String,
+    },
+    Finished(CopySummary),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CopyItem {
+    Dir {
+        source: PathBuf,
+        dest: PathBuf,
+    },
+    File {
+        source: PathBuf,
+        dest: PathBuf,
+        size: u64,
+    },
+    Symlink {
+        source: PathBuf,
+        dest: PathBuf,
+    },
+}
+
+#[derive(Debug)]
+struct PlannedRoot {
+    item: CopiedItem,
+    replace: bool,
+    items: Vec<CopyItem>,
+}
+
+#[derive(Debug, Default)]
+struct CopyPlan {
+    roots: Vec<PlannedRoot>,
+    total_files: usize,
+    total_bytes: u64,
+}
+
+pub fn execute_copy(request: &CopyRequest, emit: &mut dyn FnMut(CopyEvent)) -> CopySummary {
+    let plan = plan_copy(request, emit);
+    emit(CopyEvent::Started {
+        id: request.id,
+        total_files: plan.total_files,
+        total_bytes: plan.total_bytes,
+    });
+    let mut progress = CopyProgress {
+        id: request.id,
+        done_files: 0,
+        total_files: plan.total_files,
+        done_bytes: 0,
+        total_bytes: plan.total_bytes,
+        current: None,
+    };
+    let mut summary = CopySummary {
+        id: request.id,
+        dest_dir: request.dest_dir.clone(),
+        copied: Vec::new(),
+        copied_files: 0,
+        failed: 0,
+        removed_sources: request.remove_sources,
+    };
+    for mut root in plan.roots {
+        if !root.replace {
+            if copy_items(root.items, &mut progress, &mut summary, emit) {
+                finish_root(request, root.item, &mut summary, emit);
+            }
+            continue;
+        }
+        let dest = root.item.dest.clone();
+        let mut root_ok = true;
+        let result = replace_path(&dest, request.trash.as_ref(), |staging| {
+            let items = root
+                .items
+                .into_iter()
+                .map(|item| restage(item, &dest, staging))
+                .collect();
+            root_ok = copy_items(items, &mut progress, &mut summary, emit);
+            if root_ok {
+                Ok(())
+            } else {
+                Err(std::io::Error::other("copy failed"))
+            }
+        });
+        match result {
+            Ok(replaced) => {
+                root.item.replaced = Some(replaced);
+                finish_root(request, root.item, &mut summary, emit);
+            }
+            Err(_) if !root_ok => {}
+            Err(error) => {
+                summary.failed += 1;
+                emit(CopyEvent::ItemFailed {
+                    id: request.id,
+                    path: dest,
+                    message: error.to_string(),
+                });
+            }
+        }
+    }
+    progress.current = None;
+    emit(CopyEvent::Finished(summary.clone()));
+    summary
+}
+
+fn finish_root(
+    request: &CopyRequest,
+    item: CopiedItem,
+    summary: &mut CopySummary,
+    emit: &mut dyn FnMut(CopyEvent),
+) {
+    if request.remove_sources
+        && let Err(error) = remove_path(&item.source)
+    {
+        summary.failed += 1;
+        emit(CopyEvent::ItemFailed {
+            id: request.id,
+            path: item.source,
+            message: error.to_string(),
+        });
+        return;
+    }
+    summary.copied.push(item);
+}
+
+fn copy_items(
+    items: Vec<CopyItem>,
+    progress: &mut CopyProgress,
+    summary: &mut CopySummary,
+    emit: &mut dyn FnMut(CopyEvent),
+) -> bool {
+    let mut root_ok = true;
+    let mut skipped_dirs: Vec<PathBuf> = Vec::new();
+    let mut created_dirs = Vec::new();
+    for item in items {
+        let (source, dest) = item_paths(&item);
+        if skipped_dirs.iter().any(|dir| dest.starts_with(dir)) {
+            continue;
+        }
+        progress.current = Some(source.to_path_buf());
+        let result = match &item {
+            CopyItem::Dir { source, dest } => std::fs::create_dir(dest)
+                .map(|()| created_dirs.push((source.clone(), dest.clone()))),
+            CopyItem::File { source, dest, .. } => {
+                copy_file_with_progress(source, dest, progress, emit)
+            }
+            CopyItem::Symlink { source, dest } => copy_symlink(source, dest),
+        };
+        match result {
+            Ok(()) => {
+                if !matches!(item, CopyItem::Dir { .. }) {
+                    progress.done_files += 1;
+                    summary.copied_files += 1;
+                }
+            }
+            Err(error) => {
+                root_ok = false;
+                summary.failed += 1;
+                if let CopyItem::Dir { dest, .. } = &item {
+                    skipped_dirs.push(dest.clone());
+                }
+                if let CopyItem::File { size, .. } = &item {
+                    progress.done_files += 1;
+                    progress.done_bytes = progress.done_bytes.saturating_add(*size);
+                }
+                emit(CopyEvent::ItemFailed {
+                    id: summary.id,
+                    path: source.to_path_buf(),
+                    message: error.to_string(),
+                });
+            }
+        }
+        emit(CopyEvent::Progress(progress.clone()));
+    }
+    apply_dir_permissions(&created_dirs);
+    root_ok
+}
+
+pub fn replace_path(
+    dest: &Path,
+    trash: Option<&TrashStore>,
+    stage: impl FnOnce(&Path) -> std::io::Result<()>,
+) -> Result<TrashedItem, FileOpError> {
+    let Some(trash) = trash else {
+        return Err(FileOpError::TrashUnavailable);
+    };
+    let staging = staging_path(dest)?;
+    if let Err(error) = stage(&staging) {
+        let _ = remove_path(&staging);
+        return Err(error.into());
+    }
+    let replaced = match trash.trash(dest) {
+        Ok(replaced) => replaced,
+        Err(error) => {
+            let _ = remove_path(&staging);
+            return Err(error);
+        }
+    };
+    if let Err(error) = std::fs::rename(&staging, dest) {
+        let _ = trash.restore(&replaced);
+        let _ = remove_path(&staging);
+        return Err(error.into());
+    }
+    Ok(replaced)
+}
+
+fn staging_path(dest: &Path) -> Result<PathBuf, FileOpError> {
+    let (Some(parent), Some(name)) = (dest.parent(), dest.file_name()) else {
+        return Err(FileOpError::InvalidName(dest.display().to_string()));
+    };
+    let name = name.to_string_lossy();
+    (1..)
+        .map(|counter| parent.join(format!(".{name}.ox-{counter}.tmp")))
+        .find(|path| path.symlink_metadata().is_err())
+        .ok_or_else(|| FileOpError::InvalidName(dest.display().to_string()))
+}
+
+fn restage(item: CopyItem, dest: &Path, staging: &Path) -> CopyItem {
+    let rebase = |path: PathBuf| match path.strip_prefix(dest) {
+        Ok(relative) if relative.as_os_str().is_empty() => staging.to_path_buf(),
+        Ok(relative) => staging.join(relative),
+        Err(_) => path,
+    };
+    match item {
+        CopyItem::Dir { source, dest } => CopyItem::Dir {
+            source,
+            dest: rebase(dest),
+        },
+        CopyItem::File { source, dest, size } => CopyItem::File {
+            source,
+            dest: rebase(dest),
+            size,
+        },
+        CopyItem::Symlink { source, dest } => CopyItem::Symlink {
+            source,
+            dest: rebase(dest),
+        },
+    }
+}
+
+fn plan_copy(request: &CopyRequest, emit: &mut dyn FnMut(CopyEvent)) -> CopyPlan {
+    let mut plan = CopyPlan::default();
+    for source in &request.sources {
+        let fail = |message: String, emit: &mut dyn FnMut(CopyEvent)| {
+            emit(CopyEvent::ItemFailed {
+                id: request.id,
+                path: source.clone(),
+                message,
+            });
+        };
+        let Some(name) = source.file_name() else {
+            fail("invalid source path".to_string(), emit);
+            continue;
+        };
+        // `Rename` と `Replace` が両方あれば、指定した場所の既存エントリを置き換える。
+        let actions = request
+            .overrides
+            .iter()
+            .filter(|entry| entry.source == *source)
+            .map(|entry| &entry.action)
+            .collect::<Vec<_>>();
+        let dest = actions
+            .iter()
+            .find_map(|action| match action {
+                ConflictAction::Rename(path) => Some(path.clone()),
+                ConflictAction::Replace => None,
+            })
+            .unwrap_or_else(|| request.dest_dir.join(name));
+        let replace = actions.contains(&&ConflictAction::Replace);
+        if dest.symlink_metadata().is_ok() && !replace {
+            fail(format!("{} already exists", dest.display()), emit);
+            continue;
+        }
+        if is_inside(&request.dest_dir, source) {
+            fail("cannot copy a directory into itself".to_string(), emit);
+            continue;
+        }
+        if replace && dest == *source {
+            fail("cannot overwrite an entry with itself".to_string(), emit);
+            continue;
+        }
+        let mut items = Vec::new();
+        if let Err(error) = collect_items(source, &dest, &mut items) {
+            fail(error.to_string(), emit);
+            continue;
+        }
+        for item in &items {
+            match item {
+                CopyItem::File { size, .. } => {
+                    plan.total_files += 1;
+                    plan.total_bytes = plan.total_bytes.saturating_add(*size);
+                }
+                CopyItem::Symlink { .. } => plan.total_files += 1,
+                CopyItem::Dir { .. } => {}
+            }
+        }
+        plan.roots.push(PlannedRoot {
+            item: CopiedItem {
+                source: source.clone(),
+                dest,
+                replaced: None,
+            },
+            replace,
+            items,
+        });
+    }
+    plan
+}
+
+fn collect_items(source: &Path, dest: &Path, items: &mut Vec<CopyItem>) -> std::io::Result<()> {
+    let metadata = std::fs::symlink_metadata(source)?;
+    let file_type = metadata.file_type();
+    if file_type.is_symlink() {
+        items.push(CopyItem::Symlink {
+            source: source.to_path_buf(),
+            dest: dest.to_path_buf(),
+        });
+        return Ok(());
+    }
+    if file_type.is_dir() {
+        items.push(CopyItem::Dir {
+            source: source.to_path_buf(),
+            dest: dest.to_path_buf(),
+        });
+        let mut children = std::fs::read_dir(source)?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|entry| entry.file_name())
+            .collect::<Vec<_>>();
+        children.sort();
+        for name in children {
+            collect_items(&source.join(&name), &dest.join(&name), items)?;
+        }
+        return Ok(());
+    }
+    items.push(CopyItem::File {
+        source: source.to_path_buf(),
+        dest: dest.to_path_buf(),
+        size: metadata.len(),
+    });
+    Ok(())
+}
+
+pub fn copy_path(source: &Path, dest: &Path) -> std::io::Result<()> {
+    if dest.symlink_metadata().is_ok() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dest.display()),
+        ));
+    }
+    let mut items = Vec::new();
+    collect_items(source, dest, &mut items)?;
+    let mut progress = CopyProgress {
+        id: 0,
+        done_files: 0,
+        total_files: 0,
+        done_bytes: 0,
+        total_bytes: 0,
+        current: None,
+    };
+    for item in &items {
+        let result = match item {
+            CopyItem::Dir { dest, .. } => std::fs::create_dir(dest),
+            CopyItem::File { source, dest, .. } => {
+                copy_file_with_progress(source, dest, &mut progress, &mut |_| {})
+            }
+            CopyItem::Symlink { source, dest } => copy_symlink(source, dest),
+        };
+        if let Err(error) = result {
+            let _ = remove_path(dest);
+            return Err(error);
+        }
+    }
+    let dirs = items
+        .into_iter()
+        .filter_map(|item| match item {
+            CopyItem::Dir { source, dest } => Some((source, dest)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    apply_dir_permissions(&dirs);
+    Ok(())
+}
+
+pub fn remove_path(path: &Path) -> std::io::Result<()> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+fn item_paths(item: &CopyItem) -> (&Path, &Path) {
+    match item {
+        CopyItem::Dir { source, dest }
+        | CopyItem::File { source, dest, .. }
+        | CopyItem::Symlink { source, dest } => (source.as_path(), dest.as_path()),
+    }
+}
+
+fn is_inside(dest_dir: &Path, source: &Path) -> bool {
+    let (Ok(dest_dir), Ok(source)) = (dest_dir.canonicalize(), source.canonicalize()) else {
+        return false;
+    };
+    source.is_dir() && dest_dir.starts_with(&source)
+}
+
+/// ディレクトリは書き込める状態で作り、中身を写し終えてから元の権限を付ける。
+/// 読み取り専用のツリーでも子を作れるよう、深い方から順に付ける。
+fn apply_dir_permissions(dirs: &[(PathBuf, PathBuf)]) {
+    for (source, dest) in dirs.iter().rev() {
+        if let Ok(metadata) = std::fs::metadata(source) {
+            let _ = std::fs::set_permissions(dest, metadata.permissions());
+        }
+    }
+}
+
+fn copy_file_with_progress(
+    source: &Path,
+    dest: &Path,
+    progress: &mut CopyProgress,
+    emit: &mut dyn FnMut(CopyEvent),
+) -> std::io::Result<()> {
+    let mut reader = File::open(source)?;
+    let permissions = reader.metadata()?.permissions();
+    let mut writer = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(dest)?;
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+    let mut last_emit = Instant::now();
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => {
+                drop(writer);
+                let _ = std::fs::remove_file(dest);
+                return Err(error);
+            }
+        };
+        if let Err(error) = writer.write_all(&buffer[..read]) {
+            drop(writer);
+            let _ = std::fs::remove_file(dest);
+            return Err(error);
+        }
+        progress.done_bytes = progress.done_bytes.saturating_add(read as u64);
+        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+            emit(CopyEvent::Progress(progress.clone()));
+            last_emit = Instant::now();
+        }
+    }
+    writer.flush()?;
+    let _ = std::fs::set_permissions(dest, permissions);
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, dest: &Path) -> std::io::Result<()> {
+    let target = std::fs::read_link(source)?;
+    std::os::unix::fs::symlink(target, dest)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(source: &Path, dest: &Path) -> std::io::Result<()> {
+    std::fs::copy(source, dest).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn run_copy(request: &CopyRequest) -> (CopySummary, Vec<CopyEvent>) {
+        let mut events = Vec::new();
+        let summary = execute_copy(request, &mut |event| events.push(event));
+        (summary, events)
+    }
+
+    #[test]
+    fn execute_copy_copies_file_into_dest_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("note.txt");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::write(&source, "hello").unwrap();
+        fs::create_dir(&dest_dir).unwrap();
+
+        let (summary, _) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![source],
+            dest_dir: dest_dir.clone(),
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        assert_eq!(summary.copied_files, 1);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            summary.copied,
+            vec![CopiedItem {
+                source: temp_dir.path().join("note.txt"),
+                dest: dest_dir.join("note.txt"),
+                replaced: None,
+            }]
+        );
+        assert_eq!(
+            fs::read_to_string(dest_dir.join("note.txt")).unwrap(),
+            "hello"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn execute_copy_applies_read_only_dir_mode_after_children() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("locked");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&source).unwrap();
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(source.join("child.txt"), "child").unwrap();
+        fs::set_permissions(&source, fs::Permissions::from_mode(0o555)).unwrap();
+
+        let (summary, _) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![source.clone()],
+            dest_dir: dest_dir.clone(),
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        let copied = dest_dir.join("locked");
+        let mode = fs::metadata(&copied).unwrap().permissions().mode() & 0o777;
+        for dir in [&source, &copied] {
+            fs::set_permissions(dir, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        assert_eq!(summary.failed, 0);
+        assert_eq!(
+            fs::read_to_string(copied.join("child.txt")).unwrap(),
+            "child"
+        );
+        assert_eq!(mode, 0o555);
+    }
+
+    #[test]
+    fn execute_copy_removes_sources_only_after_a_clean_copy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let moved = temp_dir.path().join("moved");
+        let kept = temp_dir.path().join("kept");
+        let dest_dir = temp_dir.path().join("dest");
+        for dir in [&moved, &kept, &dest_dir] {
+            fs::create_dir(dir).unwrap();
+        }
+        fs::write(moved.join("a.txt"), "a").unwrap();
+        fs::write(kept.join("b.txt"), "b").unwrap();
+
+        let (summary, _) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![moved.clone(), kept.clone()],
+            dest_dir: dest_dir.clone(),
+            overrides: vec![ConflictOverride {
+                source: kept.clone(),
+                action: ConflictAction::Rename(dest_dir.join("missing").join("kept")),
+            }],
+            trash: None,
+            remove_sources: true,
+        });
+
+        assert!(summary.removed_sources);
+        assert_eq!(summary.failed, 1);
+        assert!(!moved.exists());
+        assert_eq!(
+            fs::read_to_string(dest_dir.join("moved").join("a.txt")).unwrap(),
+            "a"
+        );
+        assert!(kept.join("b.txt").exists());
+        assert_eq!(
+            summary
+                .copied
+                .iter()
+                .map(|item| item.source.clone())
+                .collect::<Vec<_>>(),
+            vec![moved]
+        );
+    }
+
+    #[test]
+    fn execute_copy_copies_directory_tree() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("tree");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("a.txt"), "a").unwrap();
+        fs::write(source.join("nested").join("b.txt"), "bb").unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+
+        let (summary, _) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![source],
+            dest_dir: dest_dir.clone(),
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        assert_eq!(summary.copied_files, 2);
+        assert_eq!(
+            fs::read_to_string(dest_dir.join("tree").join("nested").join("b.txt")).unwrap(),
+            "bb"
+        );
+    }
+
+    #[test]
+    fn execute_copy_reports_totals_and_final_progress() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("tree");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("a.txt"), "abc").unwrap();
+        fs::write(source.join("b.txt"), "de").unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+
+        let (_, events) = run_copy(&CopyRequest {
+            id: 7,
+            sources: vec![source],
+            dest_dir,
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        assert_eq!(
+            events.first(),
+            Some(&CopyEvent::Started {
+                id: 7,
+                total_files: 2,
+                total_bytes: 5,
+            })
+        );
+        let last_progress = events
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                CopyEvent::Progress(progress) => Some(progress.clone()),
+                _ => None,
+            })
+            .expect("progress event");
+        assert_eq!(last_progress.done_files, 2);
+        assert_eq!(last_progress.done_bytes, 5);
+        assert_eq!(last_progress.percent(), 100);
+        assert!(matches!(events.last(), Some(CopyEvent::Finished(_))));
+    }
+
+    #[test]
+    fn execute_copy_reports_missing_source_and_continues() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let missing = temp_dir.path().join("missing.txt");
+        let source = temp_dir.path().join("note.txt");
+        fs::write(&source, "hello").unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+
+        let (summary, events) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![missing.clone(), source],
+            dest_dir: dest_dir.clone(),
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        assert_eq!(summary.copied_files, 1);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            CopyEvent::ItemFailed { path, .. } if *path == missing
+        )));
+        assert!(dest_dir.join("note.txt").exists());
+    }
+
+    #[test]
+    fn execute_copy_refuses_to_overwrite_existing_entry() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("note.txt");
+        fs::write(&source, "new").unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("note.txt"), "old").unwrap();
+
+        let (summary, _) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![source],
+            dest_dir: dest_dir.clone(),
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        assert_eq!(summary.copied_files, 0);
+        assert_eq!(
+            fs::read_to_string(dest_dir.join("note.txt")).unwrap(),
+            "old"
+        );
+    }
+
+    #[test]
+    fn execute_copy_applies_conflict_overrides() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let first = temp_dir.path().join("a.txt");
+        let second = temp_dir.path().join("b.txt");
+        fs::write(&first, "new a").unwrap();
+        fs::write(&second, "new b").unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("a.txt"), "old a").unwrap();
+        fs::write(dest_dir.join("b.txt"), "old b").unwrap();
+
+        let (summary, _) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![first.clone(), second.clone()],
+            dest_dir: dest_dir.clone(),
+            overrides: vec![
+                ConflictOverride {
+                    source: first,
+                    action: ConflictAction::Replace,
+                },
+                ConflictOverride {
+                    source: second,
+                    action: ConflictAction::Rename(dest_dir.join("b (1).txt")),
+                },
+            ],
+            trash: Some(TrashStore::new(temp_dir.path().join("Trash"))),
+            remove_sources: false,
+        });
+
+        assert_eq!(summary.failed, 0);
+        assert_eq!(fs::read_to_string(dest_dir.join("a.txt")).unwrap(), "new a");
+        assert_eq!(
+            summary.copied[0]
+                .replaced
+                .as_ref()
+                .map(|item| item.original_path.clone()),
+            Some(dest_dir.join("a.txt"))
+        );
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("Trash/files/a.txt")).unwrap(),
+            "old a"
+        );
+        assert_eq!(fs::read_to_string(dest_dir.join("b.txt")).unwrap(), "old b");
+        assert_eq!(
+            fs::read_to_string(dest_dir.join("b (1).txt")).unwrap(),
+            "new b"
+        );
+    }
+
+    #[test]
+    fn replace_keeps_destination_when_staging_fails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let dest = temp_dir.path().join("tree");
+        fs::create_dir(&dest).unwrap();
+        fs::write(dest.join("keep.txt"), "keep").unwrap();
+
+        let result = replace_path(&dest, Some(&store), |staging| {
+            fs::create_dir(staging)?;
+            fs::write(staging.join("partial.txt"), "partial")?;
+            Err(std::io::Error::from(std::io::ErrorKind::StorageFull))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(dest.join("keep.txt")).unwrap(), "keep");
+        assert!(!dest.join("partial.txt").exists());
+        assert!(store.list().is_empty());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn replace_requires_trash() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("a.txt");
+        fs::write(&source, "new").unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("a.txt"), "old").unwrap();
+
+        let (summary, _) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![source.clone()],
+            dest_dir: dest_dir.clone(),
+            overrides: vec![ConflictOverride {
+                source,
+                action: ConflictAction::Replace,
+            }],
+            trash: None,
+            remove_sources: false,
+        });
+
+        assert_eq!(summary.failed, 1);
+        assert_eq!(fs::read_to_string(dest_dir.join("a.txt")).unwrap(), "old");
+    }
+
+    #[test]
+    fn execute_copy_refuses_directory_into_itself() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("tree");
+        fs::create_dir(&source).unwrap();
+
+        let (summary, events) = run_copy(&CopyRequest {
+            id: 1,
+            sources: vec![source.clone()],
+            dest_dir: source.clone(),
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        assert!(summary.copied.is_empty());
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, CopyEvent::ItemFailed { .. }))
+        );
+    }
+
+    #[test]
+    fn copy_path_copies_tree_under_new_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("tree");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("a.txt"), "a").unwrap();
+        let dest = temp_dir.path().join("renamed");
+
+        copy_path(&source, &dest).unwrap();
+
+        assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "a");
+        assert!(source.join("a.txt").exists());
+    }
+
+    #[test]
+    fn copy_progress_percent_uses_bytes_then_files() {
+        let mut progress = CopyProgress {
+            id: 1,
+            done_files: 1,
+            total_files: 4,
+            done_bytes: 50,
+            total_bytes: 200,
+            current: None,
+        };
+        assert_eq!(progress.percent(), 25);
+
+        progress.total_bytes = 0;
+        progress.done_bytes = 0;
+        progress.done_files = 2;
+        assert_eq!(progress.percent(), 50);
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::core::{CopyEvent, CopyRequest, execute_copy};
+
+#[derive(Debug)]
+pub struct CopyWorker {
+    request_tx: Sender<CopyRequest>,
+    event_rx: Receiver<CopyEvent>,
+}
+
+impl CopyWorker {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<CopyRequest>();
+        let (event_tx, event_rx) = mpsc::channel::<CopyEvent>();
+
+        thread::spawn(move || {
+            request_rx.into_iter().for_each(|request| {
+                execute_copy(&request, &mut |event| {
+                    let _ = event_tx.send(event);
+                });
+            });
+        });
+
+        Self {
+            request_tx,
+            event_rx,
+        }
+    }
+
+    pub fn request(&self, request: CopyRequest) {
+        let _ = self.request_tx.send(request);
+    }
+
+    pub fn poll(&self) -> Option<CopyEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    #[cfg(test)]
+    pub fn recv_timeout(&self, timeout: std::time::Duration) -> Option<CopyEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn copy_worker_copies_in_background_and_finishes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("note.txt");
+        std::fs::write(&source, "hello").unwrap();
+        let dest_dir = temp_dir.path().join("dest");
+        std::fs::create_dir(&dest_dir).unwrap();
+        let worker = CopyWorker::new();
+
+        worker.request(CopyRequest {
+            id: 3,
+            sources: vec![source],
+            dest_dir: dest_dir.clone(),
+            overrides: Vec::new(),
+            trash: None,
+            remove_sources: false,
+        });
+
+        let summary = loop {
+            match worker
+                .event_rx
+                .recv_timeout(Duration::from_secs(2))
+                .unwrap()
+            {
+                CopyEvent::Finished(summary) => break summary,
+                CopyEvent::ItemFailed { message, .. } => panic!("{message}"),
+                _ => {}
+            }
+        };
+        assert_eq!(summary.id, 3);
+        assert!(dest_dir.join("note.txt").exists());
+    }
+}
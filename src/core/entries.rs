@@ -1,26 +1,389 @@
-use std::path::Path;
+use std::cmp::Ordering;
+use std::fs::{DirEntry, FileType};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use crate::error::AppResult;
 
+const LISTING_CHUNK: usize = 1024;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Entry {
     pub name: String,
     pub is_dir: bool,
+    pub kind: EntryKind,
+    pub link_target: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Executable,
+    Dir,
+    Symlink,
+    BrokenSymlink,
+    Fifo,
+    Socket,
+    Other,
+}
+
+impl EntryKind {
+    pub fn suffix(self) -> &'static str {
+        match self {
+            EntryKind::Dir => "/",
+            EntryKind::Symlink | EntryKind::BrokenSymlink => "@",
+            EntryKind::Executable => "*",
+            EntryKind::Fifo => "|",
+            EntryKind::Socket => "=",
+            EntryKind::File | EntryKind::Other => "",
+        }
+    }
+
+    pub fn is_symlink(self) -> bool {
+        matches!(self, EntryKind::Symlink | EntryKind::BrokenSymlink)
+    }
+}
+
+impl Entry {
+    pub fn display_name(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or(&self.name)
+    }
+
+    pub fn depth(&self) -> usize {
+        self.name.matches('/').count()
+    }
+
+    /// .gitignore の照合でフォルダとして扱うか。git はフォルダへのリンクをファイルとして扱う。
+    pub fn is_plain_dir(&self) -> bool {
+        self.is_dir && self.kind != EntryKind::Symlink
+    }
+
+    #[cfg(test)]
+    pub fn file(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            is_dir: false,
+            kind: EntryKind::File,
+            link_target: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn dir(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            is_dir: true,
+            kind: EntryKind::Dir,
+            link_target: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    #[default]
+    Name,
+    Natural,
+    Size,
+    Mtime,
+    Ext,
+    Type,
+}
+
+impl SortKey {
+    pub fn all() -> &'static [SortKey] {
+        &[
+            SortKey::Name,
+            SortKey::Natural,
+            SortKey::Size,
+            SortKey::Mtime,
+            SortKey::Ext,
+            SortKey::Type,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Natural => "natural",
+            SortKey::Size => "size",
+            SortKey::Mtime => "mtime",
+            SortKey::Ext => "ext",
+            SortKey::Type => "type",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|key| key.name() == name)
+    }
+}
+
+pub const SORT_FLAGS: [&str; 3] = ["reverse", "dirs-first", "ignore-case"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SortMode {
+    pub key: SortKey,
+    pub reverse: bool,
+    pub dirs_first: bool,
+    pub case_insensitive: bool,
+}
+
+impl SortMode {
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut mode = SortMode::default();
+        let mut words = text
+            .split(|ch: char| ch.is_whitespace() || ch == ',')
+            .filter(|word| !word.is_empty());
+        mode.key = SortKey::from_name(words.next()?)?;
+        for word in words {
+            match word {
+                "reverse" => mode.reverse = true,
+                "dirs-first" => mode.dirs_first = true,
+                "ignore-case" => mode.case_insensitive = true,
+                _ => return None,
+            }
+        }
+        Some(mode)
+    }
+
+    pub fn label(&self) -> String {
+        let flags = [self.reverse, self.dirs_first, self.case_insensitive];
+        std::iter::once(self.key.name())
+            .chain(
+                SORT_FLAGS
+                    .iter()
+                    .zip(flags)
+                    .filter(|(_, enabled)| *enabled)
+                    .map(|(flag, _)| *flag),
+            )
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[derive(Debug)]
+struct SortItem {
+    entry: Entry,
+    is_symlink: bool,
+    size: u64,
+    modified: Option<SystemTime>,
 }
 
 pub fn list_entries(path: &Path, include_hidden: bool) -> AppResult<Vec<Entry>> {
-    let mut entries = Vec::new();
+    list_entries_sorted(path, include_hidden, &SortMode::default())
+}
+
+pub fn list_entries_sorted(
+    path: &Path,
+    include_hidden: bool,
+    sort: &SortMode,
+) -> AppResult<Vec<Entry>> {
+    let entries = list_entries_chunked(path, include_hidden, sort, &|| false, &mut |_| {})?;
+    Ok(entries.unwrap_or_default())
+}
+
+pub fn list_entries_chunked(
+    path: &Path,
+    include_hidden: bool,
+    sort: &SortMode,
+    is_cancelled: &dyn Fn() -> bool,
+    on_chunk: &mut dyn FnMut(Vec<Entry>),
+) -> std::io::Result<Option<Vec<Entry>>> {
+    let needs_metadata = matches!(sort.key, SortKey::Size | SortKey::Mtime);
+    let mut items = Vec::new();
+    let mut chunk_start = 0;
     for entry in std::fs::read_dir(path)? {
+        if is_cancelled() {
+            return Ok(None);
+        }
         let entry = entry?;
         let name = entry.file_name().to_string_lossy().to_string();
         if !include_hidden && name.starts_with('.') {
             continue;
         }
-        let is_dir = entry.file_type()?.is_dir();
-        entries.push(Entry { name, is_dir });
+        let file_type = entry.file_type()?;
+        let metadata = if needs_metadata {
+            entry.metadata().ok()
+        } else {
+            None
+        };
+        let (kind, is_dir) = classify(&entry, file_type);
+        let link_target = if kind.is_symlink() {
+            std::fs::read_link(entry.path()).ok()
+        } else {
+            None
+        };
+        items.push(SortItem {
+            entry: Entry {
+                name,
+                is_dir,
+                kind,
+                link_target,
+            },
+            is_symlink: file_type.is_symlink(),
+            size: metadata
+                .as_ref()
+                .map(|metadata| metadata.len())
+                .unwrap_or(0),
+            modified: metadata.and_then(|metadata| metadata.modified().ok()),
+        });
+        if items.len() - chunk_start >= LISTING_CHUNK {
+            on_chunk(chunk_entries(&items[chunk_start..]));
+            chunk_start = items.len();
+        }
+    }
+    if chunk_start < items.len() {
+        on_chunk(chunk_entries(&items[chunk_start..]));
+    }
+    items.sort_by(|left, right| compare_items(left, right, sort));
+    Ok(Some(items.into_iter().map(|item| item.entry).collect()))
+}
+
+fn classify(entry: &DirEntry, file_type: FileType) -> (EntryKind, bool) {
+    if file_type.is_dir() {
+        return (EntryKind::Dir, true);
+    }
+    if file_type.is_symlink() {
+        return match std::fs::metadata(entry.path()) {
+            Ok(metadata) => (EntryKind::Symlink, metadata.is_dir()),
+            Err(_) => (EntryKind::BrokenSymlink, false),
+        };
+    }
+    if file_type.is_file() {
+        let executable = entry
+            .metadata()
+            .is_ok_and(|metadata| is_executable(&metadata));
+        let kind = if executable {
+            EntryKind::Executable
+        } else {
+            EntryKind::File
+        };
+        return (kind, false);
     }
-    entries.sort_by(|left, right| left.name.cmp(&right.name));
-    Ok(entries)
+    (special_kind(file_type), false)
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn special_kind(file_type: FileType) -> EntryKind {
+    use std::os::unix::fs::FileTypeExt;
+    if file_type.is_fifo() {
+        EntryKind::Fifo
+    } else if file_type.is_socket() {
+        EntryKind::Socket
+    } else {
+        EntryKind::Other
+    }
+}
+
+#[cfg(not(unix))]
+fn special_kind(_file_type: FileType) -> EntryKind {
+    EntryKind::Other
+}
+
+fn chunk_entries(items: &[SortItem]) -> Vec<Entry> {
+    items.iter().map(|item| item.entry.clone()).collect()
+}
+
+fn compare_items(left: &SortItem, right: &SortItem, sort: &SortMode) -> Ordering {
+    if sort.dirs_first && left.entry.is_dir != right.entry.is_dir {
+        return right.entry.is_dir.cmp(&left.entry.is_dir);
+    }
+    let name = |item: &SortItem| {
+        if sort.case_insensitive {
+            item.entry.name.to_lowercase()
+        } else {
+            item.entry.name.clone()
+        }
+    };
+    let by_name = || name(left).cmp(&name(right));
+    let ordering = match sort.key {
+        SortKey::Name => by_name(),
+        SortKey::Natural => natural_cmp(&name(left), &name(right)),
+        SortKey::Size => left.size.cmp(&right.size).then_with(by_name),
+        SortKey::Mtime => left.modified.cmp(&right.modified).then_with(by_name),
+        SortKey::Ext => extension(&name(left))
+            .cmp(&extension(&name(right)))
+            .then_with(by_name),
+        SortKey::Type => type_rank(left).cmp(&type_rank(right)).then_with(by_name),
+    };
+    let ordering = ordering.then_with(|| left.entry.name.cmp(&right.entry.name));
+    if sort.reverse {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+fn extension(name: &str) -> Option<&str> {
+    Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+}
+
+fn type_rank(item: &SortItem) -> u8 {
+    if item.entry.is_dir {
+        0
+    } else if item.is_symlink {
+        1
+    } else {
+        2
+    }
+}
+
+pub fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let mut left_chunks = chunks(left);
+    let mut right_chunks = chunks(right);
+    loop {
+        match (left_chunks.next(), right_chunks.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left), Some(right)) => {
+                let left_digits = left.starts_with(|ch: char| ch.is_ascii_digit());
+                let right_digits = right.starts_with(|ch: char| ch.is_ascii_digit());
+                let ordering = if left_digits && right_digits {
+                    let left_trimmed = left.trim_start_matches('0');
+                    let right_trimmed = right.trim_start_matches('0');
+                    left_trimmed
+                        .len()
+                        .cmp(&right_trimmed.len())
+                        .then_with(|| left_trimmed.cmp(right_trimmed))
+                } else {
+                    left.cmp(right)
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+fn chunks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let digits = first.is_ascii_digit();
+        let end = rest
+            .char_indices()
+            .find(|(_, ch)| ch.is_ascii_digit() != digits)
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+        let (chunk, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some(chunk)
+    })
 }
 
 #[cfg(test)]
@@ -40,16 +403,7 @@ mod tests {
 
         assert_eq!(
             entries,
-            vec![
-                Entry {
-                    name: "alpha.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "beta.txt".to_string(),
-                    is_dir: false,
-                }
-            ]
+            vec![Entry::file("alpha.txt"), Entry::file("beta.txt")]
         );
     }
 
@@ -61,13 +415,40 @@ mod tests {
 
         let entries = list_entries(temp_dir.path(), true).unwrap();
 
-        assert_eq!(
-            entries,
-            vec![Entry {
-                name: "child".to_string(),
-                is_dir: true,
-            }]
-        );
+        assert_eq!(entries, vec![Entry::dir("child")]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn list_entries_classifies_links_executables_and_fifos() {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("dir")).unwrap();
+        fs::write(root.join("run.sh"), "").unwrap();
+        fs::set_permissions(root.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        symlink("dir", root.join("dir-link")).unwrap();
+        symlink("missing", root.join("dangling")).unwrap();
+        let fifo = std::process::Command::new("mkfifo")
+            .arg(root.join("pipe"))
+            .status()
+            .is_ok_and(|status| status.success());
+
+        let entries = list_entries(root, true).unwrap();
+        let find = |name: &str| entries.iter().find(|entry| entry.name == name).unwrap();
+
+        assert_eq!(find("dir").kind, EntryKind::Dir);
+        assert_eq!(find("run.sh").kind, EntryKind::Executable);
+        let link = find("dir-link");
+        assert_eq!(link.kind, EntryKind::Symlink);
+        assert!(link.is_dir);
+        assert_eq!(link.link_target, Some(PathBuf::from("dir")));
+        let dangling = find("dangling");
+        assert_eq!(dangling.kind, EntryKind::BrokenSymlink);
+        assert!(!dangling.is_dir);
+        if fifo {
+            assert_eq!(find("pipe").kind, EntryKind::Fifo);
+        }
     }
 
     #[test]
@@ -89,12 +470,51 @@ mod tests {
 
         let entries = list_entries(temp_dir.path(), true).unwrap();
 
+        assert_eq!(entries, vec![Entry::file(".secret")]);
+    }
+
+    #[test]
+    fn natural_cmp_orders_numbers_by_value() {
+        let mut names = vec!["file10", "file2", "file1", "file02b", "file"];
+
+        names.sort_by(|left, right| natural_cmp(left, right));
+
+        assert_eq!(names, vec!["file", "file1", "file2", "file02b", "file10"]);
+    }
+
+    #[test]
+    fn list_entries_sorted_applies_key_and_flags() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("b.txt"), "bbb").unwrap();
+        fs::write(temp_dir.path().join("A.rs"), "a").unwrap();
+        fs::write(temp_dir.path().join("c.md"), "cc").unwrap();
+        fs::create_dir(temp_dir.path().join("z")).unwrap();
+        let names = |sort: &str| {
+            list_entries_sorted(temp_dir.path(), true, &SortMode::parse(sort).unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("name"), vec!["A.rs", "b.txt", "c.md", "z"]);
         assert_eq!(
-            entries,
-            vec![Entry {
-                name: ".secret".to_string(),
-                is_dir: false,
-            }]
+            names("name reverse dirs-first"),
+            vec!["z", "c.md", "b.txt", "A.rs"]
         );
+        assert_eq!(names("ext"), vec!["z", "c.md", "A.rs", "b.txt"]);
+        assert_eq!(names("type"), vec!["z", "A.rs", "b.txt", "c.md"]);
+        let by_size = names("size dirs-first");
+        assert_eq!(by_size[1..], ["A.rs", "c.md", "b.txt"]);
+    }
+
+    #[test]
+    fn sort_mode_round_trips_through_label() {
+        let mode = SortMode::parse("natural, reverse ignore-case").unwrap();
+
+        assert_eq!(mode.label(), "natural reverse ignore-case");
+        assert_eq!(SortMode::parse(&mode.label()), Some(mode));
+        assert_eq!(SortMode::parse("bogus"), None);
+        assert_eq!(SortMode::parse("name sideways"), None);
     }
 }
@@ -0,0 +1,142 @@
+use std::path::{Component, Path, PathBuf};
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ExpandError {
+    #[error("HOME is not set")]
+    NoHome,
+    #[error("undefined variable: ${0}")]
+    UndefinedVariable(String),
+    #[error("unclosed ${{")]
+    UnclosedBrace,
+}
+
+pub fn expand_path(input: &str, base: &Path) -> Result<PathBuf, ExpandError> {
+    expand_path_with(input, base, &|name| std::env::var(name).ok())
+}
+
+fn expand_path_with(
+    input: &str,
+    base: &Path,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<PathBuf, ExpandError> {
+    let expanded = expand_vars(&expand_tilde(input, lookup)?, lookup)?;
+    let path = Path::new(&expanded);
+    let joined = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    };
+    Ok(normalize(&joined))
+}
+
+fn expand_tilde(
+    input: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, ExpandError> {
+    let Some(rest) = input.strip_prefix('~') else {
+        return Ok(input.to_string());
+    };
+    if !rest.is_empty() && !rest.starts_with(['/', std::path::MAIN_SEPARATOR]) {
+        return Ok(input.to_string());
+    }
+    let home = lookup("HOME").ok_or(ExpandError::NoHome)?;
+    Ok(format!("{home}{rest}"))
+}
+
+fn expand_vars(
+    input: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, ExpandError> {
+    let mut output = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '$' {
+            output.push(ch);
+            continue;
+        }
+        let name = if chars.next_if_eq(&'{').is_some() {
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => break,
+                    Some(ch) => name.push(ch),
+                    None => return Err(ExpandError::UnclosedBrace),
+                }
+            }
+            name
+        } else {
+            let mut name = String::new();
+            while let Some(ch) = chars.next_if(|ch| ch.is_ascii_alphanumeric() || *ch == '_') {
+                name.push(ch);
+            }
+            name
+        };
+        if name.is_empty() {
+            output.push('$');
+            continue;
+        }
+        let value = lookup(&name).ok_or(ExpandError::UndefinedVariable(name))?;
+        output.push_str(&value);
+    }
+    Ok(output)
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/ox".to_string()),
+            "PROJ" => Some("work/oxide".to_string()),
+            _ => None,
+        }
+    }
+
+    fn expand(input: &str) -> Result<PathBuf, ExpandError> {
+        expand_path_with(input, Path::new("/tmp/base"), &lookup)
+    }
+
+    #[test]
+    fn expand_path_resolves_tilde_variables_and_relative_paths() {
+        assert_eq!(expand("~"), Ok(PathBuf::from("/home/ox")));
+        assert_eq!(expand("~/src"), Ok(PathBuf::from("/home/ox/src")));
+        assert_eq!(
+            expand("$HOME/$PROJ"),
+            Ok(PathBuf::from("/home/ox/work/oxide"))
+        );
+        assert_eq!(expand("${HOME}/a"), Ok(PathBuf::from("/home/ox/a")));
+        assert_eq!(expand("../x/./y"), Ok(PathBuf::from("/tmp/x/y")));
+        assert_eq!(expand("~user"), Ok(PathBuf::from("/tmp/base/~user")));
+        assert_eq!(expand("cost$"), Ok(PathBuf::from("/tmp/base/cost$")));
+    }
+
+    #[test]
+    fn expand_path_reports_undefined_variables() {
+        assert_eq!(
+            expand("$NOPE/a"),
+            Err(ExpandError::UndefinedVariable("NOPE".to_string()))
+        );
+        assert_eq!(expand("${HOME"), Err(ExpandError::UnclosedBrace));
+        assert_eq!(
+            expand_path_with("~", Path::new("/"), &|_| None),
+            Err(ExpandError::NoHome)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::core::{TrashStore, TrashedItem, copy_path, remove_path};
+
+#[derive(Debug, Error)]
+pub enum FileOpError {
+    #[error("invalid name: {0}")]
+    InvalidName(String),
+    #[error("already exists: {}", .0.display())]
+    AlreadyExists(PathBuf),
+    #[error("not found: {}", .0.display())]
+    NotFound(PathBuf),
+    #[error("cannot move a directory into itself")]
+    IntoItself,
+    #[error("trash unavailable; cannot overwrite")]
+    TrashUnavailable,
+    #[error("crosses devices: {}", .0.display())]
+    CrossesDevices(PathBuf),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub fn validate_entry_name(name: &str) -> Result<(), FileOpError> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+        return Err(FileOpError::InvalidName(name.to_string()));
+    }
+    if name.contains('/') || name.contains(std::path::MAIN_SEPARATOR) || name.contains('\0') {
+        return Err(FileOpError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+pub fn rename_entry(source: &Path, new_name: &str) -> Result<PathBuf, FileOpError> {
+    validate_entry_name(new_name)?;
+    let parent = source.parent().unwrap_or_else(|| Path::new("."));
+    let target = parent.join(new_name);
+    if target == source {
+        return Ok(target);
+    }
+    move_path(source, &target)
+}
+
+/// 同じデバイス内での移動だけを行う。デバイスをまたぐときは `CrossesDevices` を返すので、
+/// 呼び出し側で進捗付きのコピーに回す。
+pub fn move_entry(source: &Path, dest: &Path) -> Result<PathBuf, FileOpError> {
+    let target = resolve_move_target(source, dest)?;
+    rename_path(source, &target)
+}
+
+pub fn move_path(source: &Path, target: &Path) -> Result<PathBuf, FileOpError> {
+    move_to_target(source, target, &|from, to| std::fs::rename(from, to))
+}
+
+pub fn rename_path(source: &Path, target: &Path) -> Result<PathBuf, FileOpError> {
+    rename_to_target(source, target, &|from, to| std::fs::rename(from, to))
+}
+
+pub fn move_replacing(
+    source: &Path,
+    target: &Path,
+    trash: Option<&TrashStore>,
+) -> Result<(PathBuf, TrashedItem), FileOpError> {
+    let Some(trash) = trash else {
+        return Err(FileOpError::TrashUnavailable);
+    };
+    if source.symlink_metadata().is_err() {
+        return Err(FileOpError::NotFound(source.to_path_buf()));
+    }
+    if is_same_entry(source, target) {
+        return Err(FileOpError::AlreadyExists(target.to_path_buf()));
+    }
+    let replaced = trash.trash(target)?;
+    match rename_path(source, target) {
+        Ok(moved) => Ok((moved, replaced)),
+        Err(error) => {
+            let _ = trash.restore(&replaced);
+            Err(error)
+        }
+    }
+}
+
+pub fn resolve_move_target(source: &Path, dest: &Path) -> Result<PathBuf, FileOpError> {
+    if dest.is_dir() {
+        let Some(name) = source.file_name() else {
+            return Err(FileOpError::InvalidName(source.display().to_string()));
+        };
+        return Ok(dest.join(name));
+    }
+    match dest.parent() {
+        Some(parent) if parent.as_os_str().is_empty() || parent.is_dir() => Ok(dest.to_path_buf()),
+        Some(parent) => Err(FileOpError::NotFound(parent.to_path_buf())),
+        None => Err(FileOpError::InvalidName(dest.display().to_string())),
+    }
+}
+
+fn move_to_target(
+    source: &Path,
+    target: &Path,
+    rename: &dyn Fn(&Path, &Path) -> std::io::Result<()>,
+) -> Result<PathBuf, FileOpError> {
+    match rename_to_target(source, target, rename) {
+        Err(FileOpError::CrossesDevices(_)) => {
+            copy_path(source, target)?;
+            remove_path(source)?;
+            Ok(target.to_path_buf())
+        }
+        result => result,
+    }
+}
+
+fn rename_to_target(
+    source: &Path,
+    target: &Path,
+    rename: &dyn Fn(&Path, &Path) -> std::io::Result<()>,
+) -> Result<PathBuf, FileOpError> {
+    if source.symlink_metadata().is_err() {
+        return Err(FileOpError::NotFound(source.to_path_buf()));
+    }
+    if target.symlink_metadata().is_ok() && !is_same_entry(source, target) {
+        return Err(FileOpError::AlreadyExists(target.to_path_buf()));
+    }
+    if source.is_dir() && is_inside(target, source) {
+        return Err(FileOpError::IntoItself);
+    }
+    match rename(source, target) {
+        Ok(()) => Ok(target.to_path_buf()),
+        Err(error) if error.kind() == std::io::ErrorKind::CrossesDevices => {
+            Err(FileOpError::CrossesDevices(target.to_path_buf()))
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn is_same_entry(source: &Path, target: &Path) -> bool {
+    match (source.canonicalize(), target.canonicalize()) {
+        (Ok(source), Ok(target)) => source == target,
+        _ => false,
+    }
+}
+
+fn is_inside(target: &Path, source: &Path) -> bool {
+    let Ok(source) = source.canonicalize() else {
+        return false;
+    };
+    let Some(parent) = target.parent() else {
+        return false;
+    };
+    match parent.canonicalize() {
+        Ok(parent) => parent.starts_with(&source),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn validate_entry_name_rejects_separators_and_dots() {
+        assert!(validate_entry_name("ok.txt").is_ok());
+        assert!(validate_entry_name("").is_err());
+        assert!(validate_entry_name("..").is_err());
+        assert!(validate_entry_name("a/b").is_err());
+    }
+
+    #[test]
+    fn rename_entry_renames_within_parent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("old.txt");
+        fs::write(&source, "data").unwrap();
+
+        let target = rename_entry(&source, "new.txt").unwrap();
+
+        assert_eq!(target, temp_dir.path().join("new.txt"));
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(target).unwrap(), "data");
+    }
+
+    #[test]
+    fn rename_entry_rejects_existing_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("old.txt");
+        fs::write(&source, "old").unwrap();
+        fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+
+        let result = rename_entry(&source, "new.txt");
+
+        assert!(matches!(result, Err(FileOpError::AlreadyExists(_))));
+        assert!(source.exists());
+    }
+
+    #[test]
+    fn move_entry_moves_into_existing_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("note.txt");
+        let dest = temp_dir.path().join("dest");
+        fs::write(&source, "data").unwrap();
+        fs::create_dir(&dest).unwrap();
+
+        let target = move_entry(&source, &dest).unwrap();
+
+        assert_eq!(target, dest.join("note.txt"));
+        assert!(target.exists());
+        assert!(!source.exists());
+    }
+
+    #[test]
+    fn move_entry_rejects_directory_into_itself() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("tree");
+        fs::create_dir_all(source.join("child")).unwrap();
+
+        let result = move_entry(&source, &source.join("child"));
+
+        assert!(matches!(result, Err(FileOpError::IntoItself)));
+    }
+
+    #[test]
+    fn move_replacing_trashes_the_old_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("new.txt");
+        let target = temp_dir.path().join("old.txt");
+        fs::write(&source, "new").unwrap();
+        fs::write(&target, "old").unwrap();
+
+        assert!(matches!(
+            move_replacing(&source, &target, None),
+            Err(FileOpError::TrashUnavailable)
+        ));
+        let (moved, replaced) = move_replacing(&source, &target, Some(&store)).unwrap();
+
+        assert_eq!(moved, target);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(replaced.original_path, target);
+        fs::remove_file(&target).unwrap();
+        store.restore(&replaced).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "old");
+    }
+
+    #[test]
+    fn move_falls_back_to_copy_when_crossing_devices() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("tree");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("a.txt"), "a").unwrap();
+        let target = temp_dir.path().join("moved");
+        let cross_device = |_: &Path, _: &Path| -> std::io::Result<()> {
+            Err(std::io::Error::from(std::io::ErrorKind::CrossesDevices))
+        };
+
+        let moved = move_to_target(&source, &target, &cross_device).unwrap();
+
+        assert_eq!(moved, target);
+        assert_eq!(fs::read_to_string(target.join("a.txt")).unwrap(), "a");
+        assert!(!source.exists());
+    }
+
+    #[test]
+    fn rename_reports_crossing_devices_without_copying() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("a.txt");
+        fs::write(&source, "a").unwrap();
+        let target = temp_dir.path().join("b.txt");
+        let cross_device = |_: &Path, _: &Path| -> std::io::Result<()> {
+            Err(std::io::Error::from(std::io::ErrorKind::CrossesDevices))
+        };
+
+        let result = rename_to_target(&source, &target, &cross_device);
+
+        assert!(matches!(result, Err(FileOpError::CrossesDevices(path)) if path == target));
+        assert!(source.exists());
+        assert!(!target.exists());
+    }
+}
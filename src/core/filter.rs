@@ -0,0 +1,51 @@
+use crate::core::glob::glob_match;
+use crate::core::regex::{Regex, RegexError};
+
+#[derive(Debug, Clone)]
+pub enum EntryFilter {
+    Glob(String),
+    Regex(Regex),
+}
+
+impl EntryFilter {
+    pub fn glob(pattern: &str) -> Self {
+        Self::Glob(pattern.to_string())
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self, RegexError> {
+        Regex::new(pattern).map(Self::Regex)
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Glob(pattern) => glob_match(pattern, name),
+            Self::Regex(regex) => regex.is_match(name),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Glob(pattern) => pattern.clone(),
+            Self::Regex(regex) => format!("regex {}", regex.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_filter_matches_glob_and_regex() {
+        let glob = EntryFilter::glob("*.rs");
+        assert!(glob.matches("main.rs"));
+        assert!(!glob.matches("main.rs.bak"));
+        assert_eq!(glob.label(), "*.rs");
+
+        let regex = EntryFilter::regex(r"\.rs").unwrap();
+        assert!(regex.matches("main.rs.bak"));
+        assert!(!regex.matches("README"));
+        assert_eq!(regex.label(), r"regex \.rs");
+        assert!(EntryFilter::regex("(").is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::core::gitignore::GitIgnore;
+use crate::core::glob::glob_match;
+
+const MAX_FIND_RESULTS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindRequest {
+    pub id: u64,
+    pub root: PathBuf,
+    pub query: String,
+    pub show_hidden: bool,
+    pub hide_ignored: bool,
+    pub max_depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FindEvent {
+    Found {
+        id: u64,
+        path: PathBuf,
+    },
+    Finished {
+        id: u64,
+        found: usize,
+        cancelled: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct FinderWorker {
+    request_tx: Sender<FindRequest>,
+    event_rx: Receiver<FindEvent>,
+    active: Arc<AtomicU64>,
+}
+
+impl FinderWorker {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<FindRequest>();
+        let (event_tx, event_rx) = mpsc::channel::<FindEvent>();
+        let active = Arc::new(AtomicU64::new(0));
+        let worker_active = Arc::clone(&active);
+
+        thread::spawn(move || {
+            request_rx.into_iter().for_each(|request| {
+                let id = request.id;
+                let is_cancelled = || worker_active.load(Ordering::Relaxed) != id;
+                let mut found = 0;
+                let completed = find_paths(&request, &is_cancelled, &mut |path| {
+                    found += 1;
+                    let _ = event_tx.send(FindEvent::Found { id, path });
+                });
+                let _ = event_tx.send(FindEvent::Finished {
+                    id,
+                    found,
+                    cancelled: !completed,
+                });
+            });
+        });
+
+        Self {
+            request_tx,
+            event_rx,
+            active,
+        }
+    }
+
+    pub fn request(&self, request: FindRequest) {
+        self.active.store(request.id, Ordering::Relaxed);
+        let _ = self.request_tx.send(request);
+    }
+
+    pub fn cancel(&self) {
+        self.active.store(0, Ordering::Relaxed);
+    }
+
+    pub fn poll(&self) -> Option<FindEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    #[cfg(test)]
+    pub fn recv_timeout(&self, timeout: std::time::Duration) -> Option<FindEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+}
+
+fn find_paths(
+    request: &FindRequest,
+    is_cancelled: &dyn Fn() -> bool,
+    on_found: &mut dyn FnMut(PathBuf),
+) -> bool {
+    let mut found = 0;
+    let walk = TreeWalk {
+        root: &request.root,
+        show_hidden: request.show_hidden,
+        hide_ignored: request.hide_ignored,
+        max_depth: request.max_depth,
+    };
+    walk.run(is_cancelled, &mut |path, _| {
+        if name_matches(&request.query, path) {
+            on_found(path.to_path_buf());
+            found += 1;
+        }
+        found < MAX_FIND_RESULTS
+    })
+}
+
+pub(crate) struct TreeWalk<'a> {
+    pub(crate) root: &'a Path,
+    pub(crate) show_hidden: bool,
+    pub(crate) hide_ignored: bool,
+    pub(crate) max_depth: usize,
+}
+
+impl TreeWalk<'_> {
+    pub(crate) fn run(
+        &self,
+        is_cancelled: &dyn Fn() -> bool,
+        visit: &mut dyn FnMut(&Path, bool) -> bool,
+    ) -> bool {
+        let ignore = self.hide_ignored.then(|| GitIgnore::for_dir(self.root));
+        let mut queue = VecDeque::from([(self.root.to_path_buf(), 0usize, ignore)]);
+        while let Some((dir, depth, ignore)) = queue.pop_front() {
+            if is_cancelled() {
+                return false;
+            }
+            let Ok(read_dir) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut children = read_dir
+                .filter_map(Result::ok)
+                .map(|entry| {
+                    let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+                    (entry.path(), is_dir)
+                })
+                .filter(|(path, _)| self.show_hidden || !is_hidden(path))
+                .filter(|(path, is_dir)| {
+                    ignore
+                        .as_ref()
+                        .is_none_or(|ignore| !ignore.is_ignored(path, *is_dir))
+                })
+                .collect::<Vec<_>>();
+            children.sort();
+            for (path, is_dir) in children {
+                if !visit(&path, is_dir) {
+                    return true;
+                }
+                if is_dir && depth < self.max_depth {
+                    let ignore = ignore.as_ref().map(|ignore| ignore.child(&path));
+                    queue.push_back((path, depth + 1, ignore));
+                }
+            }
+        }
+        true
+    }
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+fn name_matches(query: &str, path: &Path) -> bool {
+    let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+        return false;
+    };
+    if query.contains(['*', '?', '[']) {
+        return glob_match(query, &name);
+    }
+    if query.chars().any(char::is_uppercase) {
+        name.contains(query)
+    } else {
+        name.to_lowercase().contains(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    fn request(root: &Path, query: &str, max_depth: usize) -> FindRequest {
+        FindRequest {
+            id: 1,
+            root: root.to_path_buf(),
+            query: query.to_string(),
+            show_hidden: false,
+            hide_ignored: false,
+            max_depth,
+        }
+    }
+
+    fn collect(request: &FindRequest) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        find_paths(request, &|| false, &mut |path| found.push(path));
+        found
+    }
+
+    #[test]
+    fn find_paths_walks_breadth_first_within_depth() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("a/b/c/Config.toml"), "").unwrap();
+        fs::write(root.join("a/config.rs"), "").unwrap();
+        fs::write(root.join(".git/config"), "").unwrap();
+
+        assert_eq!(
+            collect(&request(root, "config", 8)),
+            vec![root.join("a/config.rs"), root.join("a/b/c/Config.toml")]
+        );
+        assert_eq!(
+            collect(&request(root, "Config", 8)),
+            vec![root.join("a/b/c/Config.toml")]
+        );
+        assert_eq!(
+            collect(&request(root, "*.rs", 1)),
+            vec![root.join("a/config.rs")]
+        );
+        assert_eq!(collect(&request(root, "config", 1)).len(), 1);
+        let mut hidden = request(root, "config", 8);
+        hidden.show_hidden = true;
+        assert_eq!(collect(&hidden).len(), 3);
+    }
+
+    #[test]
+    fn find_paths_skips_gitignored_entries_when_requested() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::create_dir_all(root.join("web/node_modules/pkg")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.join("web/.gitignore"), "node_modules\n").unwrap();
+        fs::write(root.join("target/debug/main.rs"), "").unwrap();
+        fs::write(root.join("web/node_modules/pkg/main.rs"), "").unwrap();
+        fs::write(root.join("web/main.rs"), "").unwrap();
+
+        let mut ignored = request(root, "main", 8);
+        assert_eq!(collect(&ignored).len(), 3);
+        ignored.hide_ignored = true;
+        assert_eq!(collect(&ignored), vec![root.join("web/main.rs")]);
+    }
+
+    #[test]
+    fn find_paths_stops_when_cancelled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("note.txt"), "").unwrap();
+        let mut found = Vec::new();
+
+        let completed = find_paths(
+            &request(temp_dir.path(), "note", 4),
+            &|| true,
+            &mut |path| found.push(path),
+        );
+
+        assert!(!completed);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn finder_worker_streams_results_then_finishes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/main.rs"), "").unwrap();
+        let worker = FinderWorker::new();
+
+        worker.request(request(temp_dir.path(), "main", 4));
+
+        let found = worker
+            .event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(
+            found,
+            FindEvent::Found {
+                id: 1,
+                path: temp_dir.path().join("src/main.rs"),
+            }
+        );
+        let finished = worker
+            .event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(
+            finished,
+            FindEvent::Finished {
+                id: 1,
+                found: 1,
+                cancelled: false,
+            }
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use serde_json::Value;
+
+use crate::config::config_root;
+
+const FRECENCY_FILE: &str = "frecency.json";
+const MAX_TOTAL_RANK: f64 = 1000.0;
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+#[derive(Debug, Clone, PartialEq)]
+struct FrecencyEntry {
+    path: PathBuf,
+    rank: f64,
+    last_access: u64,
+}
+
+impl FrecencyEntry {
+    fn score(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_access);
+        let weight = if age < HOUR {
+            4.0
+        } else if age < DAY {
+            2.0
+        } else if age < WEEK {
+            0.5
+        } else {
+            0.25
+        };
+        self.rank * weight
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrecencySnapshot {
+    path: PathBuf,
+    entries: Vec<FrecencyEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrecencyEvent {
+    Saved { pruned: Vec<PathBuf> },
+    SaveFailed(String),
+}
+
+#[derive(Debug)]
+pub struct FrecencyWorker {
+    request_tx: Sender<FrecencySnapshot>,
+    event_rx: Receiver<FrecencyEvent>,
+}
+
+impl FrecencyWorker {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<FrecencySnapshot>();
+        let (event_tx, event_rx) = mpsc::channel::<FrecencyEvent>();
+
+        thread::spawn(move || {
+            request_rx.into_iter().for_each(|mut snapshot| {
+                let event = match snapshot.save() {
+                    Ok(pruned) => FrecencyEvent::Saved { pruned },
+                    Err(error) => FrecencyEvent::SaveFailed(error.to_string()),
+                };
+                let _ = event_tx.send(event);
+            });
+        });
+
+        Self {
+            request_tx,
+            event_rx,
+        }
+    }
+
+    pub fn request(&self, snapshot: FrecencySnapshot) {
+        let _ = self.request_tx.send(snapshot);
+    }
+
+    pub fn poll(&self) -> Option<FrecencyEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    #[cfg(test)]
+    pub fn recv_timeout(&self, timeout: std::time::Duration) -> Option<FrecencyEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+}
+
+impl FrecencySnapshot {
+    /// 存在しないディレクトリを除いてから書き出し、除いたパスを返す。
+    fn save(&mut self) -> std::io::Result<Vec<PathBuf>> {
+        let pruned = self.prune_missing();
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, build_frecency_payload(&self.entries))?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(pruned)
+    }
+
+    fn prune_missing(&mut self) -> Vec<PathBuf> {
+        let (kept, missing) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition::<Vec<_>, _>(|entry| entry.path.is_dir());
+        self.entries = kept;
+        missing.into_iter().map(|entry| entry.path).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frecency {
+    path: Option<PathBuf>,
+    entries: Vec<FrecencyEntry>,
+}
+
+impl Frecency {
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(FRECENCY_FILE);
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .map(|content| parse_frecency(&content))
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            entries,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        config_root()
+            .map(|root| Self::load(&root))
+            .unwrap_or_else(Self::in_memory)
+    }
+
+    pub fn record(&mut self, path: &Path, now: u64) {
+        match self.entries.iter_mut().find(|entry| entry.path == path) {
+            Some(entry) => {
+                entry.rank += 1.0;
+                entry.last_access = now;
+            }
+            None => self.entries.push(FrecencyEntry {
+                path: path.to_path_buf(),
+                rank: 1.0,
+                last_access: now,
+            }),
+        }
+        self.age();
+    }
+
+    pub fn matches(&self, fragments: &[String], exclude: &Path, now: u64) -> Vec<PathBuf> {
+        let mut matches = self
+            .entries
+            .iter()
+            .filter(|entry| entry.path != exclude && path_matches(&entry.path, fragments))
+            .map(|entry| (entry.score(now), &entry.path))
+            .collect::<Vec<_>>();
+        matches.sort_by(|left, right| right.0.total_cmp(&left.0));
+        matches.into_iter().map(|(_, path)| path.clone()).collect()
+    }
+
+    /// 保存用の写しを返す。永続化先のない場合は `None`。
+    pub fn snapshot(&self) -> Option<FrecencySnapshot> {
+        Some(FrecencySnapshot {
+            path: self.path.clone()?,
+            entries: self.entries.clone(),
+        })
+    }
+
+    pub fn forget(&mut self, paths: &[PathBuf]) {
+        self.entries.retain(|entry| !paths.contains(&entry.path));
+    }
+
+    fn age(&mut self) {
+        let total = self.entries.iter().map(|entry| entry.rank).sum::<f64>();
+        if total <= MAX_TOTAL_RANK {
+            return;
+        }
+        let factor = 0.9 * MAX_TOTAL_RANK / total;
+        for entry in &mut self.entries {
+            entry.rank *= factor;
+        }
+        self.entries.retain(|entry| entry.rank >= 1.0);
+    }
+}
+
+fn path_matches(path: &Path, fragments: &[String]) -> bool {
+    let Some(last) = fragments.last() else {
+        return false;
+    };
+    let text = path.to_string_lossy().to_lowercase();
+    let name_start = text
+        .rfind(std::path::MAIN_SEPARATOR)
+        .map_or(0, |index| index + 1);
+    let mut start = 0;
+    for fragment in fragments {
+        let fragment = fragment.to_lowercase();
+        let Some(offset) = text[start..].find(&fragment) else {
+            return false;
+        };
+        start += offset + fragment.len();
+    }
+    text[name_start..].contains(&last.to_lowercase())
+}
+
+fn build_frecency_payload(entries: &[FrecencyEntry]) -> String {
+    serde_json::json!({
+        "version": 1,
+        "entries": entries
+            .iter()
+            .map(|entry| serde_json::json!({
+                "path": entry.path.to_string_lossy(),
+                "rank": entry.rank,
+                "last_access": entry.last_access,
+            }))
+            .collect::<Vec<_>>(),
+    })
+    .to_string()
+}
+
+fn parse_frecency(content: &str) -> Vec<FrecencyEntry> {
+    let Ok(value) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    value
+        .get("entries")
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    Some(FrecencyEntry {
+                        path: PathBuf::from(entry.get("path")?.as_str()?),
+                        rank: entry.get("rank")?.as_f64()?,
+                        last_access: entry.get("last_access")?.as_u64()?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    fn fragments(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_rank_by_frequency_and_recency() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let old = root.join("work").join("oxide");
+        let fresh = root.join("play").join("oxide-web");
+        fs::create_dir_all(&old).unwrap();
+        fs::create_dir_all(&fresh).unwrap();
+        let mut frecency = Frecency::load(root);
+        for _ in 0..3 {
+            frecency.record(&old, 0);
+        }
+        frecency.record(&fresh, 10 * DAY);
+
+        let now = 10 * DAY + 60;
+        assert_eq!(
+            frecency.matches(&fragments(&["oxide"]), root, now),
+            vec![fresh.clone(), old.clone()]
+        );
+        assert_eq!(
+            frecency.matches(&fragments(&["WORK", "ox"]), root, now),
+            vec![old.clone()]
+        );
+        assert!(
+            frecency
+                .matches(&fragments(&["oxide", "work"]), root, now)
+                .is_empty()
+        );
+        assert_eq!(
+            frecency.matches(&fragments(&["oxide"]), &fresh, now),
+            vec![old.clone()]
+        );
+        assert_eq!(Frecency::load(root).entries, Vec::new());
+        assert_eq!(
+            frecency.snapshot().unwrap().save().unwrap(),
+            Vec::<PathBuf>::new()
+        );
+        assert_eq!(Frecency::load(root), frecency);
+    }
+
+    #[test]
+    fn aging_and_pruning_drop_stale_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let kept = temp_dir.path().join("kept");
+        fs::create_dir(&kept).unwrap();
+        let mut frecency = Frecency::in_memory();
+        for _ in 0..1000 {
+            frecency.record(&kept, 0);
+        }
+        frecency.record(&temp_dir.path().join("gone"), 0);
+        assert_eq!(frecency.entries.len(), 1);
+        assert!(frecency.entries[0].rank < MAX_TOTAL_RANK);
+        assert_eq!(frecency.snapshot(), None);
+    }
+
+    #[test]
+    fn worker_prunes_missing_directories_and_reports_failures() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let kept = temp_dir.path().join("kept");
+        let gone = temp_dir.path().join("gone");
+        fs::create_dir(&kept).unwrap();
+        let mut frecency = Frecency::load(temp_dir.path());
+        frecency.record(&kept, 0);
+        frecency.record(&gone, 0);
+        let worker = FrecencyWorker::new();
+
+        worker.request(frecency.snapshot().unwrap());
+        let event = worker.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(
+            event,
+            FrecencyEvent::Saved {
+                pruned: vec![gone.clone()]
+            }
+        );
+        frecency.forget(&[gone]);
+        assert_eq!(Frecency::load(temp_dir.path()), frecency);
+
+        let blocked = temp_dir.path().join("blocked");
+        fs::write(&blocked, "").unwrap();
+        worker.request(Frecency::load(&blocked).snapshot().unwrap());
+        assert!(matches!(
+            worker.recv_timeout(Duration::from_secs(2)),
+            Some(FrecencyEvent::SaveFailed(_))
+        ));
+    }
+}
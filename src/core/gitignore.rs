@@ -0,0 +1,263 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::expand::expand_path;
+use crate::core::glob::glob_match;
+
+const GITIGNORE_FILE: &str = ".gitignore";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IgnoreRule {
+    base: PathBuf,
+    segments: Vec<String>,
+    anchored: bool,
+    dir_only: bool,
+    negated: bool,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str, base: &Path) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let anchored = pattern.contains('/');
+        let segments = pattern
+            .trim_start_matches('/')
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if segments.is_empty() {
+            return None;
+        }
+        Some(Self {
+            base: base.to_path_buf(),
+            segments,
+            anchored,
+            dir_only,
+            negated,
+        })
+    }
+
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let Ok(relative) = path.strip_prefix(&self.base) else {
+            return false;
+        };
+        let components = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>();
+        if !self.anchored {
+            return components
+                .last()
+                .is_some_and(|name| glob_match(&self.segments[0], name));
+        }
+        let components = components
+            .iter()
+            .map(|component| component.as_ref())
+            .collect::<Vec<_>>();
+        match_segments(&self.segments, &components)
+    }
+}
+
+fn match_segments(segments: &[String], components: &[&str]) -> bool {
+    let Some((first, rest)) = segments.split_first() else {
+        return components.is_empty();
+    };
+    if first == "**" {
+        if rest.is_empty() {
+            return !components.is_empty();
+        }
+        return (0..=components.len()).any(|skip| match_segments(rest, &components[skip..]));
+    }
+    match components.split_first() {
+        Some((component, remaining)) => {
+            glob_match(first, component) && match_segments(rest, remaining)
+        }
+        None => false,
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl GitIgnore {
+    pub fn for_dir(dir: &Path) -> Self {
+        Self::load(dir, global_excludes_path().as_deref())
+    }
+
+    fn load(dir: &Path, global_excludes: Option<&Path>) -> Self {
+        let mut ignore = Self::default();
+        let Some(root) = dir
+            .ancestors()
+            .find(|ancestor| ancestor.join(".git").exists())
+        else {
+            return ignore;
+        };
+        if let Some(path) = global_excludes {
+            ignore.add_file(path, root);
+        }
+        ignore.add_file(&root.join(".git").join("info").join("exclude"), root);
+        let mut dirs = dir
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(root))
+            .collect::<Vec<_>>();
+        dirs.reverse();
+        for dir in dirs {
+            ignore.add_file(&dir.join(GITIGNORE_FILE), dir);
+        }
+        ignore
+    }
+
+    pub fn child(&self, dir: &Path) -> Self {
+        let mut ignore = self.clone();
+        ignore.add_file(&dir.join(GITIGNORE_FILE), dir);
+        ignore
+    }
+
+    /// `path` 自身か、ルールの適用範囲にある祖先フォルダのどれかが除外されていれば真。
+    /// git と同じく、除外されたフォルダの中身は `!` で戻せない。
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        path.ancestors()
+            .skip(1)
+            .take_while(|ancestor| {
+                self.rules
+                    .iter()
+                    .any(|rule| ancestor.starts_with(&rule.base))
+            })
+            .any(|ancestor| self.matches(ancestor, true))
+            || self.matches(path, is_dir)
+    }
+
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        if path.file_name().is_some_and(|name| name == ".git") {
+            return true;
+        }
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path, is_dir))
+            .is_some_and(|rule| !rule.negated)
+    }
+
+    fn add_file(&mut self, path: &Path, base: &Path) {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return;
+        };
+        self.rules.extend(
+            content
+                .lines()
+                .filter_map(|line| IgnoreRule::parse(line, base)),
+        );
+    }
+}
+
+fn global_excludes_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let configured = home
+        .as_ref()
+        .and_then(|home| std::fs::read_to_string(home.join(".gitconfig")).ok())
+        .and_then(|content| core_excludes_file(&content));
+    if let Some(value) = configured {
+        let base = home.clone().unwrap_or_default();
+        return expand_path(&value, &base).ok();
+    }
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.map(|home| home.join(".config")))?;
+    Some(config_home.join("git").join("ignore"))
+}
+
+fn core_excludes_file(gitconfig: &str) -> Option<String> {
+    let mut in_core = false;
+    for line in gitconfig.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_core = line.eq_ignore_ascii_case("[core]");
+            continue;
+        }
+        if !in_core {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim().eq_ignore_ascii_case("excludesfile") {
+            return Some(value.trim().trim_matches('"').to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn gitignore_applies_nested_files_anchors_and_negation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(".git").join("info")).unwrap();
+        fs::create_dir_all(root.join("web").join("dist")).unwrap();
+        fs::write(
+            root.join(".gitignore"),
+            "target/\n*.log\n!keep.log\n/build\n",
+        )
+        .unwrap();
+        fs::write(root.join(".git/info/exclude"), "scratch.txt\n").unwrap();
+        fs::write(root.join("web/.gitignore"), "node_modules\ndist/**\n").unwrap();
+        let global = root.join("global-ignore");
+        fs::write(&global, "# comment\n*.swp\n").unwrap();
+
+        let ignore = GitIgnore::load(root, Some(&global));
+        assert!(ignore.is_ignored(&root.join("target"), true));
+        assert!(!ignore.is_ignored(&root.join("target"), false));
+        assert!(ignore.is_ignored(&root.join("debug.log"), false));
+        assert!(!ignore.is_ignored(&root.join("keep.log"), false));
+        assert!(ignore.is_ignored(&root.join("build"), true));
+        assert!(!ignore.is_ignored(&root.join("src").join("build"), true));
+        assert!(ignore.is_ignored(&root.join("scratch.txt"), false));
+        assert!(ignore.is_ignored(&root.join("main.rs.swp"), false));
+        assert!(ignore.is_ignored(&root.join(".git"), true));
+        assert!(!ignore.is_ignored(&root.join("web").join("node_modules"), true));
+        let nested = root.join("target").join("debug").join("foo");
+        assert!(ignore.is_ignored(&nested, false));
+        assert!(ignore.is_ignored(&root.join("target").join("debug"), true));
+        assert!(ignore.is_ignored(&root.join("target").join("keep.log"), false));
+        assert!(ignore.is_ignored(&root.join(".git").join("HEAD"), false));
+        assert!(!ignore.is_ignored(&root.join("src").join("debug").join("foo"), false));
+
+        let web = GitIgnore::load(&root.join("web"), None);
+        assert_eq!(web, GitIgnore::load(root, None).child(&root.join("web")));
+        assert!(web.is_ignored(&root.join("web").join("node_modules"), true));
+        assert!(web.is_ignored(&root.join("web").join("sub").join("app.log"), false));
+        assert!(!web.is_ignored(&root.join("web").join("dist"), true));
+        assert!(web.is_ignored(&root.join("web").join("dist").join("app.js"), false));
+    }
+
+    #[test]
+    fn gitignore_is_empty_outside_a_repository() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "*\n").unwrap();
+
+        let ignore = GitIgnore::load(temp_dir.path(), None);
+        assert!(!ignore.is_ignored(&temp_dir.path().join("a.txt"), false));
+        assert_eq!(
+            core_excludes_file("[user]\nname = a\n[core]\n  excludesFile = \"~/.ignore\"\n"),
+            Some("~/.ignore".to_string())
+        );
+    }
+}
@@ -0,0 +1,106 @@
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    match_from(&pattern, &text)
+}
+
+fn match_from(pattern: &[char], text: &[char]) -> bool {
+    let mut p = 0;
+    let mut t = 0;
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() {
+            match pattern[p] {
+                '*' => {
+                    backtrack = Some((p, t));
+                    p += 1;
+                    continue;
+                }
+                '?' => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                '[' => {
+                    if let Some((matched, next)) = match_class(&pattern[p..], text[t]) {
+                        if matched {
+                            p += next;
+                            t += 1;
+                            continue;
+                        }
+                    } else if text[t] == '[' {
+                        p += 1;
+                        t += 1;
+                        continue;
+                    }
+                }
+                ch if ch == text[t] => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        let Some((star, matched)) = backtrack else {
+            return false;
+        };
+        p = star + 1;
+        t = matched + 1;
+        backtrack = Some((star, matched + 1));
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+fn match_class(pattern: &[char], ch: char) -> Option<(bool, usize)> {
+    let mut index = 1;
+    let negated = matches!(pattern.get(index), Some('!') | Some('^'));
+    if negated {
+        index += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    while let Some(&current) = pattern.get(index) {
+        if current == ']' && !first {
+            return Some((matched != negated, index + 1));
+        }
+        first = false;
+        if pattern.get(index + 1) == Some(&'-')
+            && let Some(&end) = pattern.get(index + 2)
+            && end != ']'
+        {
+            if current <= ch && ch <= end {
+                matched = true;
+            }
+            index += 3;
+            continue;
+        }
+        if current == ch {
+            matched = true;
+        }
+        index += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_handles_star_and_question() {
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(glob_match("a?c", "abc"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*.rs", "main.rsx"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+    }
+
+    #[test]
+    fn glob_match_handles_classes() {
+        assert!(glob_match("[ab]*", "beta"));
+        assert!(glob_match("file[0-9].txt", "file7.txt"));
+        assert!(!glob_match("[!a]*", "alpha"));
+        assert!(glob_match("[x", "[x"));
+    }
+}
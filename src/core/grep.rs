@@ -0,0 +1,302 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::core::finder::TreeWalk;
+use crate::core::preview::is_binary;
+use crate::core::regex::Regex;
+
+const MAX_GREP_HITS: usize = 1000;
+const MAX_HIT_TEXT_CHARS: usize = 200;
+const MAX_MATCH_LINE_BYTES: usize = 4096;
+
+#[derive(Debug, Clone)]
+pub struct GrepRequest {
+    pub id: u64,
+    pub root: PathBuf,
+    pub pattern: Regex,
+    pub show_hidden: bool,
+    pub hide_ignored: bool,
+    pub max_depth: usize,
+    pub max_file_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepHit {
+    pub path: PathBuf,
+    pub line: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrepEvent {
+    Hit {
+        id: u64,
+        hit: GrepHit,
+    },
+    Finished {
+        id: u64,
+        hits: usize,
+        cancelled: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct GrepWorker {
+    request_tx: Sender<GrepRequest>,
+    event_rx: Receiver<GrepEvent>,
+    active: Arc<AtomicU64>,
+}
+
+impl GrepWorker {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<GrepRequest>();
+        let (event_tx, event_rx) = mpsc::channel::<GrepEvent>();
+        let active = Arc::new(AtomicU64::new(0));
+        let worker_active = Arc::clone(&active);
+
+        thread::spawn(move || {
+            request_rx.into_iter().for_each(|request| {
+                let id = request.id;
+                let is_cancelled = || worker_active.load(Ordering::Relaxed) != id;
+                let mut hits = 0;
+                let completed = grep_tree(&request, &is_cancelled, &mut |hit| {
+                    hits += 1;
+                    let _ = event_tx.send(GrepEvent::Hit { id, hit });
+                });
+                let _ = event_tx.send(GrepEvent::Finished {
+                    id,
+                    hits,
+                    cancelled: !completed,
+                });
+            });
+        });
+
+        Self {
+            request_tx,
+            event_rx,
+            active,
+        }
+    }
+
+    pub fn request(&self, request: GrepRequest) {
+        self.active.store(request.id, Ordering::Relaxed);
+        let _ = self.request_tx.send(request);
+    }
+
+    pub fn cancel(&self) {
+        self.active.store(0, Ordering::Relaxed);
+    }
+
+    pub fn poll(&self) -> Option<GrepEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    #[cfg(test)]
+    pub fn recv_timeout(&self, timeout: std::time::Duration) -> Option<GrepEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+}
+
+fn grep_tree(
+    request: &GrepRequest,
+    is_cancelled: &dyn Fn() -> bool,
+    on_hit: &mut dyn FnMut(GrepHit),
+) -> bool {
+    let mut hits = 0;
+    let mut cancelled = false;
+    let walk = TreeWalk {
+        root: &request.root,
+        show_hidden: request.show_hidden,
+        hide_ignored: request.hide_ignored,
+        max_depth: request.max_depth,
+    };
+    let completed = walk.run(is_cancelled, &mut |path, is_dir| {
+        if is_cancelled() {
+            cancelled = true;
+            return false;
+        }
+        if is_dir {
+            return true;
+        }
+        for hit in grep_file(path, &request.pattern, request.max_file_bytes, is_cancelled) {
+            on_hit(hit);
+            hits += 1;
+            if hits >= MAX_GREP_HITS {
+                return false;
+            }
+        }
+        true
+    });
+    completed && !cancelled
+}
+
+fn grep_file(
+    path: &Path,
+    pattern: &Regex,
+    max_file_bytes: u64,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Vec<GrepHit> {
+    let too_large = std::fs::metadata(path).map_or(true, |metadata| {
+        !metadata.is_file() || metadata.len() > max_file_bytes
+    });
+    if too_large {
+        return Vec::new();
+    }
+    let Ok(bytes) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    if is_binary(&bytes) {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .enumerate()
+        .take_while(|_| !is_cancelled())
+        .filter(|(_, line)| pattern.is_match(match_prefix(line)))
+        .map(|(index, line)| GrepHit {
+            path: path.to_path_buf(),
+            line: index + 1,
+            text: line.trim().chars().take(MAX_HIT_TEXT_CHARS).collect(),
+        })
+        .collect()
+}
+
+fn match_prefix(line: &str) -> &str {
+    if line.len() <= MAX_MATCH_LINE_BYTES {
+        return line;
+    }
+    let end = (0..=MAX_MATCH_LINE_BYTES)
+        .rev()
+        .find(|index| line.is_char_boundary(*index))
+        .unwrap_or(0);
+    &line[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    fn request(root: &Path, pattern: &str) -> GrepRequest {
+        GrepRequest {
+            id: 1,
+            root: root.to_path_buf(),
+            pattern: Regex::new(pattern).unwrap(),
+            show_hidden: false,
+            hide_ignored: false,
+            max_depth: 8,
+            max_file_bytes: 1024,
+        }
+    }
+
+    fn collect(request: &GrepRequest) -> Vec<(String, usize, String)> {
+        let mut hits = Vec::new();
+        grep_tree(request, &|| false, &mut |hit| {
+            let name = hit
+                .path
+                .strip_prefix(&request.root)
+                .unwrap()
+                .display()
+                .to_string();
+            hits.push((name, hit.line, hit.text));
+        });
+        hits
+    }
+
+    #[test]
+    fn grep_tree_reports_matching_lines_and_skips_binary_and_large_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(
+            root.join("src").join("main.rs"),
+            "fn main() {\n    todo!();\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("notes.txt"), "TODO later\ntodo now\n").unwrap();
+        fs::write(root.join("blob.bin"), b"todo\0").unwrap();
+        fs::write(root.join("big.txt"), "todo\n".repeat(400)).unwrap();
+
+        assert_eq!(
+            collect(&request(root, "todo")),
+            vec![
+                ("notes.txt".to_string(), 2, "todo now".to_string()),
+                (
+                    Path::new("src").join("main.rs").display().to_string(),
+                    2,
+                    "todo!();".to_string()
+                ),
+            ]
+        );
+        assert_eq!(collect(&request(root, "(?i)^todo")).len(), 2);
+    }
+
+    #[test]
+    fn grep_tree_stops_when_cancelled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "todo\n").unwrap();
+        let mut hits = Vec::new();
+
+        let completed = grep_tree(&request(temp_dir.path(), "todo"), &|| true, &mut |hit| {
+            hits.push(hit)
+        });
+
+        assert!(!completed);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn grep_file_matches_only_the_prefix_of_long_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("bundle.min.js");
+        let line = format!("{}needle", "é".repeat(100_000));
+        fs::write(&path, format!("{line}\nneedle\n")).unwrap();
+        let pattern = Regex::new(".*needle").unwrap();
+
+        let hits = grep_file(&path, &pattern, u64::MAX, &|| false);
+        assert_eq!(hits.iter().map(|hit| hit.line).collect::<Vec<_>>(), [2]);
+        assert!(grep_file(&path, &pattern, u64::MAX, &|| true).is_empty());
+        assert!(match_prefix(&line).len() <= MAX_MATCH_LINE_BYTES);
+    }
+
+    #[test]
+    fn grep_worker_streams_hits_then_finishes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "alpha\nbeta\n").unwrap();
+        let worker = GrepWorker::new();
+
+        worker.request(request(temp_dir.path(), "beta"));
+
+        let hit = worker
+            .event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(
+            hit,
+            GrepEvent::Hit {
+                id: 1,
+                hit: GrepHit {
+                    path: temp_dir.path().join("notes.txt"),
+                    line: 2,
+                    text: "beta".to_string(),
+                },
+            }
+        );
+        let finished = worker
+            .event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(
+            finished,
+            GrepEvent::Finished {
+                id: 1,
+                hits: 1,
+                cancelled: false,
+            }
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+const MAX_HISTORY_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub path: PathBuf,
+    pub focus: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NavHistory {
+    pub back: Vec<HistoryEntry>,
+    pub forward: Vec<HistoryEntry>,
+}
+
+impl NavHistory {
+    pub fn visit(&mut self, from: HistoryEntry) {
+        push_bounded(&mut self.back, from);
+        self.forward.clear();
+    }
+
+    pub fn back(&mut self, current: HistoryEntry) -> Option<HistoryEntry> {
+        let entry = pop_existing(&mut self.back)?;
+        push_bounded(&mut self.forward, current);
+        Some(entry)
+    }
+
+    pub fn forward(&mut self, current: HistoryEntry) -> Option<HistoryEntry> {
+        let entry = pop_existing(&mut self.forward)?;
+        push_bounded(&mut self.back, current);
+        Some(entry)
+    }
+}
+
+fn pop_existing(stack: &mut Vec<HistoryEntry>) -> Option<HistoryEntry> {
+    while let Some(entry) = stack.pop() {
+        if entry.path.is_dir() {
+            return Some(entry);
+        }
+    }
+    None
+}
+
+fn push_bounded(stack: &mut Vec<HistoryEntry>, entry: HistoryEntry) {
+    stack.push(entry);
+    if stack.len() > MAX_HISTORY_ENTRIES {
+        let overflow = stack.len() - MAX_HISTORY_ENTRIES;
+        stack.drain(..overflow);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn entry(path: &Path) -> HistoryEntry {
+        HistoryEntry {
+            path: path.to_path_buf(),
+            focus: None,
+        }
+    }
+
+    #[test]
+    fn back_and_forward_walk_the_stacks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let [a, b, c] = ["a", "b", "c"].map(|name| temp_dir.path().join(name));
+        for dir in [&a, &b, &c] {
+            std::fs::create_dir(dir).unwrap();
+        }
+        let mut history = NavHistory::default();
+        history.visit(entry(&a));
+        history.visit(entry(&b));
+
+        assert_eq!(history.back(entry(&c)), Some(entry(&b)));
+        assert_eq!(history.back(entry(&b)), Some(entry(&a)));
+        assert_eq!(history.back(entry(&a)), None);
+        assert_eq!(history.forward(entry(&a)), Some(entry(&b)));
+
+        history.visit(entry(&b));
+        assert!(history.forward.is_empty());
+        assert_eq!(history.forward(entry(&c)), None);
+    }
+
+    #[test]
+    fn back_skips_directories_that_no_longer_exist() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let kept = temp_dir.path().join("kept");
+        std::fs::create_dir(&kept).unwrap();
+        let mut history = NavHistory::default();
+        history.visit(entry(&kept));
+        history.visit(entry(&temp_dir.path().join("gone")));
+
+        assert_eq!(history.back(entry(temp_dir.path())), Some(entry(&kept)));
+        assert!(history.back.is_empty());
+        assert_eq!(history.forward, vec![entry(temp_dir.path())]);
+    }
+
+    #[test]
+    fn visit_keeps_a_bounded_stack() {
+        let mut history = NavHistory::default();
+        for index in 0..MAX_HISTORY_ENTRIES + 5 {
+            history.visit(entry(Path::new(&format!("/{index}"))));
+        }
+
+        assert_eq!(history.back.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(history.back[0], entry(Path::new("/5")));
+    }
+}
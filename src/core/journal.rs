@@ -0,0 +1,1098 @@
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::config::config_root;
+use crate::core::bulk_rename::{is_cycle_temp, order_moves};
+use crate::core::{FileOpError, TrashStore, TrashedItem, copy_path, move_path};
+
+const JOURNAL_FILE: &str = "journal.json";
+const MAX_JOURNAL_ENTRIES: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum JournalError {
+    #[error("nothing to {0}")]
+    Empty(&'static str),
+    #[error("modified since: {}", .0.display())]
+    Modified(PathBuf),
+    #[error("missing: {}", .0.display())]
+    Missing(PathBuf),
+    #[error("already exists: {}", .0.display())]
+    Occupied(PathBuf),
+    #[error("directory not empty: {}", .0.display())]
+    NotEmpty(PathBuf),
+    #[error("trash unavailable")]
+    TrashUnavailable,
+    #[error("{0}")]
+    FileOp(#[from] FileOpError),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub len: u64,
+    pub modified_ns: u64,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = path.symlink_metadata().ok()?;
+        let modified_ns = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(0);
+        Some(Self {
+            len: if metadata.is_dir() { 0 } else { metadata.len() },
+            modified_ns,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalPath {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    pub stamp: Option<FileStamp>,
+    pub replaced: Option<TrashedItem>,
+}
+
+impl JournalPath {
+    pub fn new(source: PathBuf, target: PathBuf) -> Self {
+        let stamp = FileStamp::of(&target);
+        Self {
+            source,
+            target,
+            stamp,
+            replaced: None,
+        }
+    }
+
+    pub fn replacing(source: PathBuf, target: PathBuf, replaced: Option<TrashedItem>) -> Self {
+        Self {
+            replaced,
+            ..Self::new(source, target)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalOp {
+    Copy { items: Vec<JournalPath> },
+    Move { items: Vec<JournalPath> },
+    Mkdir { path: PathBuf, parents: usize },
+    Trash { items: Vec<TrashedItem> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub label: String,
+    pub op: JournalOp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Journal {
+    path: Option<PathBuf>,
+    undo: Vec<JournalEntry>,
+    redo: Vec<JournalEntry>,
+}
+
+impl Journal {
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(JOURNAL_FILE);
+        let (undo, redo) = std::fs::read_to_string(&path)
+            .ok()
+            .map(|content| parse_journal(&content))
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            undo,
+            redo,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        config_root()
+            .map(|root| Self::load(&root))
+            .unwrap_or_else(Self::in_memory)
+    }
+
+    pub fn record(&mut self, label: String, op: JournalOp) -> std::io::Result<()> {
+        self.undo.push(JournalEntry { label, op });
+        if self.undo.len() > MAX_JOURNAL_ENTRIES {
+            let overflow = self.undo.len() - MAX_JOURNAL_ENTRIES;
+            self.undo.drain(..overflow);
+        }
+        self.redo.clear();
+        self.save()
+    }
+
+    pub fn undo(&mut self, trash: Option<&TrashStore>) -> Result<String, JournalError> {
+        let entry = self.undo.pop().ok_or(JournalError::Empty("undo"))?;
+        let label = entry.label.clone();
+        match undo_op(&entry.op, trash) {
+            Ok(op) => {
+                self.redo.push(JournalEntry {
+                    label: entry.label,
+                    op,
+                });
+                self.save()?;
+                Ok(label)
+            }
+            Err(partial) => {
+                let error = partial.settle(&label, &mut self.undo, &mut self.redo);
+                self.save()?;
+                Err(error)
+            }
+        }
+    }
+
+    pub fn redo(&mut self, trash: Option<&TrashStore>) -> Result<String, JournalError> {
+        let entry = self.redo.pop().ok_or(JournalError::Empty("redo"))?;
+        let label = entry.label.clone();
+        match redo_op(&entry.op, trash) {
+            Ok(op) => {
+                self.undo.push(JournalEntry {
+                    label: entry.label,
+                    op,
+                });
+                self.save()?;
+                Ok(label)
+            }
+            Err(partial) => {
+                let error = partial.settle(&label, &mut self.redo, &mut self.undo);
+                self.save()?;
+                Err(error)
+            }
+        }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, build_journal_payload(&self.undo, &self.redo))?;
+        std::fs::rename(&tmp_path, path)
+    }
+}
+
+/// 途中で失敗した操作。適用し終えた部分と、まだ適用していない部分に分ける。
+struct Partial {
+    error: JournalError,
+    done: Option<JournalOp>,
+    remaining: Option<JournalOp>,
+}
+
+impl Partial {
+    fn untouched(op: &JournalOp) -> impl FnOnce(JournalError) -> Self + '_ {
+        move |error| Self {
+            error,
+            done: None,
+            remaining: Some(op.clone()),
+        }
+    }
+
+    /// 残りは元のスタックへ戻して再試行できるようにし、済んだ部分は逆のスタックへ積む。
+    fn settle(
+        self,
+        label: &str,
+        origin: &mut Vec<JournalEntry>,
+        opposite: &mut Vec<JournalEntry>,
+    ) -> JournalError {
+        let entry = |op| JournalEntry {
+            label: label.to_string(),
+            op,
+        };
+        origin.extend(self.remaining.map(entry));
+        opposite.extend(self.done.map(entry));
+        self.error
+    }
+}
+
+fn path_op(make: fn(Vec<JournalPath>) -> JournalOp, items: &[JournalPath]) -> Option<JournalOp> {
+    (!items.is_empty()).then(|| make(items.to_vec()))
+}
+
+fn trash_op(items: &[TrashedItem]) -> Option<JournalOp> {
+    (!items.is_empty()).then(|| JournalOp::Trash {
+        items: items.to_vec(),
+    })
+}
+
+fn copy_op(items: Vec<JournalPath>) -> JournalOp {
+    JournalOp::Copy { items }
+}
+
+fn move_op(items: Vec<JournalPath>) -> JournalOp {
+    JournalOp::Move { items }
+}
+
+fn undo_op(op: &JournalOp, trash: Option<&TrashStore>) -> Result<JournalOp, Partial> {
+    match op {
+        JournalOp::Copy { items } => {
+            for item in items {
+                ensure_unchanged(item).map_err(Partial::untouched(op))?;
+            }
+            let store = trash
+                .ok_or(JournalError::TrashUnavailable)
+                .map_err(Partial::untouched(op))?;
+            ensure_replaced_in_trash(items, trash).map_err(Partial::untouched(op))?;
+            for (index, item) in items.iter().enumerate() {
+                if let Err(error) = store.trash(&item.target) {
+                    return Err(Partial {
+                        error: error.into(),
+                        done: path_op(copy_op, &items[..index]),
+                        remaining: path_op(copy_op, &items[index..]),
+                    });
+                }
+                if let Some(replaced) = item.replaced.as_ref()
+                    && let Err(error) = store.restore(replaced)
+                {
+                    return Err(Partial {
+                        error: error.into(),
+                        done: path_op(copy_op, &items[..=index]),
+                        remaining: path_op(copy_op, &items[index + 1..]),
+                    });
+                }
+            }
+            Ok(op.clone())
+        }
+        JournalOp::Move { items } => {
+            let reversed = items
+                .iter()
+                .map(|item| JournalPath {
+                    source: item.target.clone(),
+                    target: item.source.clone(),
+                    stamp: item.stamp,
+                    replaced: None,
+                })
+                .collect::<Vec<_>>();
+            for item in items {
+                ensure_unchanged(item).map_err(Partial::untouched(op))?;
+            }
+            ensure_replaced_in_trash(items, trash).map_err(Partial::untouched(op))?;
+            let (moved, failure) = match apply_moves(&reversed) {
+                Ok(moved) => (moved, None),
+                Err(failure) => (failure.moved, Some((failure.error, failure.remaining))),
+            };
+            let original = |moved: &JournalPath| {
+                items
+                    .iter()
+                    .find(|original| original.target == moved.source)
+            };
+            let done = moved
+                .iter()
+                .map(|item| {
+                    let replaced = original(item).and_then(|original| original.replaced.clone());
+                    JournalPath::replacing(item.target.clone(), item.source.clone(), replaced)
+                })
+                .collect::<Vec<_>>();
+            let restored = items
+                .iter()
+                .filter(|item| done.iter().any(|done| done.target == item.target))
+                .cloned()
+                .collect::<Vec<_>>();
+            let remaining = failure.as_ref().map(|(_, remaining)| {
+                remaining
+                    .iter()
+                    .filter_map(|item| {
+                        let original = items
+                            .iter()
+                            .find(|original| original.source == item.target)?;
+                        Some(JournalPath {
+                            target: item.source.clone(),
+                            ..original.clone()
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            });
+            let remaining_op = remaining
+                .as_deref()
+                .and_then(|items| path_op(move_op, items));
+            if let Err(error) = restore_replaced(&restored, trash) {
+                return Err(Partial {
+                    error,
+                    done: path_op(move_op, &done),
+                    remaining: remaining_op,
+                });
+            }
+            match failure {
+                None => Ok(move_op(done)),
+                Some((error, _)) => Err(Partial {
+                    error,
+                    done: path_op(move_op, &done),
+                    remaining: remaining_op,
+                }),
+            }
+        }
+        JournalOp::Mkdir { path, parents } => {
+            let created = path.ancestors().take(parents + 1).collect::<Vec<_>>();
+            let mut expected: Option<&Path> = None;
+            for dir in &created {
+                let children = std::fs::read_dir(dir)
+                    .map_err(|_| JournalError::Missing(dir.to_path_buf()))
+                    .and_then(|entries| {
+                        entries
+                            .map(|entry| entry.map(|entry| entry.path()))
+                            .collect::<Result<Vec<_>, _>>()
+                            .map_err(JournalError::from)
+                    })
+                    .map_err(Partial::untouched(op))?;
+                let only_expected = match (children.as_slice(), expected) {
+                    ([], None) => true,
+                    ([child], Some(expected)) => child == expected,
+                    _ => false,
+                };
+                if !only_expected {
+                    return Err(Partial::untouched(op)(JournalError::NotEmpty(
+                        dir.to_path_buf(),
+                    )));
+                }
+                expected = Some(dir);
+            }
+            for (index, dir) in created.iter().enumerate() {
+                if let Err(error) = std::fs::remove_dir(dir) {
+                    return Err(Partial {
+                        error: error.into(),
+                        done: (index > 0).then(|| JournalOp::Mkdir {
+                            path: path.clone(),
+                            parents: index - 1,
+                        }),
+                        remaining: Some(JournalOp::Mkdir {
+                            path: dir.to_path_buf(),
+                            parents: parents - index,
+                        }),
+                    });
+                }
+            }
+            Ok(op.clone())
+        }
+        JournalOp::Trash { items } => {
+            let store = trash
+                .ok_or(JournalError::TrashUnavailable)
+                .map_err(Partial::untouched(op))?;
+            for item in items {
+                if !store.contains(item) {
+                    return Err(Partial::untouched(op)(JournalError::Missing(
+                        item.original_path.clone(),
+                    )));
+                }
+                if item.original_path.symlink_metadata().is_ok() {
+                    return Err(Partial::untouched(op)(JournalError::Occupied(
+                        item.original_path.clone(),
+                    )));
+                }
+            }
+            for (index, item) in items.iter().enumerate() {
+                if let Err(error) = store.restore(item) {
+                    return Err(Partial {
+                        error: error.into(),
+                        done: trash_op(&items[..index]),
+                        remaining: trash_op(&items[index..]),
+                    });
+                }
+            }
+            Ok(op.clone())
+        }
+    }
+}
+
+fn redo_op(op: &JournalOp, trash: Option<&TrashStore>) -> Result<JournalOp, Partial> {
+    match op {
+        JournalOp::Copy { items } => {
+            let vacating = replaced_targets(items, trash).map_err(Partial::untouched(op))?;
+            for item in items {
+                ensure_movable(&item.source, &item.target, &vacating)
+                    .map_err(Partial::untouched(op))?;
+            }
+            let mut done = Vec::new();
+            for (index, item) in items.iter().enumerate() {
+                let mut item = item.clone();
+                if let Err(error) = redo_copy_item(&mut item, trash) {
+                    let mut remaining = vec![item];
+                    remaining.extend_from_slice(&items[index + 1..]);
+                    return Err(Partial {
+                        error,
+                        done: path_op(copy_op, &done),
+                        remaining: path_op(copy_op, &remaining),
+                    });
+                }
+                done.push(JournalPath::replacing(
+                    item.source,
+                    item.target,
+                    item.replaced,
+                ));
+            }
+            Ok(copy_op(done))
+        }
+        JournalOp::Move { items } => {
+            let mut vacating = replaced_targets(items, trash).map_err(Partial::untouched(op))?;
+            vacating.extend(items.iter().map(|item| item.source.clone()));
+            for item in items {
+                ensure_movable(&item.source, &item.target, &vacating)
+                    .map_err(Partial::untouched(op))?;
+            }
+            let items = trash_replaced(items, trash).map_err(|(error, items)| Partial {
+                error,
+                done: None,
+                remaining: Some(move_op(items)),
+            })?;
+            let (moved, failure) = match apply_moves(&items) {
+                Ok(moved) => (moved, None),
+                Err(failure) => (failure.moved, Some((failure.error, failure.remaining))),
+            };
+            let done = moved
+                .into_iter()
+                .map(|item| {
+                    let replaced = items
+                        .iter()
+                        .find(|original| original.target == item.target)
+                        .and_then(|original| original.replaced.clone());
+                    JournalPath::replacing(item.source, item.target, replaced)
+                })
+                .collect::<Vec<_>>();
+            match failure {
+                None => Ok(move_op(done)),
+                Some((error, remaining)) => Err(Partial {
+                    error,
+                    done: path_op(move_op, &done),
+                    remaining: path_op(move_op, &remaining),
+                }),
+            }
+        }
+        JournalOp::Mkdir { path, parents } => {
+            let top = path.ancestors().nth(*parents).unwrap_or(path);
+            if top.symlink_metadata().is_ok() {
+                return Err(Partial::untouched(op)(JournalError::Occupied(
+                    top.to_path_buf(),
+                )));
+            }
+            std::fs::create_dir_all(path).map_err(|error| Partial::untouched(op)(error.into()))?;
+            Ok(op.clone())
+        }
+        JournalOp::Trash { items } => {
+            let store = trash
+                .ok_or(JournalError::TrashUnavailable)
+                .map_err(Partial::untouched(op))?;
+            for item in items {
+                if item.original_path.symlink_metadata().is_err() {
+                    return Err(Partial::untouched(op)(JournalError::Missing(
+                        item.original_path.clone(),
+                    )));
+                }
+            }
+            let mut trashed = Vec::new();
+            for (index, item) in items.iter().enumerate() {
+                match store.trash(&item.original_path) {
+                    Ok(item) => trashed.push(item),
+                    Err(error) => {
+                        return Err(Partial {
+                            error: error.into(),
+                            done: trash_op(&trashed),
+                            remaining: trash_op(&items[index..]),
+                        });
+                    }
+                }
+            }
+            Ok(JournalOp::Trash { items: trashed })
+        }
+    }
+}
+
+/// 上書きしていた項目を再びゴミ箱へ送ってからコピーし直す。
+fn redo_copy_item(item: &mut JournalPath, trash: Option<&TrashStore>) -> Result<(), JournalError> {
+    if item.replaced.is_some() && item.target.symlink_metadata().is_ok() {
+        let store = trash.ok_or(JournalError::TrashUnavailable)?;
+        item.replaced = Some(store.trash(&item.target)?);
+    }
+    copy_path(&item.source, &item.target)?;
+    Ok(())
+}
+
+/// 途中で止まった移動。`remaining` の `source` は各項目が今ある場所を指す。
+struct MoveFailure {
+    error: JournalError,
+    moved: Vec<JournalPath>,
+    remaining: Vec<JournalPath>,
+}
+
+fn apply_moves(items: &[JournalPath]) -> Result<Vec<JournalPath>, MoveFailure> {
+    let sources = items
+        .iter()
+        .map(|item| item.source.clone())
+        .collect::<Vec<_>>();
+    for item in items {
+        if let Err(error) = ensure_movable(&item.source, &item.target, &sources) {
+            return Err(MoveFailure {
+                error,
+                moved: Vec::new(),
+                remaining: items.to_vec(),
+            });
+        }
+    }
+    let moves = items
+        .iter()
+        .map(|item| (item.source.clone(), item.target.clone()))
+        .collect::<Vec<_>>();
+    let (steps, _) = order_moves(&moves);
+    let mut locations = sources;
+    for (from, to) in &steps {
+        if let Err(error) = move_path(from, to) {
+            let (moved, remaining) = items
+                .iter()
+                .zip(&locations)
+                .partition::<Vec<_>, _>(|(item, location)| **location == item.target);
+            return Err(MoveFailure {
+                error: error.into(),
+                moved: moved
+                    .into_iter()
+                    .map(|(item, _)| JournalPath::new(item.source.clone(), item.target.clone()))
+                    .collect(),
+                remaining: remaining
+                    .into_iter()
+                    .map(|(item, location)| JournalPath {
+                        source: location.clone(),
+                        ..item.clone()
+                    })
+                    .collect(),
+            });
+        }
+        if let Some(location) = locations.iter_mut().find(|location| *location == from) {
+            *location = to.clone();
+        }
+    }
+    Ok(steps
+        .into_iter()
+        .filter(|(_, to)| !is_cycle_temp(to))
+        .map(|(_, to)| {
+            let source = items
+                .iter()
+                .find(|item| item.target == to)
+                .map(|item| item.source.clone())
+                .unwrap_or_default();
+            JournalPath::new(source, to)
+        })
+        .collect())
+}
+
+fn ensure_replaced_in_trash(
+    items: &[JournalPath],
+    trash: Option<&TrashStore>,
+) -> Result<(), JournalError> {
+    for replaced in items.iter().filter_map(|item| item.replaced.as_ref()) {
+        let store = trash.ok_or(JournalError::TrashUnavailable)?;
+        if !store.contains(replaced) {
+            return Err(JournalError::Missing(replaced.original_path.clone()));
+        }
+    }
+    Ok(())
+}
+
+fn restore_replaced(items: &[JournalPath], trash: Option<&TrashStore>) -> Result<(), JournalError> {
+    for replaced in items.iter().filter_map(|item| item.replaced.as_ref()) {
+        trash
+            .ok_or(JournalError::TrashUnavailable)?
+            .restore(replaced)?;
+    }
+    Ok(())
+}
+
+fn replaced_targets(
+    items: &[JournalPath],
+    trash: Option<&TrashStore>,
+) -> Result<Vec<PathBuf>, JournalError> {
+    let targets = items
+        .iter()
+        .filter(|item| item.replaced.is_some())
+        .map(|item| item.target.clone())
+        .collect::<Vec<_>>();
+    if !targets.is_empty() && trash.is_none() {
+        return Err(JournalError::TrashUnavailable);
+    }
+    Ok(targets)
+}
+
+/// 上書きしていた項目を再びゴミ箱へ送り、新しい記録に差し替えた項目を返す。
+/// 失敗したときは、それまでに差し替えた分を含む項目を返す。
+fn trash_replaced(
+    items: &[JournalPath],
+    trash: Option<&TrashStore>,
+) -> Result<Vec<JournalPath>, (JournalError, Vec<JournalPath>)> {
+    let mut items = items.to_vec();
+    for index in 0..items.len() {
+        let item = &items[index];
+        if item.replaced.is_none() || item.target.symlink_metadata().is_err() {
+            continue;
+        }
+        let trashed = match trash {
+            Some(store) => store.trash(&item.target).map_err(JournalError::from),
+            None => Err(JournalError::TrashUnavailable),
+        };
+        match trashed {
+            Ok(trashed) => items[index].replaced = Some(trashed),
+            Err(error) => return Err((error, items)),
+        }
+    }
+    Ok(items)
+}
+
+fn ensure_unchanged(item: &JournalPath) -> Result<(), JournalError> {
+    match FileStamp::of(&item.target) {
+        None => Err(JournalError::Missing(item.target.clone())),
+        Some(stamp) if Some(stamp) != item.stamp => {
+            Err(JournalError::Modified(item.target.clone()))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+fn ensure_movable(source: &Path, target: &Path, vacating: &[PathBuf]) -> Result<(), JournalError> {
+    if source.symlink_metadata().is_err() {
+        return Err(JournalError::Missing(source.to_path_buf()));
+    }
+    if target.symlink_metadata().is_ok() && !vacating.iter().any(|path| path == target) {
+        return Err(JournalError::Occupied(target.to_path_buf()));
+    }
+    Ok(())
+}
+
+fn build_journal_payload(undo: &[JournalEntry], redo: &[JournalEntry]) -> String {
+    serde_json::json!({
+        "version": 1,
+        "undo": undo.iter().map(entry_to_json).collect::<Vec<_>>(),
+        "redo": redo.iter().map(entry_to_json).collect::<Vec<_>>(),
+    })
+    .to_string()
+}
+
+fn entry_to_json(entry: &JournalEntry) -> Value {
+    let path_items = |items: &[JournalPath]| {
+        items
+            .iter()
+            .map(|item| {
+                serde_json::json!({
+                    "source": item.source.to_string_lossy(),
+                    "target": item.target.to_string_lossy(),
+                    "len": item.stamp.map(|stamp| stamp.len),
+                    "modified_ns": item.stamp.map(|stamp| stamp.modified_ns),
+                    "replaced": item.replaced.as_ref().map(trashed_to_json),
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    match &entry.op {
+        JournalOp::Copy { items } => serde_json::json!({
+            "label": entry.label,
+            "op": "copy",
+            "items": path_items(items),
+        }),
+        JournalOp::Move { items } => serde_json::json!({
+            "label": entry.label,
+            "op": "move",
+            "items": path_items(items),
+        }),
+        JournalOp::Mkdir { path, parents } => serde_json::json!({
+            "label": entry.label,
+            "op": "mkdir",
+            "path": path.to_string_lossy(),
+            "parents": parents,
+        }),
+        JournalOp::Trash { items } => serde_json::json!({
+            "label": entry.label,
+            "op": "trash",
+            "items": items.iter().map(trashed_to_json).collect::<Vec<_>>(),
+        }),
+    }
+}
+
+fn trashed_to_json(item: &TrashedItem) -> Value {
+    serde_json::json!({
+        "name": item.name,
+        "original": item.original_path.to_string_lossy(),
+        "deleted_at": item.deleted_at,
+        "trash_dir": item.trash_dir.as_ref().map(|dir| dir.to_string_lossy()),
+    })
+}
+
+fn trashed_from_json(value: &Value) -> Option<TrashedItem> {
+    let string = |key: &str| value.get(key)?.as_str().map(str::to_string);
+    Some(TrashedItem {
+        name: string("name")?,
+        original_path: PathBuf::from(string("original")?),
+        deleted_at: string("deleted_at").unwrap_or_default(),
+        trash_dir: string("trash_dir").map(PathBuf::from),
+    })
+}
+
+fn parse_journal(content: &str) -> (Vec<JournalEntry>, Vec<JournalEntry>) {
+    let Ok(value) = serde_json::from_str::<Value>(content) else {
+        return (Vec::new(), Vec::new());
+    };
+    let entries = |key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|entries| entries.iter().filter_map(entry_from_json).collect())
+            .unwrap_or_default()
+    };
+    (entries("undo"), entries("redo"))
+}
+
+fn entry_from_json(value: &Value) -> Option<JournalEntry> {
+    let label = value.get("label")?.as_str()?.to_string();
+    let string = |value: &Value, key: &str| value.get(key)?.as_str().map(str::to_string);
+    let path_items = || -> Option<Vec<JournalPath>> {
+        value
+            .get("items")?
+            .as_array()?
+            .iter()
+            .map(|item| {
+                let len = item.get("len").and_then(Value::as_u64);
+                let modified_ns = item.get("modified_ns").and_then(Value::as_u64);
+                Some(JournalPath {
+                    source: PathBuf::from(string(item, "source")?),
+                    target: PathBuf::from(string(item, "target")?),
+                    stamp: len
+                        .zip(modified_ns)
+                        .map(|(len, modified_ns)| FileStamp { len, modified_ns }),
+                    replaced: item.get("replaced").and_then(trashed_from_json),
+                })
+            })
+            .collect()
+    };
+    let op = match value.get("op")?.as_str()? {
+        "copy" => JournalOp::Copy {
+            items: path_items()?,
+        },
+        "move" => JournalOp::Move {
+            items: path_items()?,
+        },
+        "mkdir" => JournalOp::Mkdir {
+            path: PathBuf::from(string(value, "path")?),
+            parents: value
+                .get("parents")
+                .and_then(Value::as_u64)
+                .unwrap_or_default() as usize,
+        },
+        "trash" => JournalOp::Trash {
+            items: value
+                .get("items")?
+                .as_array()?
+                .iter()
+                .map(trashed_from_json)
+                .collect::<Option<Vec<_>>>()?,
+        },
+        _ => return None,
+    };
+    Some(JournalEntry { label, op })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn undo_and_redo_move_restore_paths() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("a.txt");
+        let target = temp_dir.path().join("b.txt");
+        fs::write(&source, "a").unwrap();
+        fs::rename(&source, &target).unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record(
+                "rename a.txt -> b.txt".to_string(),
+                JournalOp::Move {
+                    items: vec![JournalPath::new(source.clone(), target.clone())],
+                },
+            )
+            .unwrap();
+
+        assert_eq!(journal.undo(None).unwrap(), "rename a.txt -> b.txt");
+        assert!(source.exists());
+        assert!(!target.exists());
+
+        journal.redo(None).unwrap();
+        assert!(target.exists());
+        assert_eq!(journal.undo.len(), 1);
+        assert_eq!(journal.redo.len(), 0);
+    }
+
+    #[test]
+    fn undo_failing_on_second_item_keeps_the_rest_on_the_undo_stack() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let first_dir = temp_dir.path().join("first");
+        let second_dir = temp_dir.path().join("second");
+        let dest = temp_dir.path().join("dest");
+        for dir in [&first_dir, &second_dir, &dest] {
+            fs::create_dir(dir).unwrap();
+        }
+        let first = (first_dir.join("a.txt"), dest.join("a.txt"));
+        let second = (second_dir.join("b.txt"), dest.join("b.txt"));
+        fs::write(&first.1, "a").unwrap();
+        fs::write(&second.1, "b").unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record(
+                "move 2 items".to_string(),
+                JournalOp::Move {
+                    items: vec![
+                        JournalPath::new(first.0.clone(), first.1.clone()),
+                        JournalPath::new(second.0.clone(), second.1.clone()),
+                    ],
+                },
+            )
+            .unwrap();
+        fs::remove_dir(&second_dir).unwrap();
+
+        assert!(journal.undo(None).is_err());
+        assert!(first.0.exists());
+        assert!(second.1.exists());
+        let moved = |entry: &JournalEntry| match &entry.op {
+            JournalOp::Move { items } => items
+                .iter()
+                .map(|item| (item.source.clone(), item.target.clone()))
+                .collect::<Vec<_>>(),
+            op => panic!("unexpected op: {op:?}"),
+        };
+        assert_eq!(moved(&journal.undo[0]), vec![second.clone()]);
+        assert_eq!(moved(&journal.redo[0]), vec![first.clone()]);
+
+        fs::create_dir(&second_dir).unwrap();
+        journal.undo(None).unwrap();
+        assert!(second.0.exists());
+        assert!(journal.undo.is_empty());
+        assert_eq!(journal.redo.len(), 2);
+    }
+
+    #[test]
+    fn undo_copy_refuses_modified_target() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("a.txt");
+        let target = temp_dir.path().join("copy.txt");
+        fs::write(&source, "a").unwrap();
+        fs::write(&target, "a").unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record(
+                "copy".to_string(),
+                JournalOp::Copy {
+                    items: vec![JournalPath::new(source, target.clone())],
+                },
+            )
+            .unwrap();
+        fs::write(&target, "changed content").unwrap();
+
+        let result = journal.undo(None);
+
+        assert!(matches!(result, Err(JournalError::Modified(_))));
+        assert!(target.exists());
+        assert_eq!(journal.undo.len(), 1);
+    }
+
+    #[test]
+    fn undo_copy_moves_the_copy_to_trash() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("tree");
+        let target = temp_dir.path().join("copy");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("nested").join("a.txt"), "a").unwrap();
+        copy_path(&source, &target).unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record(
+                "copy".to_string(),
+                JournalOp::Copy {
+                    items: vec![JournalPath::new(source, target.clone())],
+                },
+            )
+            .unwrap();
+        fs::write(target.join("nested").join("a.txt"), "edited").unwrap();
+
+        assert!(matches!(
+            journal.undo(None),
+            Err(JournalError::TrashUnavailable)
+        ));
+        assert!(target.exists());
+        journal.undo(Some(&store)).unwrap();
+
+        assert!(!target.exists());
+        let trashed = store.list();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("Trash/files/copy/nested/a.txt")).unwrap(),
+            "edited"
+        );
+    }
+
+    #[test]
+    fn undo_overwriting_copy_restores_replaced_entry() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("a.txt");
+        let target = temp_dir.path().join("dest.txt");
+        fs::write(&source, "new").unwrap();
+        fs::write(&target, "old").unwrap();
+        let replaced = store.trash(&target).unwrap();
+        fs::copy(&source, &target).unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record(
+                "copy".to_string(),
+                JournalOp::Copy {
+                    items: vec![JournalPath::replacing(
+                        source,
+                        target.clone(),
+                        Some(replaced),
+                    )],
+                },
+            )
+            .unwrap();
+
+        assert!(matches!(
+            journal.undo(None),
+            Err(JournalError::TrashUnavailable)
+        ));
+        journal.undo(Some(&store)).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "old");
+        assert_eq!(store.list().len(), 1);
+
+        journal.redo(Some(&store)).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(store.list().len(), 2);
+    }
+
+    #[test]
+    fn undo_swap_of_two_names_uses_temp_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let a = temp_dir.path().join("a");
+        let b = temp_dir.path().join("b");
+        fs::write(&a, "was b").unwrap();
+        fs::write(&b, "was a").unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record(
+                "bulkrename".to_string(),
+                JournalOp::Move {
+                    items: vec![
+                        JournalPath::new(a.clone(), b.clone()),
+                        JournalPath::new(b.clone(), a.clone()),
+                    ],
+                },
+            )
+            .unwrap();
+
+        journal.undo(None).unwrap();
+
+        assert_eq!(fs::read_to_string(&a).unwrap(), "was a");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "was b");
+    }
+
+    #[test]
+    fn undo_trash_restores_and_redo_trashes_again() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("note.txt");
+        fs::write(&source, "data").unwrap();
+        let item = store.trash(&source).unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record("trash".to_string(), JournalOp::Trash { items: vec![item] })
+            .unwrap();
+
+        assert!(matches!(
+            journal.undo(None),
+            Err(JournalError::TrashUnavailable)
+        ));
+        journal.undo(Some(&store)).unwrap();
+        assert!(source.exists());
+
+        journal.redo(Some(&store)).unwrap();
+        assert!(!source.exists());
+        assert_eq!(store.list().len(), 1);
+    }
+
+    #[test]
+    fn undo_mkdir_removes_created_parents_and_redo_recreates_them() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let leaf = temp_dir.path().join("a").join("b");
+        fs::create_dir_all(&leaf).unwrap();
+        let mut journal = Journal::in_memory();
+        journal
+            .record(
+                "mkdir a/b".to_string(),
+                JournalOp::Mkdir {
+                    path: leaf.clone(),
+                    parents: 1,
+                },
+            )
+            .unwrap();
+
+        assert!(journal.undo(None).is_ok());
+        assert!(!temp_dir.path().join("a").exists());
+        assert!(journal.redo(None).is_ok());
+        assert!(leaf.is_dir());
+
+        fs::write(temp_dir.path().join("a").join("keep.txt"), "k").unwrap();
+        assert!(matches!(journal.undo(None), Err(JournalError::NotEmpty(_))));
+        assert!(leaf.is_dir());
+    }
+
+    #[test]
+    fn journal_persists_entries_across_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut journal = Journal::load(temp_dir.path());
+        journal
+            .record(
+                "mkdir new".to_string(),
+                JournalOp::Mkdir {
+                    path: temp_dir.path().join("new"),
+                    parents: 0,
+                },
+            )
+            .unwrap();
+        journal
+            .record(
+                "move".to_string(),
+                JournalOp::Move {
+                    items: vec![JournalPath {
+                        source: PathBuf::from("/a"),
+                        target: PathBuf::from("/b"),
+                        stamp: Some(FileStamp {
+                            len: 3,
+                            modified_ns: 42,
+                        }),
+                        replaced: Some(TrashedItem {
+                            name: "b".to_string(),
+                            original_path: PathBuf::from("/b"),
+                            deleted_at: "2026-01-01T00:00:00".to_string(),
+                            trash_dir: Some(PathBuf::from("/mnt/usb/.Trash-1000")),
+                        }),
+                    }],
+                },
+            )
+            .unwrap();
+
+        let loaded = Journal::load(temp_dir.path());
+
+        assert_eq!(loaded, journal);
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::{Entry, SortMode, list_entries_chunked};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingRequest {
+    pub id: u64,
+    pub dir: PathBuf,
+    pub show_hidden: bool,
+    pub sort: SortMode,
+}
+
+#[derive(Debug)]
+pub enum ListingEvent {
+    Chunk {
+        id: u64,
+        entries: Vec<Entry>,
+    },
+    Finished {
+        id: u64,
+        result: std::io::Result<Vec<Entry>>,
+    },
+}
+
+/// 一覧を読み込む関数。チャンクごとに `emit` を呼び、キャンセルされたら `Ok(None)` を返す。
+pub type Lister = Arc<
+    dyn Fn(
+            &ListingRequest,
+            &dyn Fn() -> bool,
+            &mut dyn FnMut(Vec<Entry>),
+        ) -> std::io::Result<Option<Vec<Entry>>>
+        + Send
+        + Sync,
+>;
+
+pub struct ListingWorker {
+    lister: Lister,
+    request_tx: Sender<ListingRequest>,
+    event_rx: Receiver<ListingEvent>,
+    active: Arc<AtomicU64>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl std::fmt::Debug for ListingWorker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ListingWorker")
+            .field("active", &self.active)
+            .field("in_flight", &self.in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ListingWorker {
+    pub fn new() -> Self {
+        Self::with_lister(Arc::new(|request, is_cancelled, emit| {
+            list_entries_chunked(
+                &request.dir,
+                request.show_hidden,
+                &request.sort,
+                is_cancelled,
+                emit,
+            )
+        }))
+    }
+
+    pub fn with_lister(lister: Lister) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<ListingRequest>();
+        let (event_tx, event_rx) = mpsc::channel::<ListingEvent>();
+        let active = Arc::new(AtomicU64::new(0));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let worker_lister = Arc::clone(&lister);
+        let worker_active = Arc::clone(&active);
+        let worker_in_flight = Arc::clone(&in_flight);
+
+        thread::spawn(move || {
+            request_rx.into_iter().for_each(|request| {
+                let id = request.id;
+                let is_cancelled = || worker_active.load(Ordering::Relaxed) != id;
+                if !is_cancelled() {
+                    let result = worker_lister(&request, &is_cancelled, &mut |entries| {
+                        let _ = event_tx.send(ListingEvent::Chunk { id, entries });
+                    });
+                    if let Some(result) = result.transpose() {
+                        let _ = event_tx.send(ListingEvent::Finished { id, result });
+                    }
+                }
+                worker_in_flight.fetch_sub(1, Ordering::Relaxed);
+            });
+        });
+
+        Self {
+            lister,
+            request_tx,
+            event_rx,
+            active,
+            in_flight,
+        }
+    }
+
+    /// 前の要求がまだ終わっていなければ、そのスレッドを捨てて新しいスレッドで受け付ける。
+    /// 止まった `read_dir` はキャンセルできないため、後の要求がその後ろで待たないようにする。
+    pub fn request(&mut self, request: ListingRequest) {
+        if self.in_flight.load(Ordering::Relaxed) > 0 {
+            self.restart();
+        }
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        self.active.store(request.id, Ordering::Relaxed);
+        let _ = self.request_tx.send(request);
+    }
+
+    /// 処理中の要求を見捨て、同じ読み込み関数で新しいスレッドを起こす。
+    pub fn restart(&mut self) {
+        self.cancel();
+        *self = Self::with_lister(Arc::clone(&self.lister));
+    }
+
+    pub fn cancel(&self) {
+        self.active.store(0, Ordering::Relaxed);
+    }
+
+    pub fn wait(
+        &self,
+        id: u64,
+        budget: Duration,
+        partial: &mut Vec<Entry>,
+    ) -> Option<std::io::Result<Vec<Entry>>> {
+        let deadline = Instant::now() + budget;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let event = if remaining.is_zero() {
+                self.event_rx.try_recv().ok()?
+            } else {
+                self.event_rx.recv_timeout(remaining).ok()?
+            };
+            match event {
+                ListingEvent::Chunk {
+                    id: event_id,
+                    entries,
+                } if event_id == id => partial.extend(entries),
+                ListingEvent::Finished {
+                    id: event_id,
+                    result,
+                } if event_id == id => return Some(result),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn poll(&self) -> Option<ListingEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    #[cfg(test)]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ListingEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(id: u64, dir: PathBuf) -> ListingRequest {
+        ListingRequest {
+            id,
+            dir,
+            show_hidden: false,
+            sort: SortMode::default(),
+        }
+    }
+
+    #[test]
+    fn listing_worker_streams_chunks_then_sorted_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for index in 0..1500 {
+            std::fs::write(temp_dir.path().join(format!("{index:04}.txt")), "").unwrap();
+        }
+        let mut worker = ListingWorker::new();
+        worker.request(request(1, temp_dir.path().to_path_buf()));
+
+        let mut partial = Vec::new();
+        let entries = worker
+            .wait(1, Duration::from_secs(5), &mut partial)
+            .unwrap()
+            .unwrap();
+        assert_eq!(partial.len(), 1500);
+        assert_eq!(entries.len(), 1500);
+        assert_eq!(entries[0].name, "0000.txt");
+        assert_eq!(entries[1499].name, "1499.txt");
+
+        worker.request(request(2, temp_dir.path().join("missing")));
+        let missing = worker.wait(2, Duration::from_secs(5), &mut Vec::new());
+        assert!(missing.is_some_and(|result| result.is_err()));
+    }
+
+    #[test]
+    fn listing_worker_does_not_queue_behind_a_stuck_request() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let stalled = temp_dir.path().join("stalled");
+        let healthy = temp_dir.path().join("healthy");
+        std::fs::create_dir(&healthy).unwrap();
+        std::fs::write(healthy.join("a.txt"), "").unwrap();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = std::sync::Mutex::new(release_rx);
+        let blocked = stalled.clone();
+        let mut worker =
+            ListingWorker::with_lister(Arc::new(move |request, is_cancelled, emit| {
+                if request.dir == blocked {
+                    let _ = release_rx.lock().unwrap().recv();
+                }
+                list_entries_chunked(
+                    &request.dir,
+                    request.show_hidden,
+                    &request.sort,
+                    is_cancelled,
+                    emit,
+                )
+            }));
+
+        worker.request(request(1, stalled));
+        worker.request(request(2, healthy));
+
+        let entries = worker.wait(2, Duration::from_secs(5), &mut Vec::new());
+        assert_eq!(entries.map(|result| result.unwrap().len()), Some(1));
+        drop(release_tx);
+    }
+
+    #[test]
+    fn listing_worker_ignores_superseded_requests() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "").unwrap();
+        let mut worker = ListingWorker::new();
+        worker.request(request(1, temp_dir.path().to_path_buf()));
+        worker.request(request(2, temp_dir.path().to_path_buf()));
+
+        let mut partial = Vec::new();
+        let entries = worker.wait(2, Duration::from_secs(5), &mut partial);
+        assert_eq!(entries.map(|result| result.unwrap().len()), Some(1));
+        assert_eq!(partial.len(), 1);
+    }
+}
@@ -1,24 +1,75 @@
+mod bookmarks;
+mod bulk_rename;
+mod conflict;
+mod copy;
+mod copy_worker;
 mod entries;
+mod expand;
+mod file_ops;
+mod filter;
+mod finder;
+mod frecency;
 mod git;
+mod gitignore;
+mod glob;
+mod grep;
+mod history;
+mod journal;
+mod listing;
 mod metadata;
+mod osc52;
 mod preview;
+mod regex;
+mod search;
 mod session;
 mod shell;
 mod shell_worker;
 mod slash_command;
 mod theme;
+mod trash;
+mod tree;
 pub mod user_notice;
+mod watch;
 
-pub use entries::{Entry, list_entries};
+pub use bookmarks::Bookmarks;
+pub use bulk_rename::{
+    BulkRenamePlan, apply_bulk_rename, build_bulk_rename_buffer, plan_bulk_rename,
+};
+pub use conflict::{
+    ConflictAction, ConflictChoice, ConflictOverride, ConflictPolicy, resolve_conflict,
+};
+pub use copy::{CopyEvent, CopyProgress, CopyRequest, copy_path, execute_copy, remove_path};
+pub use copy_worker::CopyWorker;
+pub use entries::{
+    Entry, EntryKind, SORT_FLAGS, SortKey, SortMode, list_entries, list_entries_chunked,
+    list_entries_sorted,
+};
+pub use expand::expand_path;
+pub use file_ops::{
+    FileOpError, move_entry, move_path, move_replacing, rename_entry, rename_path,
+    resolve_move_target,
+};
+pub use filter::EntryFilter;
+pub use finder::{FindEvent, FindRequest, FinderWorker};
+pub use frecency::{Frecency, FrecencyEvent, FrecencyWorker};
 pub use git::GitWorker;
+pub use gitignore::GitIgnore;
+pub use glob::glob_match;
+pub use grep::{GrepEvent, GrepRequest, GrepWorker};
+pub use history::{HistoryEntry, NavHistory};
+pub use journal::{Journal, JournalError, JournalOp, JournalPath};
+pub use listing::{ListingEvent, ListingRequest, ListingWorker};
 pub use metadata::{
     EntryMetadata, FetchPriority, FetchQueue, MetadataFetchResult, MetadataRequest,
     MetadataSnapshot, MetadataStatus, MetadataWindow, RequestId, RequestTracker, entry_metadata,
 };
+pub use osc52::osc52_sequence;
 pub use preview::{
     PreviewContent, PreviewError, PreviewEvent, PreviewFailed, PreviewReady, PreviewRequest,
     load_preview,
 };
+pub use regex::Regex;
+pub use search::{SearchMatcher, SearchMode};
 #[cfg(test)]
 pub(crate) use session::push_session_event_for_test;
 pub use session::{
@@ -31,3 +82,6 @@ pub use shell::{
 pub use shell_worker::{ShellEvent, ShellWorker};
 pub use slash_command::{SlashCommand, SlashCommandError, parse_slash_command};
 pub use theme::{ColorRgb, ColorTheme, ColorThemeId};
+pub use trash::{TrashStore, TrashedItem};
+pub use tree::{TreeEvent, TreeRequest, TreeWorker};
+pub use watch::{FsWatcher, WatchEvent};
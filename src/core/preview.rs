@@ -6,6 +6,7 @@ use std::path::{Path, PathBuf};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PreviewContent {
     pub lines: Vec<String>,
+    pub first_line: usize,
     pub truncated: bool,
     pub reason: Option<String>,
     pub kind_flags: Vec<LineKind>,
@@ -16,6 +17,7 @@ pub struct PreviewRequest {
     pub id: u64,
     pub path: PathBuf,
     pub max_bytes: usize,
+    pub focus_line: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,6 +25,8 @@ pub struct PreviewReady {
     pub id: u64,
     pub path: PathBuf,
     pub lines: Vec<String>,
+    pub first_line: usize,
+    pub focus_line: Option<usize>,
     pub truncated: bool,
     pub reason: Option<String>,
     pub kind_flags: Vec<LineKind>,
@@ -57,7 +61,13 @@ pub enum LineKind {
     Normal,
 }
 
-pub fn load_preview(path: &Path, max_bytes: usize) -> Result<PreviewContent, PreviewError> {
+const FOCUS_CONTEXT_LINES: usize = 5;
+
+pub fn load_preview(
+    path: &Path,
+    max_bytes: usize,
+    focus_line: Option<usize>,
+) -> Result<PreviewContent, PreviewError> {
     let metadata = std::fs::metadata(path).map_err(map_io_error)?;
     if metadata.len() as usize > max_bytes {
         return Err(PreviewError::TooLarge);
@@ -69,7 +79,7 @@ pub fn load_preview(path: &Path, max_bytes: usize) -> Result<PreviewContent, Pre
     if buffer.len() > max_bytes {
         return Err(PreviewError::TooLarge);
     }
-    if buffer.contains(&0) {
+    if is_binary(&buffer) {
         return Err(PreviewError::BinaryFile);
     }
 
@@ -85,22 +95,34 @@ pub fn load_preview(path: &Path, max_bytes: usize) -> Result<PreviewContent, Pre
         }
     };
 
-    Ok(build_preview_content(&text, reason))
+    Ok(build_preview_content(&text, reason, focus_line))
+}
+
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.contains(&0)
 }
 
-fn build_preview_content(text: &str, reason: Option<String>) -> PreviewContent {
+fn build_preview_content(
+    text: &str,
+    reason: Option<String>,
+    focus_line: Option<usize>,
+) -> PreviewContent {
     const MAX_LINES: usize = 40;
     const MAX_LINE_WIDTH: usize = 120;
+    let start = focus_line
+        .map(|line| line.saturating_sub(FOCUS_CONTEXT_LINES + 1))
+        .unwrap_or(0);
     let mut lines = Vec::new();
     let mut kind_flags = Vec::new();
-    for line in text.lines().take(MAX_LINES) {
+    for line in text.lines().skip(start).take(MAX_LINES) {
         let normalized = normalize_line(line, MAX_LINE_WIDTH);
         kind_flags.push(detect_line_kind(&normalized));
         lines.push(normalized);
     }
-    let truncated = text.lines().count() > MAX_LINES;
+    let truncated = text.lines().count() > start + MAX_LINES;
     PreviewContent {
         lines,
+        first_line: start + 1,
         truncated,
         reason,
         kind_flags,
@@ -152,20 +174,39 @@ mod tests {
             writeln!(file, "line-{index}").unwrap();
         }
 
-        let preview = load_preview(&file_path, 1024 * 1024).unwrap();
+        let preview = load_preview(&file_path, 1024 * 1024, None).unwrap();
 
         assert_eq!(preview.lines.len(), 40);
         assert!(preview.truncated);
         assert_eq!(preview.reason, None);
     }
 
+    #[test]
+    fn load_preview_starts_near_focus_line() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("note.txt");
+        let mut file = File::create(&file_path).unwrap();
+        for index in 1..=100 {
+            writeln!(file, "line-{index}").unwrap();
+        }
+
+        let preview = load_preview(&file_path, 1024 * 1024, Some(60)).unwrap();
+
+        assert_eq!(preview.first_line, 55);
+        assert_eq!(preview.lines[0], "line-55");
+        assert_eq!(preview.lines[5], "line-60");
+        assert!(preview.truncated);
+        let top = load_preview(&file_path, 1024 * 1024, Some(2)).unwrap();
+        assert_eq!(top.first_line, 1);
+    }
+
     #[test]
     fn load_preview_marks_markdown_kinds() {
         let temp_dir = tempfile::tempdir().unwrap();
         let file_path = temp_dir.path().join("note.md");
         std::fs::write(&file_path, "# Title\n- item\n```\ncode\nplain\n").unwrap();
 
-        let preview = load_preview(&file_path, 1024 * 1024).unwrap();
+        let preview = load_preview(&file_path, 1024 * 1024, None).unwrap();
 
         assert_eq!(
             preview.kind_flags,
@@ -185,7 +226,7 @@ mod tests {
         let file_path = temp_dir.path().join("note.bin");
         std::fs::write(&file_path, vec![0xF0, 0x28, 0x8C, 0x28]).unwrap();
 
-        let preview = load_preview(&file_path, 1024 * 1024).unwrap();
+        let preview = load_preview(&file_path, 1024 * 1024, None).unwrap();
 
         assert_eq!(preview.reason, Some("非UTF-8のため簡易モード".to_string()));
     }
@@ -197,7 +238,7 @@ mod tests {
         let content = vec![b'a'; 1024];
         std::fs::write(&file_path, content).unwrap();
 
-        let result = load_preview(&file_path, 10);
+        let result = load_preview(&file_path, 10, None);
 
         assert_eq!(result.unwrap_err(), PreviewError::TooLarge);
     }
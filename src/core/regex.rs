@@ -0,0 +1,595 @@
+use std::ops::Range;
+
+const MAX_PROGRAM_LEN: usize = 10_000;
+
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RegexError {
+    #[error("unclosed group")]
+    UnclosedGroup,
+    #[error("unmatched ')'")]
+    UnmatchedParen,
+    #[error("unclosed character class")]
+    UnclosedClass,
+    #[error("nothing to repeat")]
+    NothingToRepeat,
+    #[error("invalid repetition")]
+    InvalidRepeat,
+    #[error("trailing backslash")]
+    TrailingBackslash,
+    #[error("pattern too large")]
+    TooLarge,
+}
+
+#[derive(Debug, Clone)]
+pub struct Regex {
+    source: String,
+    program: Vec<Inst>,
+    ignore_case: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    Start,
+    End,
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Char(char),
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ClassItem {
+    Range(char, char),
+    Digit,
+    Word,
+    Space,
+}
+
+impl ClassItem {
+    fn matches(self, ch: char) -> bool {
+        match self {
+            Self::Range(start, end) => start <= ch && ch <= end,
+            Self::Digit => ch.is_ascii_digit(),
+            Self::Word => ch.is_alphanumeric() || ch == '_',
+            Self::Space => ch.is_whitespace(),
+        }
+    }
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Self, RegexError> {
+        Self::with_case(pattern, false)
+    }
+
+    pub fn with_case(pattern: &str, ignore_case: bool) -> Result<Self, RegexError> {
+        let (body, ignore_case) = match pattern.strip_prefix("(?i)") {
+            Some(body) => (body, true),
+            None => (pattern, ignore_case),
+        };
+        let mut parser = Parser {
+            chars: body.chars().collect(),
+            index: 0,
+        };
+        let root = parser.parse_alternation()?;
+        if parser.index < parser.chars.len() {
+            return Err(RegexError::UnmatchedParen);
+        }
+        let mut compiler = Compiler::default();
+        compiler.alternation(&root)?;
+        compiler.push(Inst::Match)?;
+        Ok(Self {
+            source: pattern.to_string(),
+            program: compiler.program,
+            ignore_case,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    pub fn find(&self, text: &str) -> Option<Range<usize>> {
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut matched = None;
+        let positions = text
+            .char_indices()
+            .map(|(index, ch)| (index, Some(ch)))
+            .chain(std::iter::once((text.len(), None)));
+        for (index, ch) in positions {
+            if matched.is_none() {
+                self.add_thread(&mut current, 0, index, index, text.len());
+            }
+            if current.is_empty() && matched.is_some() {
+                break;
+            }
+            next.clear();
+            for &(pc, start) in &current.threads {
+                match &self.program[pc] {
+                    Inst::Match => {
+                        matched = Some(start..index);
+                        break;
+                    }
+                    inst => {
+                        if let Some(ch) = ch
+                            && self.char_matches(inst, ch)
+                        {
+                            let end = index + ch.len_utf8();
+                            self.add_thread(&mut next, pc + 1, start, end, text.len());
+                        }
+                    }
+                }
+            }
+            std::mem::swap(&mut current, &mut next);
+        }
+        matched
+    }
+
+    fn add_thread(&self, threads: &mut Threads, pc: usize, start: usize, index: usize, len: usize) {
+        let mut stack = vec![pc];
+        while let Some(pc) = stack.pop() {
+            if !threads.visit(pc) {
+                continue;
+            }
+            match &self.program[pc] {
+                Inst::Jump(target) => stack.push(*target),
+                Inst::Split(preferred, other) => {
+                    stack.push(*other);
+                    stack.push(*preferred);
+                }
+                Inst::Start => {
+                    if index == 0 {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::End => {
+                    if index == len {
+                        stack.push(pc + 1);
+                    }
+                }
+                _ => threads.threads.push((pc, start)),
+            }
+        }
+    }
+
+    fn char_matches(&self, inst: &Inst, ch: char) -> bool {
+        let matches = |candidate: char| match inst {
+            Inst::Any => candidate != '\n',
+            Inst::Char(expected) => candidate == *expected,
+            Inst::Class { items, .. } => items.iter().any(|item| item.matches(candidate)),
+            _ => false,
+        };
+        let found = matches(ch)
+            || (self.ignore_case
+                && (ch.to_lowercase().any(matches) || ch.to_uppercase().any(matches)));
+        match inst {
+            Inst::Class { negated, .. } => found != *negated,
+            _ => found,
+        }
+    }
+}
+
+struct Threads {
+    threads: Vec<(usize, usize)>,
+    visited: Vec<bool>,
+}
+
+impl Threads {
+    fn new(len: usize) -> Self {
+        Self {
+            threads: Vec::new(),
+            visited: vec![false; len],
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.threads.is_empty()
+    }
+
+    fn visit(&mut self, pc: usize) -> bool {
+        !std::mem::replace(&mut self.visited[pc], true)
+    }
+
+    fn clear(&mut self) {
+        self.threads.clear();
+        self.visited.fill(false);
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek();
+        if ch.is_some() {
+            self.index += 1;
+        }
+        ch
+    }
+
+    fn parse_alternation(&mut self) -> Result<Vec<Vec<Node>>, RegexError> {
+        let mut branches = vec![self.parse_sequence()?];
+        while self.peek() == Some('|') {
+            self.index += 1;
+            branches.push(self.parse_sequence()?);
+        }
+        Ok(branches)
+    }
+
+    fn parse_sequence(&mut self) -> Result<Vec<Node>, RegexError> {
+        let mut nodes = Vec::new();
+        while let Some(ch) = self.peek() {
+            if ch == '|' || ch == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_repeat(atom)?);
+        }
+        Ok(nodes)
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, RegexError> {
+        match self.next() {
+            Some('(') => {
+                if self.chars[self.index..].starts_with(&['?', ':']) {
+                    self.index += 2;
+                }
+                let branches = self.parse_alternation()?;
+                if self.next() != Some(')') {
+                    return Err(RegexError::UnclosedGroup);
+                }
+                Ok(Node::Group(branches))
+            }
+            Some('[') => self.parse_class(),
+            Some('.') => Ok(Node::Any),
+            Some('^') => Ok(Node::Start),
+            Some('$') => Ok(Node::End),
+            Some('*' | '+' | '?') => Err(RegexError::NothingToRepeat),
+            Some('\\') => {
+                let ch = self.next().ok_or(RegexError::TrailingBackslash)?;
+                Ok(escape_node(ch))
+            }
+            Some(ch) => Ok(Node::Char(ch)),
+            None => Err(RegexError::NothingToRepeat),
+        }
+    }
+
+    fn parse_class(&mut self) -> Result<Node, RegexError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.index += 1;
+        }
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            let ch = self.next().ok_or(RegexError::UnclosedClass)?;
+            if ch == ']' && !first {
+                break;
+            }
+            first = false;
+            let start = if ch == '\\' {
+                let escaped = self.next().ok_or(RegexError::UnclosedClass)?;
+                match escape_class(escaped) {
+                    Some(item) => {
+                        items.push(item);
+                        continue;
+                    }
+                    None => escaped,
+                }
+            } else {
+                ch
+            };
+            let is_range = self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.index + 1)
+                    .is_some_and(|next| *next != ']');
+            if is_range {
+                self.index += 1;
+                let end = match self.next() {
+                    Some('\\') => self.next().ok_or(RegexError::UnclosedClass)?,
+                    Some(end) => end,
+                    None => return Err(RegexError::UnclosedClass),
+                };
+                items.push(ClassItem::Range(start, end));
+            } else {
+                items.push(ClassItem::Range(start, start));
+            }
+        }
+        Ok(Node::Class { items, negated })
+    }
+
+    fn parse_repeat(&mut self, atom: Node) -> Result<Node, RegexError> {
+        let (min, max) = match self.peek() {
+            Some('{') => match self.parse_bounds()? {
+                Some(bounds) => bounds,
+                None => return Ok(atom),
+            },
+            Some(ch @ ('*' | '+' | '?')) => {
+                self.index += 1;
+                match ch {
+                    '*' => (0, None),
+                    '+' => (1, None),
+                    _ => (0, Some(1)),
+                }
+            }
+            _ => return Ok(atom),
+        };
+        if matches!(atom, Node::Start | Node::End | Node::Repeat { .. }) {
+            return Err(RegexError::NothingToRepeat);
+        }
+        let greedy = self.peek() != Some('?');
+        if !greedy {
+            self.index += 1;
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+        })
+    }
+
+    fn parse_bounds(&mut self) -> Result<Option<(usize, Option<usize>)>, RegexError> {
+        let Some(close) = self.chars[self.index..]
+            .iter()
+            .position(|ch| *ch == '}')
+            .map(|offset| self.index + offset)
+        else {
+            return Ok(None);
+        };
+        let body = self.chars[self.index + 1..close].iter().collect::<String>();
+        let parse = |value: &str| value.trim().parse::<usize>().ok();
+        let bounds = match body.split_once(',') {
+            None => parse(&body).map(|count| (count, Some(count))),
+            Some((min, "")) => parse(min).map(|min| (min, None)),
+            Some((min, max)) => parse(min)
+                .zip(parse(max))
+                .map(|(min, max)| (min, Some(max))),
+        };
+        let Some((min, max)) = bounds else {
+            return Ok(None);
+        };
+        if max.is_some_and(|max| max < min) {
+            return Err(RegexError::InvalidRepeat);
+        }
+        self.index = close + 1;
+        Ok(Some((min, max)))
+    }
+}
+
+fn escape_class(ch: char) -> Option<ClassItem> {
+    match ch {
+        'd' => Some(ClassItem::Digit),
+        'w' => Some(ClassItem::Word),
+        's' => Some(ClassItem::Space),
+        _ => None,
+    }
+}
+
+fn escape_node(ch: char) -> Node {
+    if let Some(item) = escape_class(ch) {
+        return Node::Class {
+            items: vec![item],
+            negated: false,
+        };
+    }
+    if let Some(item) = escape_class(ch.to_ascii_lowercase()) {
+        return Node::Class {
+            items: vec![item],
+            negated: true,
+        };
+    }
+    match ch {
+        't' => Node::Char('\t'),
+        'n' => Node::Char('\n'),
+        ch => Node::Char(ch),
+    }
+}
+
+#[derive(Default)]
+struct Compiler {
+    program: Vec<Inst>,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> Result<usize, RegexError> {
+        if self.program.len() >= MAX_PROGRAM_LEN {
+            return Err(RegexError::TooLarge);
+        }
+        self.program.push(inst);
+        Ok(self.program.len() - 1)
+    }
+
+    fn patch(&mut self, at: usize, target: usize) {
+        match &mut self.program[at] {
+            Inst::Jump(jump) => *jump = target,
+            Inst::Split(_, other) => *other = target,
+            _ => {}
+        }
+    }
+
+    fn alternation(&mut self, branches: &[Vec<Node>]) -> Result<(), RegexError> {
+        let mut exits = Vec::new();
+        for (index, branch) in branches.iter().enumerate() {
+            let split = if index + 1 < branches.len() {
+                let at = self.program.len();
+                Some(self.push(Inst::Split(at + 1, 0))?)
+            } else {
+                None
+            };
+            for node in branch {
+                self.node(node)?;
+            }
+            if let Some(split) = split {
+                exits.push(self.push(Inst::Jump(0))?);
+                let next = self.program.len();
+                self.patch(split, next);
+            }
+        }
+        let end = self.program.len();
+        for exit in exits {
+            self.patch(exit, end);
+        }
+        Ok(())
+    }
+
+    fn node(&mut self, node: &Node) -> Result<(), RegexError> {
+        match node {
+            Node::Char(ch) => self.push(Inst::Char(*ch)).map(drop),
+            Node::Any => self.push(Inst::Any).map(drop),
+            Node::Class { items, negated } => self
+                .push(Inst::Class {
+                    items: items.clone(),
+                    negated: *negated,
+                })
+                .map(drop),
+            Node::Start => self.push(Inst::Start).map(drop),
+            Node::End => self.push(Inst::End).map(drop),
+            Node::Group(branches) => self.alternation(branches),
+            Node::Repeat {
+                node,
+                min,
+                max,
+                greedy,
+            } => {
+                for _ in 0..*min {
+                    self.node(node)?;
+                }
+                match max {
+                    None => {
+                        let split = self.split(*greedy)?;
+                        self.node(node)?;
+                        self.push(Inst::Jump(split))?;
+                        let end = self.program.len();
+                        self.patch_split(split, end, *greedy);
+                    }
+                    Some(max) => {
+                        let mut splits = Vec::new();
+                        for _ in *min..*max {
+                            splits.push(self.split(*greedy)?);
+                            self.node(node)?;
+                        }
+                        let end = self.program.len();
+                        for split in splits {
+                            self.patch_split(split, end, *greedy);
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn split(&mut self, greedy: bool) -> Result<usize, RegexError> {
+        let body = self.program.len() + 1;
+        if greedy {
+            self.push(Inst::Split(body, 0))
+        } else {
+            self.push(Inst::Split(0, body))
+        }
+    }
+
+    fn patch_split(&mut self, at: usize, exit: usize, greedy: bool) {
+        if let Inst::Split(preferred, other) = &mut self.program[at] {
+            if greedy {
+                *other = exit;
+            } else {
+                *preferred = exit;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regex_matches_classes_groups_and_anchors() {
+        let regex = Regex::new(r"^file_\d{2,3}\.(rs|toml)$").unwrap();
+        assert!(regex.is_match("file_01.rs"));
+        assert!(regex.is_match("file_123.toml"));
+        assert!(!regex.is_match("file_1.rs"));
+        assert!(!regex.is_match("file_01.rsx"));
+
+        let regex = Regex::new("[^a-c]+x?$").unwrap();
+        assert_eq!(regex.find("abcdef"), Some(3..6));
+        assert!(Regex::new("a(b*)+c").unwrap().is_match("ac"));
+    }
+
+    #[test]
+    fn regex_find_reports_leftmost_byte_range() {
+        let regex = Regex::new("o+").unwrap();
+        assert_eq!(regex.find("föoo"), Some(3..5));
+        assert_eq!(Regex::new("a.*?b").unwrap().find("xaxbxb"), Some(1..4));
+        assert_eq!(
+            Regex::new("(?i)readme").unwrap().find("the README"),
+            Some(4..10)
+        );
+        assert_eq!(Regex::new("z").unwrap().find("abc"), None);
+    }
+
+    #[test]
+    fn regex_handles_long_lines_and_nested_quantifiers() {
+        let line = "a".repeat(100_000);
+        assert!(!Regex::new(".*b").unwrap().is_match(&line));
+        assert_eq!(Regex::new(".*a$").unwrap().find(&line), Some(0..line.len()));
+        assert!(!Regex::new("(a*)*b").unwrap().is_match(&line));
+        assert!(!Regex::new("(a|aa)+$x").unwrap().is_match(&line));
+        assert_eq!(
+            Regex::new("((a{1,100}){1,100})").unwrap_err(),
+            RegexError::TooLarge
+        );
+    }
+
+    #[test]
+    fn regex_rejects_invalid_patterns() {
+        assert_eq!(Regex::new("(ab").unwrap_err(), RegexError::UnclosedGroup);
+        assert_eq!(Regex::new("ab)").unwrap_err(), RegexError::UnmatchedParen);
+        assert_eq!(Regex::new("[ab").unwrap_err(), RegexError::UnclosedClass);
+        assert_eq!(Regex::new("*a").unwrap_err(), RegexError::NothingToRepeat);
+        assert_eq!(Regex::new("a{3,1}").unwrap_err(), RegexError::InvalidRepeat);
+        assert_eq!(
+            Regex::new("a\\").unwrap_err(),
+            RegexError::TrailingBackslash
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::core::regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    #[default]
+    Prefix,
+    Substring,
+    Fuzzy,
+    Regex,
+}
+
+impl SearchMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "prefix" => Some(Self::Prefix),
+            "substring" => Some(Self::Substring),
+            "fuzzy" => Some(Self::Fuzzy),
+            "regex" => Some(Self::Regex),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Prefix => "prefix",
+            Self::Substring => "substring",
+            Self::Fuzzy => "fuzzy",
+            Self::Regex => "regex",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SearchMatcher {
+    Prefix(Vec<char>),
+    Substring(Vec<char>),
+    Fuzzy {
+        needle: Vec<char>,
+        ignore_case: bool,
+    },
+    Regex(Regex),
+}
+
+impl SearchMatcher {
+    pub fn new(mode: SearchMode, needle: &str) -> Option<Self> {
+        if needle.is_empty() {
+            return None;
+        }
+        let chars = needle.chars().collect::<Vec<_>>();
+        match mode {
+            SearchMode::Prefix => Some(Self::Prefix(chars)),
+            SearchMode::Substring => Some(Self::Substring(chars)),
+            SearchMode::Fuzzy => {
+                let ignore_case = !chars.iter().any(|ch| ch.is_uppercase());
+                Some(Self::Fuzzy {
+                    needle: chars,
+                    ignore_case,
+                })
+            }
+            SearchMode::Regex => Regex::new(needle).ok().map(Self::Regex),
+        }
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        self.find(name).is_some()
+    }
+
+    pub fn find(&self, name: &str) -> Option<Vec<usize>> {
+        let text = name.chars().collect::<Vec<_>>();
+        match self {
+            Self::Prefix(needle) => text
+                .starts_with(needle)
+                .then(|| (0..needle.len()).collect()),
+            Self::Substring(needle) => text
+                .windows(needle.len())
+                .position(|window| window == needle.as_slice())
+                .map(|start| (start..start + needle.len()).collect()),
+            Self::Fuzzy {
+                needle,
+                ignore_case,
+            } => fuzzy_positions(needle, &text, *ignore_case),
+            Self::Regex(regex) => {
+                let range = regex.find(name)?;
+                let positions = name
+                    .char_indices()
+                    .enumerate()
+                    .filter(|(_, (byte, _))| range.contains(byte))
+                    .map(|(index, _)| index)
+                    .collect();
+                Some(positions)
+            }
+        }
+    }
+}
+
+fn fuzzy_positions(needle: &[char], text: &[char], ignore_case: bool) -> Option<Vec<usize>> {
+    let fold = |ch: char| {
+        if ignore_case {
+            ch.to_lowercase().next().unwrap_or(ch)
+        } else {
+            ch
+        }
+    };
+    let mut positions = Vec::with_capacity(needle.len());
+    let mut start = 0;
+    for expected in needle {
+        let offset = text[start..]
+            .iter()
+            .position(|ch| fold(*ch) == fold(*expected))?;
+        positions.push(start + offset);
+        start += offset + 1;
+    }
+    Some(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(mode: SearchMode, needle: &str, name: &str) -> Option<Vec<usize>> {
+        SearchMatcher::new(mode, needle).and_then(|matcher| matcher.find(name))
+    }
+
+    #[test]
+    fn search_modes_report_matched_positions() {
+        assert_eq!(find(SearchMode::Prefix, "ma", "main.rs"), Some(vec![0, 1]));
+        assert_eq!(find(SearchMode::Prefix, "in", "main.rs"), None);
+        assert_eq!(
+            find(SearchMode::Substring, "in", "main.rs"),
+            Some(vec![2, 3])
+        );
+        assert_eq!(
+            find(SearchMode::Regex, r"\.r.$", "mäin.rs"),
+            Some(vec![4, 5, 6])
+        );
+        assert_eq!(find(SearchMode::Regex, "(", "main.rs"), None);
+        assert!(SearchMatcher::new(SearchMode::Substring, "").is_none());
+    }
+
+    #[test]
+    fn fuzzy_search_uses_smart_case() {
+        assert_eq!(
+            find(SearchMode::Fuzzy, "mrs", "Main.RS"),
+            Some(vec![0, 5, 6])
+        );
+        assert_eq!(find(SearchMode::Fuzzy, "MR", "main.rs"), None);
+        assert_eq!(find(SearchMode::Fuzzy, "MR", "Main.Rs"), Some(vec![0, 5]));
+        assert_eq!(find(SearchMode::Fuzzy, "sm", "main.rs"), None);
+    }
+
+    #[test]
+    fn search_mode_parses_names() {
+        assert_eq!(SearchMode::from_name("Fuzzy"), Some(SearchMode::Fuzzy));
+        assert_eq!(SearchMode::from_name("glob"), None);
+        assert_eq!(SearchMode::Regex.name(), "regex");
+    }
+}
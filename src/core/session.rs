@@ -3,6 +3,7 @@ use std::sync::{Mutex, OnceLock, mpsc};
 use std::thread;
 
 use crate::config::config_root;
+use crate::core::{HistoryEntry, NavHistory, SortMode};
 use uuid::Uuid;
 
 pub fn load_session_tabs() -> Vec<SessionTab> {
@@ -22,6 +23,8 @@ pub struct SessionTab {
     pub tab_id: u64,
     pub path: PathBuf,
     pub theme_name: String,
+    pub sort: SortMode,
+    pub history: NavHistory,
 }
 
 #[derive(Debug, Clone)]
@@ -144,10 +147,18 @@ fn parse_session_tabs(content: &str) -> Vec<SessionTab> {
                 .and_then(|theme| theme.as_str())
                 .unwrap_or("")
                 .to_string();
+            let sort = tab
+                .get("sort")
+                .and_then(|sort| sort.as_str())
+                .and_then(SortMode::parse)
+                .unwrap_or_default();
+            let history = tab.get("history").map(parse_history).unwrap_or_default();
             Some(SessionTab {
                 tab_id,
                 path: PathBuf::from(path),
                 theme_name,
+                sort,
+                history,
             })
         })
         .collect()
@@ -161,6 +172,8 @@ fn build_session_payload(tabs: &[SessionTab], session_id: &str) -> String {
                 "tab_id": tab.tab_id,
                 "path": tab.path.to_string_lossy(),
                 "theme": tab.theme_name,
+                "sort": tab.sort.label(),
+                "history": history_to_json(&tab.history),
             })
         })
         .collect::<Vec<_>>();
@@ -172,6 +185,53 @@ fn build_session_payload(tabs: &[SessionTab], session_id: &str) -> String {
     .to_string()
 }
 
+fn parse_history(value: &serde_json::Value) -> NavHistory {
+    let entries = |key: &str| {
+        value
+            .get(key)
+            .and_then(|entries| entries.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let path = entry.get("path").and_then(|path| path.as_str())?;
+                        let focus = entry
+                            .get("focus")
+                            .and_then(|focus| focus.as_str())
+                            .map(str::to_string);
+                        Some(HistoryEntry {
+                            path: PathBuf::from(path),
+                            focus,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    NavHistory {
+        back: entries("back"),
+        forward: entries("forward"),
+    }
+}
+
+fn history_to_json(history: &NavHistory) -> serde_json::Value {
+    let entries = |entries: &[HistoryEntry]| {
+        entries
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "path": entry.path.to_string_lossy(),
+                    "focus": entry.focus,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    serde_json::json!({
+        "back": entries(&history.back),
+        "forward": entries(&history.forward),
+    })
+}
+
 fn generate_session_id() -> String {
     Uuid::now_v7().to_string()
 }
@@ -246,11 +306,15 @@ mod tests {
                     tab_id: 9,
                     path: PathBuf::from("/one"),
                     theme_name: "Night Harbor".to_string(),
+                    sort: SortMode::default(),
+                    history: NavHistory::default(),
                 },
                 SessionTab {
                     tab_id: 2,
                     path: PathBuf::from("/two"),
                     theme_name: "".to_string(),
+                    sort: SortMode::default(),
+                    history: NavHistory::default(),
                 },
             ]
         );
@@ -262,6 +326,8 @@ mod tests {
             tab_id: 1,
             path: PathBuf::from("/one"),
             theme_name: "Glacier Coast".to_string(),
+            sort: SortMode::default(),
+            history: NavHistory::default(),
         }];
 
         let payload = build_session_payload(&tabs, "test-session");
@@ -282,6 +348,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_payload_round_trips_history() {
+        let history = NavHistory {
+            back: vec![HistoryEntry {
+                path: PathBuf::from("/one"),
+                focus: Some("a.txt".to_string()),
+            }],
+            forward: vec![HistoryEntry {
+                path: PathBuf::from("/three"),
+                focus: None,
+            }],
+        };
+        let tabs = vec![SessionTab {
+            tab_id: 1,
+            path: PathBuf::from("/two"),
+            theme_name: "Glacier Coast".to_string(),
+            sort: SortMode::default(),
+            history,
+        }];
+
+        let payload = build_session_payload(&tabs, "test-session");
+
+        assert_eq!(parse_session_tabs(&payload), tabs);
+    }
+
     #[test]
     fn prune_session_history_keeps_latest_50() {
         let dir = tempdir().expect("tempdir");
@@ -319,6 +410,8 @@ mod tests {
             tab_id: 1,
             path: PathBuf::from("/"),
             theme_name: String::new(),
+            sort: SortMode::default(),
+            history: NavHistory::default(),
         }]);
 
         let deadline = Instant::now() + Duration::from_secs(1);
@@ -102,6 +102,39 @@ impl ShellCommandParser {
     }
 }
 
+const SHELL_TARGETS_PLACEHOLDER: &str = "{files}";
+const SHELL_TARGETS_ESCAPED: &str = "{{files}}";
+
+pub fn expand_shell_targets(raw_command: &str, targets: &[String]) -> String {
+    let quoted = targets
+        .iter()
+        .map(|target| quote_shell_arg(target))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut expanded = String::with_capacity(raw_command.len());
+    let mut rest = raw_command;
+    while let Some(index) = rest.find('{') {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some(after) = rest.strip_prefix(SHELL_TARGETS_ESCAPED) {
+            expanded.push_str(SHELL_TARGETS_PLACEHOLDER);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(SHELL_TARGETS_PLACEHOLDER) {
+            expanded.push_str(&quoted);
+            rest = after;
+        } else {
+            expanded.push('{');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+fn quote_shell_arg(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
 #[derive(Debug)]
 struct ParseState {
     args: Vec<String>,
@@ -271,6 +304,11 @@ impl ShellCommandRequest {
             args,
         })
     }
+
+    pub fn with_targets(mut self, targets: &[String]) -> Self {
+        self.raw_command = expand_shell_targets(&self.raw_command, targets);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -466,6 +504,37 @@ mod tests {
         });
     }
 
+    #[test]
+    fn expand_shell_targets_quotes_each_target() {
+        let targets = vec!["a b.txt".to_string(), "it's".to_string()];
+
+        let expanded = expand_shell_targets("ls -l {files}", &targets);
+
+        assert_eq!(expanded, "ls -l 'a b.txt' 'it'\\''s'");
+        assert_eq!(expand_shell_targets("pwd", &targets), "pwd");
+        assert_eq!(expand_shell_targets("date +%s", &targets), "date +%s");
+        assert_eq!(
+            expand_shell_targets("echo {{files}} {x} {files}", &targets[..1]),
+            "echo {files} {x} 'a b.txt'"
+        );
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn shell_targets_are_passed_literally() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let probe = temp_dir.path().join("probe");
+        let name = format!("it's \"$(touch {})\" `id` $HOME", probe.display());
+        let request = ShellCommandRequest::new(temp_dir.path().to_path_buf(), "printf %s {files}")
+            .unwrap()
+            .with_targets(std::slice::from_ref(&name));
+
+        let result = ShellExecutionGuard::new().execute(&request).unwrap();
+
+        assert_eq!(result.stdout, name);
+        assert!(!probe.exists());
+    }
+
     #[test]
     fn parse_args_supports_quotes() {
         let args = ShellCommandParser::parse_args("echo \"foo bar\"").unwrap();
@@ -0,0 +1,501 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+use crate::core::{FileOpError, move_path};
+
+const TRASH_INFO_EXTENSION: &str = "trashinfo";
+const MOUNTS_FILE: &str = "/proc/self/mounts";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedItem {
+    pub name: String,
+    pub original_path: PathBuf,
+    pub deleted_at: String,
+    /// ホーム以外のデバイスにある `$topdir/.Trash-$uid`。`None` はホームのゴミ箱。
+    pub trash_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashStore {
+    root: PathBuf,
+    mount_trash: bool,
+}
+
+impl TrashStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            mount_trash: false,
+        }
+    }
+
+    pub fn from_env() -> Option<Self> {
+        if cfg!(test) {
+            return None;
+        }
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+            })?;
+        Some(Self {
+            mount_trash: true,
+            ..Self::new(data_home.join("Trash"))
+        })
+    }
+
+    pub fn trash(&self, path: &Path) -> Result<TrashedItem, FileOpError> {
+        if path.symlink_metadata().is_err() {
+            return Err(FileOpError::NotFound(path.to_path_buf()));
+        }
+        let original_path = absolute_path(path)?;
+        let Some(file_name) = original_path.file_name() else {
+            return Err(FileOpError::InvalidName(
+                original_path.display().to_string(),
+            ));
+        };
+        std::fs::create_dir_all(files_dir(&self.root))?;
+        std::fs::create_dir_all(info_dir(&self.root))?;
+        let trash_dir = self.mount_trash_for(&original_path);
+        let root = trash_dir.as_deref().unwrap_or(&self.root);
+        let deleted_at = Local::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        let base_name = file_name.to_string_lossy().to_string();
+        let (name, info_path) = reserve_info_file(root, &base_name, &original_path, &deleted_at)?;
+        if let Err(error) = move_path(&original_path, &files_dir(root).join(&name)) {
+            let _ = std::fs::remove_file(&info_path);
+            return Err(error);
+        }
+        Ok(TrashedItem {
+            name,
+            original_path,
+            deleted_at,
+            trash_dir,
+        })
+    }
+
+    pub fn list(&self) -> Vec<TrashedItem> {
+        let mut items = list_trash_dir(&self.root, None);
+        if self.mount_trash {
+            for trash_dir in self.mount_trash_dirs() {
+                items.extend(list_trash_dir(&trash_dir, Some(trash_dir.clone())));
+            }
+        }
+        items.sort_by(|left, right| {
+            right
+                .deleted_at
+                .cmp(&left.deleted_at)
+                .then_with(|| left.name.cmp(&right.name))
+        });
+        items
+    }
+
+    pub fn contains(&self, item: &TrashedItem) -> bool {
+        let root = self.root_of(item);
+        info_path(root, &item.name).is_file()
+            && files_dir(root).join(&item.name).symlink_metadata().is_ok()
+    }
+
+    pub fn restore(&self, item: &TrashedItem) -> Result<PathBuf, FileOpError> {
+        let root = self.root_of(item);
+        let source = files_dir(root).join(&item.name);
+        if item.original_path.symlink_metadata().is_ok() {
+            return Err(FileOpError::AlreadyExists(item.original_path.clone()));
+        }
+        if let Some(parent) = item.original_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let restored = move_path(&source, &item.original_path)?;
+        let _ = std::fs::remove_file(info_path(root, &item.name));
+        Ok(restored)
+    }
+
+    fn root_of<'a>(&'a self, item: &'a TrashedItem) -> &'a Path {
+        item.trash_dir.as_deref().unwrap_or(&self.root)
+    }
+
+    /// 別のデバイスにあるエントリは、コピーせずに済むようそのデバイスの
+    /// `$topdir/.Trash-$uid` へ送る。作れなければホームのゴミ箱へ戻る。
+    fn mount_trash_for(&self, original_path: &Path) -> Option<PathBuf> {
+        if !self.mount_trash {
+            return None;
+        }
+        let parent = original_path.parent()?;
+        let topdir = mount_topdir(parent, &self.root)?;
+        let trash_dir = topdir.join(format!(".Trash-{}", owner_uid(&self.root)?));
+        create_private_dir(&files_dir(&trash_dir)).ok()?;
+        create_private_dir(&info_dir(&trash_dir)).ok()?;
+        Some(trash_dir)
+    }
+
+    fn mount_trash_dirs(&self) -> Vec<PathBuf> {
+        let Some(uid) = owner_uid(&self.root) else {
+            return Vec::new();
+        };
+        let Ok(mounts) = std::fs::read_to_string(MOUNTS_FILE) else {
+            return Vec::new();
+        };
+        parse_mount_points(&mounts)
+            .into_iter()
+            .map(|mount| mount.join(format!(".Trash-{uid}")))
+            .filter(|trash_dir| trash_dir.is_dir() && *trash_dir != self.root)
+            .collect()
+    }
+}
+
+fn files_dir(root: &Path) -> PathBuf {
+    root.join("files")
+}
+
+fn info_dir(root: &Path) -> PathBuf {
+    root.join("info")
+}
+
+fn info_path(root: &Path, name: &str) -> PathBuf {
+    info_dir(root).join(format!("{name}.{TRASH_INFO_EXTENSION}"))
+}
+
+fn reserve_info_file(
+    root: &Path,
+    base_name: &str,
+    original_path: &Path,
+    deleted_at: &str,
+) -> Result<(String, PathBuf), FileOpError> {
+    let payload = build_trash_info(original_path, deleted_at);
+    let mut counter = 1usize;
+    loop {
+        let name = if counter == 1 {
+            base_name.to_string()
+        } else {
+            format!("{base_name}.{counter}")
+        };
+        counter += 1;
+        if files_dir(root).join(&name).symlink_metadata().is_ok() {
+            continue;
+        }
+        let info_path = info_path(root, &name);
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(mut file) => {
+                file.write_all(payload.as_bytes())?;
+                return Ok((name, info_path));
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+fn list_trash_dir(root: &Path, trash_dir: Option<PathBuf>) -> Vec<TrashedItem> {
+    let Ok(read_dir) = std::fs::read_dir(info_dir(root)) else {
+        return Vec::new();
+    };
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(TRASH_INFO_EXTENSION) {
+                return None;
+            }
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            let content = std::fs::read_to_string(&path).ok()?;
+            let (original_path, deleted_at) = parse_trash_info(&content)?;
+            if files_dir(root).join(&name).symlink_metadata().is_err() {
+                return None;
+            }
+            Some(TrashedItem {
+                name,
+                original_path,
+                deleted_at,
+                trash_dir: trash_dir.clone(),
+            })
+        })
+        .collect()
+}
+
+fn absolute_path(path: &Path) -> std::io::Result<PathBuf> {
+    if path.is_absolute() {
+        return Ok(path.to_path_buf());
+    }
+    Ok(std::env::current_dir()?.join(path))
+}
+
+/// `/proc/self/mounts` のマウントポイント。空白などは `\040` のような 8 進で書かれている。
+fn parse_mount_points(mounts: &str) -> Vec<PathBuf> {
+    mounts
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(|field| {
+            let bytes = field.as_bytes();
+            let mut decoded = Vec::with_capacity(bytes.len());
+            let mut index = 0;
+            while index < bytes.len() {
+                let octal = field
+                    .get(index + 1..index + 4)
+                    .filter(|_| bytes[index] == b'\\')
+                    .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+                match octal {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 4;
+                    }
+                    None => {
+                        decoded.push(bytes[index]);
+                        index += 1;
+                    }
+                }
+            }
+            path_from_bytes(decoded)
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn mount_topdir(dir: &Path, home_trash: &Path) -> Option<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+
+    let device = std::fs::metadata(dir).ok()?.dev();
+    if std::fs::metadata(home_trash).ok()?.dev() == device {
+        return None;
+    }
+    dir.ancestors()
+        .take_while(|ancestor| {
+            std::fs::metadata(ancestor).is_ok_and(|metadata| metadata.dev() == device)
+        })
+        .last()
+        .map(Path::to_path_buf)
+}
+
+#[cfg(not(unix))]
+fn mount_topdir(_dir: &Path, _home_trash: &Path) -> Option<PathBuf> {
+    None
+}
+
+#[cfg(unix)]
+fn owner_uid(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(path).ok().map(|metadata| metadata.uid())
+}
+
+#[cfg(not(unix))]
+fn owner_uid(_path: &Path) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(path)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(path)
+}
+
+#[cfg(unix)]
+fn path_bytes(path: &Path) -> &[u8] {
+    use std::os::unix::ffi::OsStrExt;
+
+    path.as_os_str().as_bytes()
+}
+
+#[cfg(not(unix))]
+fn path_bytes(path: &Path) -> &[u8] {
+    path.to_str().map(str::as_bytes).unwrap_or_default()
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+
+    PathBuf::from(std::ffi::OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn build_trash_info(original_path: &Path, deleted_at: &str) -> String {
+    format!(
+        "[Trash Info]\nPath={}\nDeletionDate={deleted_at}\n",
+        encode_trash_path(path_bytes(original_path))
+    )
+}
+
+fn parse_trash_info(content: &str) -> Option<(PathBuf, String)> {
+    let mut in_section = false;
+    let mut path = None;
+    let mut deleted_at = String::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_section = trimmed == "[Trash Info]";
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if let Some(value) = trimmed.strip_prefix("Path=") {
+            path = decode_trash_path(value);
+        } else if let Some(value) = trimmed.strip_prefix("DeletionDate=") {
+            deleted_at = value.to_string();
+        }
+    }
+    Some((path?, deleted_at))
+}
+
+fn encode_trash_path(path: &[u8]) -> String {
+    let mut encoded = String::new();
+    for &byte in path {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn decode_trash_path(value: &str) -> Option<PathBuf> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = value.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    Some(path_from_bytes(decoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn encode_and_decode_trash_path_round_trip() {
+        let encoded = encode_trash_path(b"/tmp/My File (1).txt");
+
+        assert_eq!(encoded, "/tmp/My%20File%20%281%29.txt");
+        assert_eq!(
+            decode_trash_path(&encoded),
+            Some(PathBuf::from("/tmp/My File (1).txt"))
+        );
+    }
+
+    #[test]
+    fn trash_moves_entry_and_writes_info() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("note.txt");
+        fs::write(&source, "data").unwrap();
+
+        let item = store.trash(&source).unwrap();
+
+        assert!(!source.exists());
+        assert_eq!(item.name, "note.txt");
+        assert!(temp_dir.path().join("Trash/files/note.txt").exists());
+        let info =
+            fs::read_to_string(temp_dir.path().join("Trash/info/note.txt.trashinfo")).unwrap();
+        assert!(info.starts_with("[Trash Info]\nPath="));
+        assert!(info.contains("DeletionDate="));
+    }
+
+    #[test]
+    fn trash_uses_unique_name_on_collision() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("note.txt");
+        fs::write(&source, "one").unwrap();
+        store.trash(&source).unwrap();
+        fs::write(&source, "two").unwrap();
+
+        let item = store.trash(&source).unwrap();
+
+        assert_eq!(item.name, "note.txt.2");
+        assert_eq!(store.list().len(), 2);
+    }
+
+    #[test]
+    fn restore_moves_entry_back_and_removes_info() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("note.txt");
+        fs::write(&source, "data").unwrap();
+        store.trash(&source).unwrap();
+
+        let item = store.list().into_iter().next().expect("trashed item");
+        let restored = store.restore(&item).unwrap();
+
+        assert_eq!(restored, source);
+        assert_eq!(fs::read_to_string(&source).unwrap(), "data");
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite_existing_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("note.txt");
+        fs::write(&source, "old").unwrap();
+        let item = store.trash(&source).unwrap();
+        fs::write(&source, "new").unwrap();
+
+        let result = store.restore(&item);
+
+        assert!(matches!(result, Err(FileOpError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(&source).unwrap(), "new");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn trash_round_trips_non_utf8_names() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = TrashStore::new(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join(OsStr::from_bytes(b"caf\xe9.txt"));
+        fs::write(&source, "data").unwrap();
+
+        let item = store.trash(&source).unwrap();
+        let info =
+            fs::read_to_string(info_path(&temp_dir.path().join("Trash"), &item.name)).unwrap();
+        assert!(info.contains("caf%E9.txt"));
+
+        let listed = store.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].original_path, source);
+        store.restore(&listed[0]).unwrap();
+        assert_eq!(fs::read_to_string(&source).unwrap(), "data");
+    }
+
+    #[test]
+    fn parse_mount_points_decodes_octal_escapes() {
+        let mounts = "/dev/sda1 / ext4 rw 0 0\n/dev/sdb1 /media/usb\\040disk vfat rw 0 0\n";
+
+        assert_eq!(
+            parse_mount_points(mounts),
+            vec![PathBuf::from("/"), PathBuf::from("/media/usb disk")]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::core::{Entry, GitIgnore, SortMode, list_entries_sorted};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeRequest {
+    pub dir: PathBuf,
+    pub show_hidden: bool,
+    pub hide_ignored: bool,
+    pub sort: SortMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEvent {
+    pub dir: PathBuf,
+    pub result: Result<Vec<Entry>, String>,
+}
+
+#[derive(Debug)]
+pub struct TreeWorker {
+    request_tx: Sender<TreeRequest>,
+    event_rx: Receiver<TreeEvent>,
+}
+
+impl TreeWorker {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<TreeRequest>();
+        let (event_tx, event_rx) = mpsc::channel::<TreeEvent>();
+
+        thread::spawn(move || {
+            for request in request_rx {
+                let result = list_entries_sorted(&request.dir, request.show_hidden, &request.sort)
+                    .map(|mut entries| {
+                        if request.hide_ignored {
+                            let ignore = GitIgnore::for_dir(&request.dir);
+                            entries.retain(|entry| {
+                                !ignore.is_ignored(
+                                    &request.dir.join(&entry.name),
+                                    entry.is_plain_dir(),
+                                )
+                            });
+                        }
+                        entries
+                    })
+                    .map_err(|error| error.to_string());
+                let _ = event_tx.send(TreeEvent {
+                    dir: request.dir,
+                    result,
+                });
+            }
+        });
+
+        Self {
+            request_tx,
+            event_rx,
+        }
+    }
+
+    pub fn request(&self, request: TreeRequest) {
+        let _ = self.request_tx.send(request);
+    }
+
+    pub fn poll(&self) -> Option<TreeEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    #[cfg(test)]
+    pub fn recv_timeout(&self, timeout: std::time::Duration) -> Option<TreeEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn tree_worker_lists_children_in_background() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("main.rs"), "").unwrap();
+        let worker = TreeWorker::new();
+
+        worker.request(TreeRequest {
+            dir: temp_dir.path().to_path_buf(),
+            show_hidden: false,
+            hide_ignored: false,
+            sort: SortMode::default(),
+        });
+        worker.request(TreeRequest {
+            dir: temp_dir.path().join("missing"),
+            show_hidden: false,
+            hide_ignored: false,
+            sort: SortMode::default(),
+        });
+
+        let loaded = worker
+            .event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(loaded.dir, temp_dir.path());
+        let names = loaded
+            .result
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["main.rs".to_string(), "src".to_string()]);
+        let missing = worker
+            .event_rx
+            .recv_timeout(Duration::from_secs(2))
+            .unwrap();
+        assert!(missing.result.is_err());
+    }
+}
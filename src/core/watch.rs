@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const MAX_STAT_ENTRIES: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchEvent {
+    pub dir: PathBuf,
+}
+
+/// 監視対象の基準を取り終えた `watch` の世代。
+type WatchAck = Arc<(Mutex<u64>, Condvar)>;
+
+/// 変更の検知方法。`watch` で対象を差し替え、`changed` で前回から変わったフォルダを返す。
+trait WatchBackend {
+    fn watch(&mut self, dirs: Vec<PathBuf>);
+    fn changed(&mut self) -> Vec<PathBuf>;
+}
+
+#[derive(Debug)]
+pub struct FsWatcher {
+    request_tx: Sender<(u64, Vec<PathBuf>)>,
+    event_rx: Receiver<WatchEvent>,
+    generation: AtomicU64,
+    #[cfg(test)]
+    acked: WatchAck,
+}
+
+impl FsWatcher {
+    pub fn new(interval: Duration) -> Self {
+        Self::with_backend(interval, default_backend)
+    }
+
+    fn with_backend(
+        interval: Duration,
+        backend: impl FnOnce() -> Box<dyn WatchBackend> + Send + 'static,
+    ) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<(u64, Vec<PathBuf>)>();
+        let (event_tx, event_rx) = mpsc::channel::<WatchEvent>();
+        let acked = WatchAck::default();
+        let worker_acked = Arc::clone(&acked);
+
+        thread::spawn(move || {
+            let mut backend = backend();
+            loop {
+                match request_rx.recv_timeout(interval) {
+                    Ok((generation, dirs)) => {
+                        backend.watch(dirs);
+                        let (lock, ready) = &*worker_acked;
+                        if let Ok(mut acked) = lock.lock() {
+                            *acked = generation;
+                            ready.notify_all();
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        for dir in backend.changed() {
+                            let _ = event_tx.send(WatchEvent { dir });
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Self {
+            request_tx,
+            event_rx,
+            generation: AtomicU64::new(0),
+            #[cfg(test)]
+            acked,
+        }
+    }
+
+    pub fn watch(&self, dirs: Vec<PathBuf>) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let _ = self.request_tx.send((generation, dirs));
+    }
+
+    pub fn poll(&self) -> Option<WatchEvent> {
+        self.event_rx.try_recv().ok()
+    }
+
+    #[cfg(test)]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<WatchEvent> {
+        self.event_rx.recv_timeout(timeout).ok()
+    }
+
+    /// 直前の `watch` の基準が取られるまで待つ。
+    #[cfg(test)]
+    pub fn wait_watched(&self, timeout: Duration) -> bool {
+        let generation = self.generation.load(Ordering::Relaxed);
+        let (lock, ready) = &*self.acked;
+        let Ok(acked) = lock.lock() else {
+            return false;
+        };
+        ready
+            .wait_timeout_while(acked, timeout, |acked| *acked < generation)
+            .is_ok_and(|(_, result)| !result.timed_out())
+    }
+}
+
+/// inotify を使えればそれを、使えなければ一覧の指紋を取り直すポーリングを使う。
+fn default_backend() -> Box<dyn WatchBackend> {
+    #[cfg(target_os = "linux")]
+    if let Ok(backend) = inotify::InotifyBackend::new() {
+        return Box::new(backend);
+    }
+    Box::new(PollingBackend::default())
+}
+
+#[derive(Debug, Default)]
+struct PollingBackend {
+    watched: HashMap<PathBuf, Option<u64>>,
+}
+
+impl WatchBackend for PollingBackend {
+    fn watch(&mut self, dirs: Vec<PathBuf>) {
+        self.watched = dirs
+            .into_iter()
+            .map(|dir| {
+                let print = fingerprint(&dir);
+                (dir, print)
+            })
+            .collect();
+    }
+
+    fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (dir, print) in &mut self.watched {
+            let next = fingerprint(dir);
+            if next != *print {
+                *print = next;
+                changed.push(dir.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn fingerprint(dir: &Path) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    std::fs::metadata(dir)
+        .ok()?
+        .modified()
+        .ok()
+        .hash(&mut hasher);
+    let mut children = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+    children.sort_by_key(|child| child.file_name());
+    let stat = children.len() <= MAX_STAT_ENTRIES;
+    for child in children {
+        child.file_name().hash(&mut hasher);
+        if stat && let Ok(metadata) = child.metadata() {
+            metadata.len().hash(&mut hasher);
+            metadata.modified().ok().hash(&mut hasher);
+        }
+    }
+    Some(hasher.finish())
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::collections::{BTreeSet, HashMap};
+    use std::ffi::{CString, c_char, c_int};
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::{AsRawFd, FromRawFd};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::PathBuf;
+
+    use super::{PollingBackend, WatchBackend};
+
+    const IN_NONBLOCK: c_int = 0o4000;
+    const IN_CLOEXEC: c_int = 0o2000000;
+    const IN_MODIFY: u32 = 0x2;
+    const IN_ATTRIB: u32 = 0x4;
+    const IN_CLOSE_WRITE: u32 = 0x8;
+    const IN_MOVED_FROM: u32 = 0x40;
+    const IN_MOVED_TO: u32 = 0x80;
+    const IN_CREATE: u32 = 0x100;
+    const IN_DELETE: u32 = 0x200;
+    const IN_DELETE_SELF: u32 = 0x400;
+    const IN_MOVE_SELF: u32 = 0x800;
+    const IN_Q_OVERFLOW: u32 = 0x4000;
+    const IN_IGNORED: u32 = 0x8000;
+    const IN_ONLYDIR: u32 = 0x0100_0000;
+    const WATCH_MASK: u32 = IN_MODIFY
+        | IN_ATTRIB
+        | IN_CLOSE_WRITE
+        | IN_MOVED_FROM
+        | IN_MOVED_TO
+        | IN_CREATE
+        | IN_DELETE
+        | IN_DELETE_SELF
+        | IN_MOVE_SELF
+        | IN_ONLYDIR;
+    /// `struct inotify_event` の固定部分 (wd, mask, cookie, len)。
+    const EVENT_HEADER_BYTES: usize = 16;
+    const READ_BUFFER_BYTES: usize = 64 * 1024;
+
+    unsafe extern "C" {
+        fn inotify_init1(flags: c_int) -> c_int;
+        fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+        fn inotify_rm_watch(fd: c_int, wd: c_int) -> c_int;
+    }
+
+    /// カーネルから届いたイベントだけを読むので、フォルダの大きさに関係なく確認は安い。
+    /// 監視を追加できなかったフォルダ (上限超過など) はポーリングで見る。
+    #[derive(Debug)]
+    pub(super) struct InotifyBackend {
+        file: File,
+        watches: HashMap<c_int, PathBuf>,
+        fallback: PollingBackend,
+    }
+
+    impl InotifyBackend {
+        pub(super) fn new() -> std::io::Result<Self> {
+            // SAFETY: 引数はフラグのみで、戻り値の fd は検査してから所有権を `File` に移す。
+            let fd = unsafe { inotify_init1(IN_NONBLOCK | IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(Self {
+                // SAFETY: `fd` は直前に開いたもので、ほかに所有者はいない。
+                file: unsafe { File::from_raw_fd(fd) },
+                watches: HashMap::new(),
+                fallback: PollingBackend::default(),
+            })
+        }
+
+        fn add_watch(&self, dir: &std::path::Path) -> Option<c_int> {
+            let path = CString::new(dir.as_os_str().as_bytes()).ok()?;
+            // SAFETY: `path` は NUL 終端の文字列で、呼び出しの間は生きている。
+            let wd = unsafe { inotify_add_watch(self.file.as_raw_fd(), path.as_ptr(), WATCH_MASK) };
+            (wd >= 0).then_some(wd)
+        }
+
+        fn read_events(&mut self) -> BTreeSet<PathBuf> {
+            let mut changed = BTreeSet::new();
+            let mut buffer = vec![0u8; READ_BUFFER_BYTES];
+            loop {
+                let read = match self.file.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(read) => read,
+                    Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(_) => break,
+                };
+                let mut offset = 0;
+                while offset + EVENT_HEADER_BYTES <= read {
+                    let field = |index: usize| {
+                        let start = offset + index * 4;
+                        let mut bytes = [0u8; 4];
+                        bytes.copy_from_slice(&buffer[start..start + 4]);
+                        bytes
+                    };
+                    let wd = c_int::from_ne_bytes(field(0));
+                    let mask = u32::from_ne_bytes(field(1));
+                    let name_len = u32::from_ne_bytes(field(3)) as usize;
+                    offset += EVENT_HEADER_BYTES + name_len;
+                    if mask & IN_Q_OVERFLOW != 0 {
+                        changed.extend(self.watches.values().cloned());
+                        continue;
+                    }
+                    let removed = mask & IN_IGNORED != 0;
+                    let dir = if removed {
+                        self.watches.remove(&wd)
+                    } else {
+                        self.watches.get(&wd).cloned()
+                    };
+                    changed.extend(dir);
+                }
+            }
+            changed
+        }
+    }
+
+    impl WatchBackend for InotifyBackend {
+        fn watch(&mut self, dirs: Vec<PathBuf>) {
+            for wd in std::mem::take(&mut self.watches).into_keys() {
+                // SAFETY: `wd` はこの fd に追加した監視で、失敗しても害はない。
+                unsafe { inotify_rm_watch(self.file.as_raw_fd(), wd) };
+            }
+            self.read_events();
+            let mut unwatched = Vec::new();
+            for dir in dirs {
+                match self.add_watch(&dir) {
+                    Some(wd) => {
+                        self.watches.insert(wd, dir);
+                    }
+                    None => unwatched.push(dir),
+                }
+            }
+            self.fallback.watch(unwatched);
+        }
+
+        fn changed(&mut self) -> Vec<PathBuf> {
+            let mut changed = self.read_events();
+            changed.extend(self.fallback.changed());
+            changed.into_iter().collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_changes_when_entries_change() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let before = fingerprint(temp_dir.path());
+        assert!(before.is_some());
+        assert_eq!(fingerprint(temp_dir.path()), before);
+
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        let added = fingerprint(temp_dir.path());
+        assert_ne!(added, before);
+
+        std::fs::write(temp_dir.path().join("a.txt"), "longer").unwrap();
+        assert_ne!(fingerprint(temp_dir.path()), added);
+        assert_eq!(fingerprint(&temp_dir.path().join("missing")), None);
+    }
+
+    #[test]
+    fn watcher_reports_changed_directories() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let quiet = temp_dir.path().join("quiet");
+        let busy = temp_dir.path().join("busy");
+        std::fs::create_dir(&quiet).unwrap();
+        std::fs::create_dir(&busy).unwrap();
+        let watcher = FsWatcher::new(Duration::from_millis(10));
+        watcher.watch(vec![quiet, busy.clone()]);
+        assert!(watcher.wait_watched(Duration::from_secs(5)));
+
+        std::fs::write(busy.join("new.txt"), "").unwrap();
+
+        let event = watcher.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(event, WatchEvent { dir: busy });
+    }
+
+    #[test]
+    fn polling_backend_reports_changed_directories() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let watcher = FsWatcher::with_backend(Duration::from_millis(10), || {
+            Box::new(PollingBackend::default())
+        });
+        watcher.watch(vec![temp_dir.path().to_path_buf()]);
+        assert!(watcher.wait_watched(Duration::from_secs(5)));
+
+        std::fs::write(temp_dir.path().join("new.txt"), "").unwrap();
+
+        let event = watcher.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(event.dir, temp_dir.path());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn inotify_backend_reports_only_touched_directories() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let quiet = temp_dir.path().join("quiet");
+        let busy = temp_dir.path().join("busy");
+        std::fs::create_dir(&quiet).unwrap();
+        std::fs::create_dir(&busy).unwrap();
+        let mut backend = inotify::InotifyBackend::new().unwrap();
+        backend.watch(vec![quiet, busy.clone()]);
+        assert!(backend.changed().is_empty());
+
+        std::fs::write(busy.join("new.txt"), "").unwrap();
+
+        assert_eq!(backend.changed(), vec![busy.clone()]);
+        assert!(backend.changed().is_empty());
+        std::fs::remove_dir_all(&busy).unwrap();
+        assert_eq!(backend.changed(), vec![busy]);
+    }
+}
@@ -1,6 +1,7 @@
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
-use crate::core::{ColorThemeId, SessionTab};
+use crate::core::{ColorThemeId, EntryFilter, HistoryEntry, NavHistory, SessionTab, SortMode};
 
 #[derive(Debug, Clone)]
 pub(crate) struct TabsState {
@@ -16,6 +17,17 @@ pub(crate) struct Tab {
     id: u64,
     path: PathBuf,
     theme_id: ColorThemeId,
+    marks: BTreeSet<String>,
+    sort: SortMode,
+    filter: Option<EntryFilter>,
+    history: NavHistory,
+    tree: TreeState,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TreeState {
+    pub(crate) enabled: bool,
+    pub(crate) expanded: BTreeSet<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +48,7 @@ pub(crate) enum TabsEvent {
     ActivePathChanged { tab_id: u64, path: PathBuf },
     ActiveThemeChanged { tab_id: u64, theme_id: ColorThemeId },
     TabAdded { tab_id: u64, path: PathBuf },
+    ActiveSortChanged { tab_id: u64, sort: SortMode },
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +67,11 @@ impl TabsState {
                 id: 1,
                 path: current_dir,
                 theme_id,
+                marks: BTreeSet::new(),
+                sort: SortMode::default(),
+                filter: None,
+                history: NavHistory::default(),
+                tree: TreeState::default(),
             }],
             active: 0,
             next_id: 2,
@@ -74,6 +92,11 @@ impl TabsState {
                     id: tab.tab_id,
                     path: tab.path,
                     theme_id,
+                    marks: BTreeSet::new(),
+                    sort: tab.sort,
+                    filter: None,
+                    history: tab.history,
+                    tree: TreeState::default(),
                 }
             })
             .collect::<Vec<_>>();
@@ -101,6 +124,7 @@ impl TabsState {
                 return;
             }
             slot.path = current_dir.to_path_buf();
+            slot.marks.clear();
             self.events.push(TabsEvent::ActivePathChanged {
                 tab_id: slot.id,
                 path: slot.path.clone(),
@@ -113,10 +137,16 @@ impl TabsState {
         let theme_id = self.rotation.next();
         let tab_id = self.next_id;
         let path = current_dir.to_path_buf();
+        let sort = self.active_sort();
         self.tabs.push(Tab {
             id: tab_id,
             path: path.clone(),
             theme_id,
+            marks: BTreeSet::new(),
+            sort,
+            filter: None,
+            history: NavHistory::default(),
+            tree: TreeState::default(),
         });
         self.next_id = self.next_id.saturating_add(1);
         self.active = self.tabs.len().saturating_sub(1);
@@ -185,6 +215,71 @@ impl TabsState {
         tab.color_preference()
     }
 
+    pub(crate) fn active_marks(&self) -> Option<&BTreeSet<String>> {
+        self.tabs.get(self.active).map(|tab| &tab.marks)
+    }
+
+    pub(crate) fn active_marks_mut(&mut self) -> Option<&mut BTreeSet<String>> {
+        self.tabs.get_mut(self.active).map(|tab| &mut tab.marks)
+    }
+
+    pub(crate) fn active_sort(&self) -> SortMode {
+        self.tabs
+            .get(self.active)
+            .map(|tab| tab.sort)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_active_sort(&mut self, sort: SortMode) {
+        if let Some(tab) = self.tabs.get_mut(self.active)
+            && tab.sort != sort
+        {
+            tab.sort = sort;
+            self.events.push(TabsEvent::ActiveSortChanged {
+                tab_id: tab.id,
+                sort,
+            });
+        }
+    }
+
+    pub(crate) fn active_filter(&self) -> Option<&EntryFilter> {
+        self.tabs
+            .get(self.active)
+            .and_then(|tab| tab.filter.as_ref())
+    }
+
+    pub(crate) fn set_active_filter(&mut self, filter: Option<EntryFilter>) {
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            tab.filter = filter;
+        }
+    }
+
+    pub(crate) fn record_visit(&mut self, from: HistoryEntry) {
+        if let Some(tab) = self.tabs.get_mut(self.active) {
+            tab.history.visit(from);
+        }
+    }
+
+    pub(crate) fn history_back(&mut self, current: HistoryEntry) -> Option<HistoryEntry> {
+        self.tabs
+            .get_mut(self.active)
+            .and_then(|tab| tab.history.back(current))
+    }
+
+    pub(crate) fn history_forward(&mut self, current: HistoryEntry) -> Option<HistoryEntry> {
+        self.tabs
+            .get_mut(self.active)
+            .and_then(|tab| tab.history.forward(current))
+    }
+
+    pub(crate) fn active_tree(&self) -> Option<&TreeState> {
+        self.tabs.get(self.active).map(|tab| &tab.tree)
+    }
+
+    pub(crate) fn active_tree_mut(&mut self) -> Option<&mut TreeState> {
+        self.tabs.get_mut(self.active).map(|tab| &mut tab.tree)
+    }
+
     pub(crate) fn session_tabs(&self) -> Vec<SessionTab> {
         self.tabs
             .iter()
@@ -192,6 +287,8 @@ impl TabsState {
                 tab_id: tab.id,
                 path: tab.path.clone(),
                 theme_name: tab.theme_id.name().to_string(),
+                sort: tab.sort,
+                history: tab.history.clone(),
             })
             .collect()
     }
@@ -263,11 +360,21 @@ mod tests {
                     id: 1,
                     path: dir_one,
                     theme_id: ColorThemeId::GlacierCoast,
+                    marks: BTreeSet::new(),
+                    sort: SortMode::default(),
+                    filter: None,
+                    history: NavHistory::default(),
+                    tree: TreeState::default(),
                 },
                 Tab {
                     id: 2,
                     path: dir_two.clone(),
                     theme_id: ColorThemeId::NightHarbor,
+                    marks: BTreeSet::new(),
+                    sort: SortMode::default(),
+                    filter: None,
+                    history: NavHistory::default(),
+                    tree: TreeState::default(),
                 },
             ],
             active: 0,
@@ -283,6 +390,41 @@ mod tests {
         assert_eq!(tabs.tabs[0].path, dir_three);
     }
 
+    #[test]
+    fn marks_are_per_tab_and_cleared_on_path_change() {
+        let dir_one = PathBuf::from("/one");
+        let dir_two = PathBuf::from("/two");
+        let mut tabs = TabsState::new(dir_one.clone(), None);
+        if let Some(marks) = tabs.active_marks_mut() {
+            marks.insert("a.txt".to_string());
+        }
+
+        tabs.push_new(&dir_one);
+        assert!(tabs.active_marks().is_some_and(|marks| marks.is_empty()));
+        tabs.switch_to(0, &dir_one);
+        assert_eq!(tabs.active_marks().map(|marks| marks.len()), Some(1));
+
+        tabs.update_active_path(&dir_two);
+        assert!(tabs.active_marks().is_some_and(|marks| marks.is_empty()));
+    }
+
+    #[test]
+    fn tree_state_is_per_tab() {
+        let dir_one = PathBuf::from("/one");
+        let mut tabs = TabsState::new(dir_one.clone(), None);
+        if let Some(tree) = tabs.active_tree_mut() {
+            tree.enabled = true;
+            tree.expanded.insert(dir_one.join("src"));
+        }
+
+        tabs.push_new(&dir_one);
+        assert_eq!(tabs.active_tree(), Some(&TreeState::default()));
+        tabs.switch_to(0, &dir_one);
+        assert!(tabs.active_tree().is_some_and(|tree| tree.enabled));
+        tabs.update_active_path(&PathBuf::from("/two"));
+        assert_eq!(tabs.active_tree().map(|tree| tree.expanded.len()), Some(1));
+    }
+
     #[test]
     fn summaries_marks_active_tab() {
         let dir_one = PathBuf::from("/one");
@@ -293,11 +435,21 @@ mod tests {
                     id: 1,
                     path: dir_one.clone(),
                     theme_id: ColorThemeId::GlacierCoast,
+                    marks: BTreeSet::new(),
+                    sort: SortMode::default(),
+                    filter: None,
+                    history: NavHistory::default(),
+                    tree: TreeState::default(),
                 },
                 Tab {
                     id: 2,
                     path: dir_two.clone(),
                     theme_id: ColorThemeId::NightHarbor,
+                    marks: BTreeSet::new(),
+                    sort: SortMode::default(),
+                    filter: None,
+                    history: NavHistory::default(),
+                    tree: TreeState::default(),
                 },
             ],
             active: 1,
@@ -331,6 +483,11 @@ mod tests {
             id: 1,
             path: PathBuf::from("/one"),
             theme_id: ColorThemeId::GlacierCoast,
+            marks: BTreeSet::new(),
+            sort: SortMode::default(),
+            filter: None,
+            history: NavHistory::default(),
+            tree: TreeState::default(),
         };
 
         tab.set_theme(ColorThemeId::DeepForest);
@@ -344,4 +501,43 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn filter_is_per_tab_and_survives_path_changes() {
+        let mut tabs = TabsState::new(PathBuf::from("/one"), None);
+        tabs.set_active_filter(Some(EntryFilter::glob("*.rs")));
+
+        tabs.update_active_path(Path::new("/two"));
+        assert_eq!(
+            tabs.active_filter().map(EntryFilter::label),
+            Some("*.rs".to_string())
+        );
+        tabs.push_new(Path::new("/two"));
+        assert!(tabs.active_filter().is_none());
+        tabs.switch_to(0, Path::new("/two"));
+        assert!(tabs.active_filter().is_some());
+        tabs.set_active_filter(None);
+        assert!(tabs.active_filter().is_none());
+    }
+
+    #[test]
+    fn sort_is_per_tab_and_inherited_by_new_tabs() {
+        let dir_one = PathBuf::from("/one");
+        let mut tabs = TabsState::new(dir_one.clone(), None);
+        let sort = SortMode::parse("size reverse").unwrap();
+
+        tabs.set_active_sort(sort);
+        tabs.push_new(&dir_one);
+        assert_eq!(tabs.active_sort(), sort);
+        tabs.set_active_sort(SortMode::default());
+        tabs.switch_to(0, &dir_one);
+
+        assert_eq!(tabs.active_sort(), sort);
+        assert_eq!(tabs.session_tabs()[1].sort, SortMode::default());
+        assert!(
+            tabs.take_events()
+                .iter()
+                .any(|event| matches!(event, TabsEvent::ActiveSortChanged { .. }))
+        );
+    }
 }
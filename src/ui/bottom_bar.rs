@@ -7,10 +7,10 @@ use ratatui::{
     widgets::Paragraph,
 };
 
-use crate::app::{SlashCandidates, SlashFeedback};
+use crate::app::{ConflictPrompt, SlashCandidates, SlashFeedback};
 use crate::core::ColorTheme;
 use crate::core::user_notice::{UserNotice, UserNoticeLevel};
-use crate::core::{EntryMetadata, MetadataStatus};
+use crate::core::{CopyProgress, EntryMetadata, MetadataStatus, SearchMode};
 use crate::tabs::TabSummary;
 use crate::ui::theme::to_color;
 
@@ -21,6 +21,7 @@ pub(crate) struct BottomBarState<'a> {
     pub(crate) git: Option<&'a str>,
     pub(crate) notice: Option<&'a UserNotice>,
     pub(crate) feedback: Option<&'a SlashFeedback>,
+    pub(crate) progress: Option<&'a str>,
 }
 
 impl<'a> BottomBarState<'a> {
@@ -37,8 +38,14 @@ impl<'a> BottomBarState<'a> {
             git,
             notice,
             feedback,
+            progress: None,
         }
     }
+
+    pub(crate) fn with_progress(mut self, progress: Option<&'a str>) -> Self {
+        self.progress = progress;
+        self
+    }
 }
 
 pub fn render_bottom_bar(
@@ -67,13 +74,19 @@ pub fn render_slash_bar(
     frame.render_widget(bar, area);
 }
 
-pub fn render_search_bar(frame: &mut Frame<'_>, area: Rect, input: &str, theme: &ColorTheme) {
+pub fn render_search_bar(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    input: &str,
+    mode: SearchMode,
+    theme: &ColorTheme,
+) {
     let suffix_style = Style::default()
         .fg(to_color(theme.grayscale.high))
         .add_modifier(Modifier::DIM);
     let bar = Paragraph::new(build_search_bar_line(
         input,
-        " - incremental search",
+        &format!(" - incremental search ({})", mode.name()),
         suffix_style,
         area.width,
     ))
@@ -81,6 +94,12 @@ pub fn render_search_bar(frame: &mut Frame<'_>, area: Rect, input: &str, theme:
     frame.render_widget(bar, area);
 }
 
+pub fn render_confirm_bar(frame: &mut Frame<'_>, area: Rect, prompt: &str, theme: &ColorTheme) {
+    let style = slash_bar_style(theme).fg(to_color(theme.semantic.warn));
+    let bar = Paragraph::new(prompt.to_string()).style(style);
+    frame.render_widget(bar, area);
+}
+
 fn build_bottom_bar(state: &BottomBarState<'_>, width: u16, theme: &ColorTheme) -> Line<'static> {
     let default_style = Style::default().fg(to_color(theme.grayscale.high));
     let mut left_spans = Vec::new();
@@ -114,6 +133,15 @@ fn build_bottom_bar(state: &BottomBarState<'_>, width: u16, theme: &ColorTheme)
             left_spans.push(Span::styled(text, style));
         }
     }
+    if let Some(progress) = state.progress {
+        if !left_spans.is_empty() {
+            left_spans.push(Span::styled(" | ", default_style));
+        }
+        left_spans.push(Span::styled(
+            progress.to_string(),
+            Style::default().fg(to_color(theme.semantic.info)),
+        ));
+    }
     let (metadata_text, metadata_style) =
         metadata_parts(state.metadata, state.metadata_status, theme);
     if !metadata_text.is_empty() {
@@ -295,6 +323,35 @@ fn format_tabs(tabs: &[TabSummary]) -> String {
     format!("tabs: {}", entries.join(" "))
 }
 
+pub fn format_conflict_lines(prompt: &ConflictPrompt) -> Vec<String> {
+    let describe = |metadata: Option<&EntryMetadata>| {
+        metadata
+            .map(format_metadata)
+            .unwrap_or_else(|| "metadata: unavailable".to_string())
+    };
+    vec![
+        format!("conflict {}/{}", prompt.index, prompt.total),
+        format!("source: {}", prompt.source.display()),
+        format!("  {}", describe(prompt.source_metadata.as_ref())),
+        format!("target: {}", prompt.target.display()),
+        format!("  {}", describe(prompt.target_metadata.as_ref())),
+    ]
+}
+
+pub fn format_conflict_choices(prompt: &ConflictPrompt) -> String {
+    let all = if prompt.apply_to_all { "on" } else { "off" };
+    format!("exists: [o]verwrite [s]kip [k]eep both [n]ewer only | [a]ll: {all} | Esc: cancel")
+}
+
+pub fn format_copy_progress(progress: &CopyProgress) -> String {
+    format!(
+        "copy: {}% ({}/{})",
+        progress.percent(),
+        progress.done_files,
+        progress.total_files
+    )
+}
+
 pub fn format_metadata(metadata: &EntryMetadata) -> String {
     format!(
         "size: {} | modified: {}",
@@ -489,6 +546,67 @@ mod tests {
         assert!(line.contains("size: 7 B"));
     }
 
+    #[test]
+    fn format_conflict_lines_show_both_sides() {
+        let prompt = ConflictPrompt {
+            source: std::path::PathBuf::from("/src/a.txt"),
+            target: std::path::PathBuf::from("/dest/a.txt"),
+            source_metadata: Some(EntryMetadata {
+                size: 7,
+                modified: std::time::SystemTime::UNIX_EPOCH,
+            }),
+            target_metadata: None,
+            index: 1,
+            total: 2,
+            apply_to_all: true,
+        };
+
+        let lines = format_conflict_lines(&prompt);
+
+        assert_eq!(lines[0], "conflict 1/2");
+        assert!(lines[2].contains("size: 7 B"));
+        assert_eq!(lines[4], "  metadata: unavailable");
+        assert!(format_conflict_choices(&prompt).contains("[a]ll: on"));
+    }
+
+    #[test]
+    fn format_copy_progress_shows_percent_and_files() {
+        let progress = CopyProgress {
+            id: 1,
+            done_files: 1,
+            total_files: 5,
+            done_bytes: 20,
+            total_bytes: 100,
+            current: None,
+        };
+
+        assert_eq!(format_copy_progress(&progress), "copy: 20% (1/5)");
+    }
+
+    #[test]
+    fn render_bottom_bar_shows_copy_progress() {
+        let backend = TestBackend::new(60, 1);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let area = Rect::new(0, 0, 60, 1);
+        let theme = ColorThemeId::GlacierCoast.theme();
+        terminal
+            .draw(|frame| {
+                render_bottom_bar(
+                    frame,
+                    area,
+                    BottomBarState::new(None, None, None, None, None)
+                        .with_progress(Some("copy: 40% (2/5)")),
+                    &theme,
+                )
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let line = buffer_line(buffer, 0, 60);
+
+        assert!(line.contains("copy: 40% (2/5)"));
+    }
+
     #[test]
     fn render_bottom_bar_shows_user_notice() {
         let notice = UserNotice::new(UserNoticeLevel::Info, "exit=0", "shell");
@@ -700,14 +818,14 @@ mod tests {
         let area = Rect::new(0, 0, 40, 1);
         let theme = ColorThemeId::GlacierCoast.theme();
         terminal
-            .draw(|frame| render_search_bar(frame, area, "alpha", &theme))
+            .draw(|frame| render_search_bar(frame, area, "alpha", SearchMode::Fuzzy, &theme))
             .unwrap();
 
         let buffer = terminal.backend().buffer();
         let line = buffer_line(buffer, 0, 40);
 
         assert!(line.contains("alpha"));
-        assert!(line.contains("incremental search"));
+        assert!(line.contains("incremental search (fuzzy)"));
     }
 
     #[test]
@@ -717,7 +835,7 @@ mod tests {
         let area = Rect::new(0, 0, 40, 1);
         let theme = ColorThemeId::GlacierCoast.theme();
         terminal
-            .draw(|frame| render_search_bar(frame, area, "alpha", &theme))
+            .draw(|frame| render_search_bar(frame, area, "alpha", SearchMode::Fuzzy, &theme))
             .unwrap();
 
         let buffer = terminal.backend().buffer();
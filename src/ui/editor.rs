@@ -0,0 +1,50 @@
+use std::path::Path;
+use std::process::Command;
+
+const DEFAULT_EDITOR: &str = "vi";
+
+pub fn run_editor(path: &Path) -> Result<(), String> {
+    let editor = std::env::var("VISUAL")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| std::env::var("EDITOR").ok());
+    let argv = editor_argv(editor.as_deref());
+    let Some((program, args)) = argv.split_first() else {
+        return Err("no editor".to_string());
+    };
+    let status = Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .map_err(|error| format!("{program}: {error}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{program}: {status}"))
+    }
+}
+
+fn editor_argv(editor: Option<&str>) -> Vec<String> {
+    let argv = editor
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if argv.is_empty() {
+        vec![DEFAULT_EDITOR.to_string()]
+    } else {
+        argv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editor_argv_splits_args_and_falls_back_to_vi() {
+        assert_eq!(editor_argv(Some("code -w")), vec!["code", "-w"]);
+        assert_eq!(editor_argv(Some("  ")), vec!["vi"]);
+        assert_eq!(editor_argv(None), vec!["vi"]);
+    }
+}
@@ -1,5 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
+use crate::core::ConflictChoice;
+
 pub fn is_quit_event(key: KeyEvent) -> bool {
     key.kind == KeyEventKind::Press
         && key.code == KeyCode::Char('q')
@@ -31,9 +33,7 @@ pub fn is_enter_dir_event(key: KeyEvent) -> bool {
         return false;
     }
     key.code == KeyCode::Right
-        && (key.modifiers.is_empty()
-            || key.modifiers.contains(KeyModifiers::ALT)
-            || key.modifiers.contains(KeyModifiers::SUPER))
+        && (key.modifiers.is_empty() || key.modifiers.contains(KeyModifiers::SUPER))
 }
 
 pub fn is_parent_event(key: KeyEvent) -> bool {
@@ -41,9 +41,19 @@ pub fn is_parent_event(key: KeyEvent) -> bool {
         return false;
     }
     key.code == KeyCode::Left
-        && (key.modifiers.is_empty()
-            || key.modifiers.contains(KeyModifiers::ALT)
-            || key.modifiers.contains(KeyModifiers::SUPER))
+        && (key.modifiers.is_empty() || key.modifiers.contains(KeyModifiers::SUPER))
+}
+
+pub fn is_history_back_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Left
+        && key.modifiers.contains(KeyModifiers::ALT)
+}
+
+pub fn is_history_forward_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Right
+        && key.modifiers.contains(KeyModifiers::ALT)
 }
 
 pub fn is_toggle_hidden_event(key: KeyEvent) -> bool {
@@ -52,12 +62,24 @@ pub fn is_toggle_hidden_event(key: KeyEvent) -> bool {
         && key.modifiers.contains(KeyModifiers::CONTROL)
 }
 
+pub fn is_toggle_ignored_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Char('g')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
 pub fn is_new_tab_event(key: KeyEvent) -> bool {
     key.kind == KeyEventKind::Press
         && key.code == KeyCode::Char('t')
         && key.modifiers.contains(KeyModifiers::CONTROL)
 }
 
+pub fn is_bookmark_picker_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Char('b')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
 pub fn is_shell_output_toggle_event(key: KeyEvent) -> bool {
     key.kind == KeyEventKind::Press
         && key.code == KeyCode::Char('o')
@@ -163,6 +185,73 @@ pub fn is_search_backspace_event(key: KeyEvent) -> bool {
     key.kind == KeyEventKind::Press && key.code == KeyCode::Backspace
 }
 
+pub fn is_search_next_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Char('n')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+pub fn is_search_prev_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Char('p')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+pub fn is_toggle_mark_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && matches!(key.code, KeyCode::Tab | KeyCode::Insert)
+        && key.modifiers.is_empty()
+}
+
+pub fn is_extend_selection_up_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Up
+        && key.modifiers.contains(KeyModifiers::SHIFT)
+}
+
+pub fn is_extend_selection_down_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Down
+        && key.modifiers.contains(KeyModifiers::SHIFT)
+}
+
+pub fn is_select_all_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Char('a')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+pub fn is_delete_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press && key.code == KeyCode::Delete && key.modifiers.is_empty()
+}
+
+pub fn is_permanent_delete_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press
+        && key.code == KeyCode::Delete
+        && key.modifiers.contains(KeyModifiers::SHIFT)
+}
+
+pub fn is_confirm_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press && matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y'))
+}
+
+pub fn conflict_choice_event(key: KeyEvent) -> Option<ConflictChoice> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char('o') => Some(ConflictChoice::Overwrite),
+        KeyCode::Char('s') => Some(ConflictChoice::Skip),
+        KeyCode::Char('k') => Some(ConflictChoice::KeepBoth),
+        KeyCode::Char('n') => Some(ConflictChoice::OverwriteIfNewer),
+        _ => None,
+    }
+}
+
+pub fn is_conflict_apply_all_event(key: KeyEvent) -> bool {
+    key.kind == KeyEventKind::Press && key.code == KeyCode::Char('a')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,9 +263,10 @@ mod tests {
     }
 
     #[test]
-    fn is_enter_dir_event_requires_alt_right() {
+    fn alt_right_goes_forward_instead_of_entering() {
         let key = KeyEvent::new(KeyCode::Right, KeyModifiers::ALT);
-        assert!(is_enter_dir_event(key));
+        assert!(!is_enter_dir_event(key));
+        assert!(is_history_forward_event(key));
     }
 
     #[test]
@@ -210,9 +300,10 @@ mod tests {
     }
 
     #[test]
-    fn is_parent_event_requires_alt_left() {
+    fn alt_left_goes_back_instead_of_parent() {
         let key = KeyEvent::new(KeyCode::Left, KeyModifiers::ALT);
-        assert!(is_parent_event(key));
+        assert!(!is_parent_event(key));
+        assert!(is_history_back_event(key));
     }
 
     #[test]
@@ -245,12 +336,28 @@ mod tests {
         assert!(!is_toggle_hidden_event(key));
     }
 
+    #[test]
+    fn is_toggle_ignored_event_requires_ctrl_g() {
+        let key = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL);
+        assert!(is_toggle_ignored_event(key));
+        let key = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE);
+        assert!(!is_toggle_ignored_event(key));
+    }
+
     #[test]
     fn is_new_tab_event_accepts_ctrl_t() {
         let key = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL);
         assert!(is_new_tab_event(key));
     }
 
+    #[test]
+    fn is_bookmark_picker_event_accepts_ctrl_b() {
+        let key = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL);
+        assert!(is_bookmark_picker_event(key));
+        let key = KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE);
+        assert!(!is_bookmark_picker_event(key));
+    }
+
     #[test]
     fn is_shell_output_toggle_event_accepts_ctrl_o() {
         let key = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
@@ -377,9 +484,75 @@ mod tests {
         assert!(is_search_reset_event(key));
     }
 
+    #[test]
+    fn search_next_and_prev_events_accept_ctrl_n_and_p() {
+        let next = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        let prev = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert!(is_search_next_event(next));
+        assert!(is_search_prev_event(prev));
+        assert!(!is_search_next_event(KeyEvent::new(
+            KeyCode::Char('n'),
+            KeyModifiers::NONE
+        )));
+    }
+
     #[test]
     fn is_search_backspace_event_accepts_backspace() {
         let key = KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE);
         assert!(is_search_backspace_event(key));
     }
+
+    #[test]
+    fn is_delete_event_distinguishes_shift() {
+        let plain = KeyEvent::new(KeyCode::Delete, KeyModifiers::NONE);
+        let shifted = KeyEvent::new(KeyCode::Delete, KeyModifiers::SHIFT);
+        assert!(is_delete_event(plain));
+        assert!(!is_delete_event(shifted));
+        assert!(is_permanent_delete_event(shifted));
+        assert!(!is_permanent_delete_event(plain));
+    }
+
+    #[test]
+    fn is_confirm_event_accepts_only_y() {
+        assert!(is_confirm_event(KeyEvent::new(
+            KeyCode::Char('y'),
+            KeyModifiers::NONE
+        )));
+        assert!(!is_confirm_event(KeyEvent::new(
+            KeyCode::Char('n'),
+            KeyModifiers::NONE
+        )));
+    }
+
+    #[test]
+    fn selection_events_use_tab_shift_arrows_and_ctrl_a() {
+        assert!(is_toggle_mark_event(KeyEvent::new(
+            KeyCode::Tab,
+            KeyModifiers::NONE
+        )));
+        assert!(is_extend_selection_down_event(KeyEvent::new(
+            KeyCode::Down,
+            KeyModifiers::SHIFT
+        )));
+        assert!(!is_extend_selection_up_event(KeyEvent::new(
+            KeyCode::Up,
+            KeyModifiers::NONE
+        )));
+        assert!(is_select_all_event(KeyEvent::new(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL
+        )));
+    }
+
+    #[test]
+    fn conflict_keys_map_to_choices() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        assert_eq!(
+            conflict_choice_event(key(KeyCode::Char('k'))),
+            Some(ConflictChoice::KeepBoth)
+        );
+        assert_eq!(conflict_choice_event(key(KeyCode::Char('x'))), None);
+        assert!(is_conflict_apply_all_event(key(KeyCode::Char('a'))));
+    }
 }
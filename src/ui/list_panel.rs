@@ -0,0 +1,66 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::core::ColorTheme;
+use crate::ui::theme::to_color;
+
+pub fn render_list_panel(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    title: &str,
+    lines: &[String],
+    cursor: Option<usize>,
+    theme: &ColorTheme,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height == 0 || inner.width == 0 {
+        return;
+    }
+    let height = inner.height as usize;
+    let offset = list_offset(cursor, height);
+    let selected_style = Style::default()
+        .fg(to_color(theme.primary))
+        .add_modifier(Modifier::REVERSED);
+    let visible = lines
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(height)
+        .map(|(index, line)| {
+            if Some(index) == cursor {
+                Line::styled(line.clone(), selected_style)
+            } else {
+                Line::raw(line.clone())
+            }
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(Paragraph::new(visible), inner);
+}
+
+fn list_offset(cursor: Option<usize>, height: usize) -> usize {
+    match cursor {
+        Some(cursor) if height > 0 && cursor >= height => cursor + 1 - height,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_offset_keeps_cursor_visible() {
+        assert_eq!(list_offset(Some(2), 5), 0);
+        assert_eq!(list_offset(Some(7), 5), 3);
+        assert_eq!(list_offset(None, 5), 0);
+    }
+}
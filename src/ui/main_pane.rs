@@ -1,18 +1,25 @@
+use std::collections::BTreeSet;
+
 use ratatui::{
     Frame,
     layout::Rect,
     style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState},
 };
 
-use crate::core::{ColorTheme, Entry};
+use crate::core::{ColorTheme, Entry, EntryKind, SearchMatcher, SearchMode};
 use crate::ui::theme::to_color;
 
+const MARK_PREFIX: &str = "+ ";
+
 pub struct EntryListParams<'a> {
     pub entries: &'a [Entry],
     pub cursor: Option<usize>,
+    pub marked: &'a BTreeSet<String>,
     pub title: &'a str,
     pub search_text: &'a str,
+    pub search_mode: SearchMode,
     pub theme: &'a ColorTheme,
     pub active: bool,
 }
@@ -23,15 +30,37 @@ pub fn entry_list_view_height(area: Rect) -> usize {
 }
 
 pub fn render_entry_list(frame: &mut Frame<'_>, area: Rect, params: &EntryListParams<'_>) {
-    let matches = search_matches(params.entries, params.search_text);
+    let matcher = SearchMatcher::new(params.search_mode, params.search_text);
+    let guides = tree_guides(params.entries);
     let items: Vec<ListItem> = params
         .entries
         .iter()
+        .zip(&guides)
         .enumerate()
-        .map(|(index, entry)| {
-            let mut item = ListItem::new(display_name(entry));
-            if matches.len() > 1 && matches.iter().skip(1).any(|&hit| hit == index) {
+        .map(|(index, (entry, guide))| {
+            let marked = params.marked.contains(&entry.name);
+            let positions = matcher
+                .as_ref()
+                .and_then(|matcher| matcher.find(entry.display_name()));
+            let name = display_name(entry, marked, guide);
+            let mut item = match &positions {
+                Some(positions) => {
+                    let marker_width = if marked {
+                        MARK_PREFIX.chars().count()
+                    } else {
+                        0
+                    };
+                    let offset = marker_width + guide.chars().count();
+                    ListItem::new(highlight_line(&name, positions, offset))
+                }
+                None => ListItem::new(name),
+            };
+            if marked {
+                item = item.style(marked_style(params.theme));
+            } else if positions.is_some() && params.cursor != Some(index) {
                 item = item.style(secondary_match_style(params.theme));
+            } else if let Some(style) = kind_style(entry.kind, params.theme) {
+                item = item.style(style);
             }
             item
         })
@@ -72,12 +101,53 @@ pub fn render_entry_list(frame: &mut Frame<'_>, area: Rect, params: &EntryListPa
     // ListState is handled above to keep footer aligned inside the border.
 }
 
-fn display_name(entry: &Entry) -> String {
-    if entry.is_dir {
-        format!("{}/", entry.name)
-    } else {
-        entry.name.clone()
+fn display_name(entry: &Entry, marked: bool, guide: &str) -> String {
+    let marker = if marked { MARK_PREFIX } else { "" };
+    let name = entry.display_name();
+    let suffix = entry.kind.suffix();
+    match entry.link_target.as_ref() {
+        Some(target) => format!("{marker}{guide}{name}{suffix} -> {}", target.display()),
+        None => format!("{marker}{guide}{name}{suffix}"),
+    }
+}
+
+fn kind_style(kind: EntryKind, theme: &ColorTheme) -> Option<Style> {
+    let style = match kind {
+        EntryKind::Symlink => Style::default().fg(to_color(theme.semantic.info)),
+        EntryKind::BrokenSymlink => Style::default()
+            .fg(to_color(theme.semantic.error))
+            .add_modifier(Modifier::CROSSED_OUT),
+        EntryKind::Executable => Style::default()
+            .fg(to_color(theme.semantic.success))
+            .add_modifier(Modifier::BOLD),
+        EntryKind::Fifo | EntryKind::Socket => Style::default().fg(to_color(theme.semantic.warn)),
+        EntryKind::File | EntryKind::Dir | EntryKind::Other => return None,
+    };
+    Some(style)
+}
+
+fn tree_guides(entries: &[Entry]) -> Vec<String> {
+    let mut guides = vec![String::new(); entries.len()];
+    let mut open: Vec<bool> = Vec::new();
+    for (index, entry) in entries.iter().enumerate().rev() {
+        let depth = entry.depth();
+        open.resize(depth + 1, false);
+        if depth > 0 {
+            let guide = &mut guides[index];
+            for &continues in &open[1..depth] {
+                guide.push_str(if continues { "│  " } else { "   " });
+            }
+            guide.push_str(if open[depth] { "├─ " } else { "└─ " });
+        }
+        open[depth] = true;
     }
+    guides
+}
+
+fn marked_style(theme: &ColorTheme) -> Style {
+    Style::default()
+        .fg(to_color(theme.secondary))
+        .add_modifier(Modifier::BOLD)
 }
 
 fn highlight_style(search_active: bool, theme: &ColorTheme) -> Style {
@@ -101,21 +171,35 @@ fn highlight_symbol(search_text: &str) -> &'static str {
     if search_text.is_empty() { "> " } else { "? " }
 }
 
-fn search_matches(entries: &[Entry], search_text: &str) -> Vec<usize> {
-    if search_text.is_empty() {
-        return Vec::new();
+fn highlight_line(text: &str, positions: &[usize], offset: usize) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_hit = false;
+    for (index, ch) in text.chars().enumerate() {
+        let hit = index
+            .checked_sub(offset)
+            .is_some_and(|position| positions.contains(&position));
+        if hit != current_hit && !current.is_empty() {
+            spans.push(match_span(std::mem::take(&mut current), current_hit));
+        }
+        current_hit = hit;
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        spans.push(match_span(current, current_hit));
+    }
+    Line::from(spans)
+}
+
+fn match_span(text: String, hit: bool) -> Span<'static> {
+    if hit {
+        Span::styled(
+            text,
+            Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        )
+    } else {
+        Span::raw(text)
     }
-    entries
-        .iter()
-        .enumerate()
-        .filter_map(|(index, entry)| {
-            if entry.name.starts_with(search_text) {
-                Some(index)
-            } else {
-                None
-            }
-        })
-        .collect()
 }
 
 #[cfg(test)]
@@ -139,16 +223,7 @@ mod tests {
     fn render_directory_list_shows_entries() {
         let backend = TestBackend::new(20, 5);
         let mut terminal = Terminal::new(backend).unwrap();
-        let entries = vec![
-            Entry {
-                name: "a.txt".to_string(),
-                is_dir: false,
-            },
-            Entry {
-                name: "b.txt".to_string(),
-                is_dir: false,
-            },
-        ];
+        let entries = vec![Entry::file("a.txt"), Entry::file("b.txt")];
 
         let area = Rect::new(0, 0, 20, 5);
         let theme = ColorThemeId::GlacierCoast.theme();
@@ -157,8 +232,10 @@ mod tests {
                 let params = EntryListParams {
                     entries: &entries,
                     cursor: Some(0),
+                    marked: &BTreeSet::new(),
                     title: "current",
                     search_text: "",
+                    search_mode: SearchMode::Prefix,
                     theme: &theme,
                     active: true,
                 };
@@ -177,16 +254,7 @@ mod tests {
     fn render_directory_list_highlights_selected_item() {
         let backend = TestBackend::new(20, 5);
         let mut terminal = Terminal::new(backend).unwrap();
-        let entries = vec![
-            Entry {
-                name: "a.txt".to_string(),
-                is_dir: false,
-            },
-            Entry {
-                name: "b.txt".to_string(),
-                is_dir: false,
-            },
-        ];
+        let entries = vec![Entry::file("a.txt"), Entry::file("b.txt")];
 
         let area = Rect::new(0, 0, 20, 5);
         let theme = ColorThemeId::GlacierCoast.theme();
@@ -195,8 +263,10 @@ mod tests {
                 let params = EntryListParams {
                     entries: &entries,
                     cursor: Some(0),
+                    marked: &BTreeSet::new(),
                     title: "current",
                     search_text: "",
+                    search_mode: SearchMode::Prefix,
                     theme: &theme,
                     active: true,
                 };
@@ -214,10 +284,7 @@ mod tests {
     fn render_directory_list_adds_trailing_slash_for_directories() {
         let backend = TestBackend::new(20, 5);
         let mut terminal = Terminal::new(backend).unwrap();
-        let entries = vec![Entry {
-            name: "docs".to_string(),
-            is_dir: true,
-        }];
+        let entries = vec![Entry::dir("docs")];
 
         let area = Rect::new(0, 0, 20, 5);
         let theme = ColorThemeId::GlacierCoast.theme();
@@ -226,8 +293,10 @@ mod tests {
                 let params = EntryListParams {
                     entries: &entries,
                     cursor: Some(0),
+                    marked: &BTreeSet::new(),
                     title: "current",
                     search_text: "",
+                    search_mode: SearchMode::Prefix,
                     theme: &theme,
                     active: true,
                 };
@@ -241,6 +310,86 @@ mod tests {
         assert!(content.contains("docs/"));
     }
 
+    #[test]
+    fn render_directory_list_shows_kind_suffixes_and_link_targets() {
+        let backend = TestBackend::new(30, 7);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let entry = |name: &str, kind: EntryKind, link_target: Option<&str>| Entry {
+            name: name.to_string(),
+            is_dir: false,
+            kind,
+            link_target: link_target.map(std::path::PathBuf::from),
+        };
+        let entries = vec![
+            entry("build.sh", EntryKind::Executable, None),
+            entry("gone", EntryKind::BrokenSymlink, Some("old")),
+            entry("pipe", EntryKind::Fifo, None),
+            entry("sock", EntryKind::Socket, None),
+        ];
+
+        let area = Rect::new(0, 0, 30, 7);
+        let theme = ColorThemeId::GlacierCoast.theme();
+        terminal
+            .draw(|frame| {
+                let params = EntryListParams {
+                    entries: &entries,
+                    cursor: None,
+                    marked: &BTreeSet::new(),
+                    title: "current",
+                    search_text: "",
+                    search_mode: SearchMode::Prefix,
+                    theme: &theme,
+                    active: true,
+                };
+                render_entry_list(frame, area, &params)
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content = buffer_text(buffer, 30, 7);
+
+        assert!(content.contains("build.sh*"));
+        assert!(content.contains("gone@ -> old"));
+        assert!(content.contains("pipe|"));
+        assert!(content.contains("sock="));
+        let broken = find_cell_style(buffer, "gone@").unwrap();
+        assert_eq!(broken.fg, Some(to_color(theme.semantic.error)));
+        assert!(broken.add_modifier.contains(Modifier::CROSSED_OUT));
+    }
+
+    #[test]
+    fn render_directory_list_prefixes_marked_entries() {
+        let backend = TestBackend::new(20, 5);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let entries = vec![Entry::file("a.txt"), Entry::file("b.txt")];
+        let marked = BTreeSet::from(["b.txt".to_string()]);
+
+        let area = Rect::new(0, 0, 20, 5);
+        let theme = ColorThemeId::GlacierCoast.theme();
+        terminal
+            .draw(|frame| {
+                let params = EntryListParams {
+                    entries: &entries,
+                    cursor: Some(0),
+                    marked: &marked,
+                    title: "current",
+                    search_text: "",
+                    search_mode: SearchMode::Prefix,
+                    theme: &theme,
+                    active: true,
+                };
+                render_entry_list(frame, area, &params)
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content = buffer_text(buffer, 20, 5);
+        let style = find_cell_style(buffer, "+ b.txt").expect("style not found");
+
+        assert!(!content.contains("+ a.txt"));
+        assert_eq!(style.fg, Some(to_color(theme.secondary)));
+    }
+
     #[test]
     fn highlight_style_changes_when_search_active() {
         let theme = ColorThemeId::GlacierCoast.theme();
@@ -263,18 +412,9 @@ mod tests {
         let backend = TestBackend::new(24, 6);
         let mut terminal = Terminal::new(backend).unwrap();
         let entries = vec![
-            Entry {
-                name: "alpha.txt".to_string(),
-                is_dir: false,
-            },
-            Entry {
-                name: "beta.txt".to_string(),
-                is_dir: false,
-            },
-            Entry {
-                name: "bravo.txt".to_string(),
-                is_dir: false,
-            },
+            Entry::file("alpha.txt"),
+            Entry::file("beta.txt"),
+            Entry::file("bravo.txt"),
         ];
 
         let area = Rect::new(0, 0, 24, 6);
@@ -284,8 +424,10 @@ mod tests {
                 let params = EntryListParams {
                     entries: &entries,
                     cursor: Some(1),
+                    marked: &BTreeSet::new(),
                     title: "current",
                     search_text: "b",
+                    search_mode: SearchMode::Prefix,
                     theme: &theme,
                     active: true,
                 };
@@ -304,10 +446,7 @@ mod tests {
     fn render_search_footer_is_removed() {
         let backend = TestBackend::new(24, 6);
         let mut terminal = Terminal::new(backend).unwrap();
-        let entries = vec![Entry {
-            name: "alpha.txt".to_string(),
-            is_dir: false,
-        }];
+        let entries = vec![Entry::file("alpha.txt")];
 
         let area = Rect::new(0, 0, 24, 6);
         let theme = ColorThemeId::GlacierCoast.theme();
@@ -316,8 +455,10 @@ mod tests {
                 let params = EntryListParams {
                     entries: &entries,
                     cursor: Some(0),
+                    marked: &BTreeSet::new(),
                     title: "current",
                     search_text: "al",
+                    search_mode: SearchMode::Prefix,
                     theme: &theme,
                     active: true,
                 };
@@ -330,6 +471,63 @@ mod tests {
         assert!(!content.contains("search: al"));
     }
 
+    #[test]
+    fn matched_characters_are_underlined() {
+        let backend = TestBackend::new(24, 6);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let entries = vec![Entry::file("alpha.txt"), Entry::file("graph.rs")];
+
+        let area = Rect::new(0, 0, 24, 6);
+        let theme = ColorThemeId::GlacierCoast.theme();
+        terminal
+            .draw(|frame| {
+                let params = EntryListParams {
+                    entries: &entries,
+                    cursor: Some(0),
+                    marked: &BTreeSet::new(),
+                    title: "current",
+                    search_text: "ph",
+                    search_mode: SearchMode::Substring,
+                    theme: &theme,
+                    active: true,
+                };
+                render_entry_list(frame, area, &params)
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let hit = find_cell_style(buffer, "ph.rs").expect("style not found");
+        let miss = find_cell_style(buffer, "gra").expect("style not found");
+
+        assert!(hit.add_modifier.contains(Modifier::UNDERLINED));
+        assert!(!miss.add_modifier.contains(Modifier::UNDERLINED));
+        assert_eq!(miss.fg, Some(to_color(theme.secondary)));
+    }
+
+    #[test]
+    fn tree_guides_connect_nested_entries() {
+        let entries = [
+            "docs",
+            "src",
+            "src/core",
+            "src/core/mod.rs",
+            "src/main.rs",
+            "z.txt",
+        ]
+        .map(|name| {
+            if name.contains('.') {
+                Entry::file(name)
+            } else {
+                Entry::dir(name)
+            }
+        });
+
+        let guides = tree_guides(&entries);
+
+        assert_eq!(guides, vec!["", "", "├─ ", "│  └─ ", "└─ ", ""]);
+        assert_eq!(display_name(&entries[3], false, &guides[3]), "│  └─ mod.rs");
+    }
+
     fn buffer_text(buffer: &Buffer, width: u16, height: u16) -> String {
         (0..height)
             .flat_map(|y| (0..width).map(move |x| buffer[(x, y)].symbol().to_string()))
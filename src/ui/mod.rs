@@ -1,19 +1,23 @@
 mod bottom_bar;
+mod editor;
 mod event;
 mod layout;
+mod list_panel;
 mod main_pane;
 mod metadata_worker;
+mod paste;
 mod preview_pane;
 mod preview_worker;
 mod shell_output_view;
 mod theme;
 mod top_bar;
 
-use std::io::{self, Stdout};
+use std::collections::BTreeSet;
+use std::io::{self, Stdout, Write};
 
 use crossterm::{
     cursor::{Hide, Show},
-    event::{self as crossterm_event, Event},
+    event::{self as crossterm_event, DisableBracketedPaste, EnableBracketedPaste, Event},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -28,21 +32,31 @@ use crate::{
 use crate::core::{
     FetchPriority, GitWorker, MetadataFetchResult, MetadataSnapshot, MetadataStatus,
     MetadataWindow, PreviewEvent, PreviewFailed, PreviewReady, PreviewRequest, RequestId,
-    RequestTracker,
+    RequestTracker, osc52_sequence,
 };
-use bottom_bar::{format_metadata, render_bottom_bar, render_search_bar, render_slash_bar};
+use bottom_bar::{
+    format_conflict_choices, format_conflict_lines, format_copy_progress, format_metadata,
+    render_bottom_bar, render_confirm_bar, render_search_bar, render_slash_bar,
+};
+use editor::run_editor;
 use event::{
+    conflict_choice_event, is_bookmark_picker_event, is_confirm_event, is_conflict_apply_all_event,
     is_cursor_down_event, is_cursor_left_event, is_cursor_right_event, is_cursor_up_event,
-    is_end_event, is_enter_dir_event, is_enter_event, is_home_event, is_new_tab_event,
-    is_next_tab_event, is_page_down_event, is_page_up_event, is_parent_event, is_prev_tab_event,
-    is_quit_event, is_search_backspace_event, is_search_reset_event, is_shell_output_toggle_event,
+    is_delete_event, is_end_event, is_enter_dir_event, is_enter_event,
+    is_extend_selection_down_event, is_extend_selection_up_event, is_history_back_event,
+    is_history_forward_event, is_home_event, is_new_tab_event, is_next_tab_event,
+    is_page_down_event, is_page_up_event, is_parent_event, is_permanent_delete_event,
+    is_prev_tab_event, is_quit_event, is_search_backspace_event, is_search_next_event,
+    is_search_prev_event, is_search_reset_event, is_select_all_event, is_shell_output_toggle_event,
     is_slash_activate_event, is_slash_cancel_event, is_slash_complete_event,
-    is_slash_history_next_event, is_slash_history_prev_event, is_toggle_hidden_event, search_char,
-    slash_input_char,
+    is_slash_history_next_event, is_slash_history_prev_event, is_toggle_hidden_event,
+    is_toggle_ignored_event, is_toggle_mark_event, search_char, slash_input_char,
 };
 use layout::{split_main, split_panes};
+use list_panel::render_list_panel;
 use main_pane::{EntryListParams, entry_list_view_height, render_entry_list};
 use metadata_worker::MetadataWorker;
+use paste::parse_dropped_paths;
 use preview_pane::{PreviewPaneState, render_preview_pane};
 use preview_worker::PreviewWorker;
 use shell_output_view::render_shell_output_view;
@@ -63,7 +77,7 @@ pub fn run(mut app: App, opener: &dyn EntryOpener) -> AppResult<()> {
     let mut metadata_cache_dir: Option<std::path::PathBuf> = None;
     let mut last_git_dir: Option<std::path::PathBuf> = None;
     let mut git_display: Option<String> = None;
-    let mut last_preview_path: Option<std::path::PathBuf> = None;
+    let mut last_preview_key: Option<(Option<usize>, std::path::PathBuf)> = None;
     let mut preview_state = PreviewState::Idle;
     let mut preview_request_id: u64 = 0;
     let mut active_preview_id: Option<u64> = None;
@@ -74,9 +88,21 @@ pub fn run(mut app: App, opener: &dyn EntryOpener) -> AppResult<()> {
             theme_state.apply(event);
         }
         app.poll_shell_events();
+        app.poll_copy_events();
+        app.poll_results_events();
+        app.poll_tree_events();
+        app.poll_listing_events();
         app.poll_config_events();
         app.poll_session_events();
         app.flush_session_save();
+        app.poll_frecency_events();
+        app.flush_frecency_save();
+        app.poll_watch_events();
+        if app.take_fs_changed() {
+            metadata_snapshot.clear();
+            last_metadata_path = None;
+            last_preview_key = None;
+        }
         let current_path = app.selected_entry_path();
         if metadata_cache_dir.as_ref() != Some(&app.current_dir) {
             metadata_snapshot.clear();
@@ -185,8 +211,11 @@ pub fn run(mut app: App, opener: &dyn EntryOpener) -> AppResult<()> {
             };
         }
         if app.preview_visible() {
-            if current_path != last_preview_path {
-                if let Some(path) = current_path.clone() {
+            let preview_key = current_path
+                .clone()
+                .map(|path| (app.preview_focus_line(&path), path));
+            if preview_key != last_preview_key {
+                if let Some((focus_line, path)) = preview_key.clone() {
                     preview_request_id += 1;
                     let id = preview_request_id;
                     active_preview_id = Some(id);
@@ -195,25 +224,39 @@ pub fn run(mut app: App, opener: &dyn EntryOpener) -> AppResult<()> {
                         id,
                         path,
                         max_bytes: 1024 * 1024,
+                        focus_line,
                     });
                 } else {
                     preview_state = PreviewState::Idle;
                     active_preview_id = None;
                 }
-                last_preview_path = current_path.clone();
+                last_preview_key = preview_key;
             }
         } else {
-            last_preview_path = None;
+            last_preview_key = None;
             preview_state = PreviewState::Idle;
             active_preview_id = None;
         }
 
+        if let Some(path) = app.take_editor_request() {
+            guard.suspend()?;
+            let result = run_editor(&path);
+            guard.resume()?;
+            app.finish_bulk_rename_edit(result);
+        }
+
+        if let Some(text) = app.take_clipboard_request() {
+            let sequence = osc52_sequence(&text, std::env::var_os("TMUX").is_some());
+            let backend = guard.terminal_mut().backend_mut();
+            backend.write_all(sequence.as_bytes())?;
+            backend.flush()?;
+        }
+
         let current_list_height = {
             let size = guard.terminal_mut().size()?;
             let area = Rect::new(0, 0, size.width, size.height);
-            let show_command_bar = app.slash_input_active() || !app.search_text().is_empty();
-            let (_, main, _, _) = split_main(area, show_command_bar);
-            let preview_ratio = if app.preview_visible() || app.shell_output_active() {
+            let (_, main, _, _) = split_main(area, command_bar_visible(&app));
+            let preview_ratio = if side_panel_visible(&app) {
                 Some(app.preview_ratio_percent())
             } else {
                 None
@@ -234,13 +277,38 @@ pub fn run(mut app: App, opener: &dyn EntryOpener) -> AppResult<()> {
             )
         })?;
 
-        if crossterm_event::poll(Duration::from_millis(200))?
-            && let Event::Key(key) = crossterm_event::read()?
-        {
+        if crossterm_event::poll(Duration::from_millis(200))? {
+            let key = match crossterm_event::read()? {
+                Event::Key(key) => key,
+                Event::Paste(text) => {
+                    handle_paste_event(&mut app, &text);
+                    continue;
+                }
+                _ => continue,
+            };
             if is_quit_event(key) {
                 app.force_session_save();
+                app.force_frecency_save();
                 break;
             }
+            if app.conflict_active() {
+                if let Some(choice) = conflict_choice_event(key) {
+                    app.resolve_pending_conflict(choice);
+                } else if is_conflict_apply_all_event(key) {
+                    app.toggle_conflict_apply_to_all();
+                } else if is_search_reset_event(key) {
+                    app.cancel_conflicts();
+                }
+                continue;
+            }
+            if app.confirmation_prompt().is_some() {
+                if is_confirm_event(key) {
+                    app.confirm_pending();
+                } else {
+                    app.cancel_pending();
+                }
+                continue;
+            }
             if app.shell_output_active() {
                 if is_shell_output_toggle_event(key) || is_search_reset_event(key) {
                     app.close_shell_output();
@@ -311,14 +379,66 @@ pub fn run(mut app: App, opener: &dyn EntryOpener) -> AppResult<()> {
                 }
                 continue;
             }
+            if app.results_view_active() {
+                if is_search_reset_event(key) {
+                    app.cancel_or_close_results();
+                } else if is_cursor_up_event(key) {
+                    app.move_results_cursor_up();
+                } else if is_cursor_down_event(key) {
+                    app.move_results_cursor_down();
+                } else if is_enter_event(key) {
+                    app.open_selected_result()?;
+                }
+                continue;
+            }
+            if app.trash_view_active() {
+                if is_search_reset_event(key) {
+                    app.close_trash_view();
+                } else if is_cursor_up_event(key) {
+                    app.move_trash_cursor_up();
+                } else if is_cursor_down_event(key) {
+                    app.move_trash_cursor_down();
+                } else if is_enter_event(key) {
+                    app.restore_selected_trash_item();
+                }
+                continue;
+            }
             if is_shell_output_toggle_event(key) {
                 app.toggle_shell_output();
                 continue;
             }
+            if is_delete_event(key) {
+                app.trash_selected();
+                continue;
+            }
+            if is_toggle_mark_event(key) {
+                app.toggle_mark();
+                continue;
+            }
+            if is_extend_selection_up_event(key) {
+                app.extend_selection_up();
+                continue;
+            }
+            if is_extend_selection_down_event(key) {
+                app.extend_selection_down();
+                continue;
+            }
+            if is_select_all_event(key) {
+                app.select_all();
+                continue;
+            }
+            if is_permanent_delete_event(key) {
+                app.request_permanent_delete();
+                continue;
+            }
             if is_slash_activate_event(key) {
                 app.activate_slash_input();
                 continue;
             }
+            if is_bookmark_picker_event(key) {
+                app.open_bookmark_picker();
+                continue;
+            }
             if is_new_tab_event(key) {
                 app.new_tab()?;
                 continue;
@@ -352,17 +472,44 @@ pub fn run(mut app: App, opener: &dyn EntryOpener) -> AppResult<()> {
             if is_enter_event(key) {
                 app.open_selected(opener)?;
             }
+            if is_history_back_event(key) {
+                app.history_back()?;
+            }
+            if is_history_forward_event(key) {
+                app.history_forward()?;
+            }
             if is_enter_dir_event(key) {
-                app.enter_selected_dir()?;
+                if app.tree_enabled() {
+                    app.expand_selected();
+                } else {
+                    app.enter_selected_dir()?;
+                }
             }
             if is_parent_event(key) {
-                app.move_to_parent()?;
+                if app.tree_enabled() {
+                    app.collapse_selected()?;
+                } else {
+                    app.move_to_parent()?;
+                }
             }
             if is_toggle_hidden_event(key) {
                 app.toggle_hidden()?;
             }
+            if is_toggle_ignored_event(key) {
+                app.toggle_ignored()?;
+            }
             if is_search_reset_event(key) {
-                app.reset_search();
+                if app.search_text().is_empty() {
+                    app.clear_marks();
+                } else {
+                    app.reset_search();
+                }
+            }
+            if is_search_next_event(key) {
+                app.search_next();
+            }
+            if is_search_prev_event(key) {
+                app.search_prev();
             }
             if is_search_backspace_event(key) {
                 app.backspace_search_char();
@@ -386,20 +533,22 @@ fn draw(
     theme: &crate::core::ColorTheme,
 ) {
     let area = frame.area();
-    let show_command_bar = app.slash_input_active() || !app.search_text().is_empty();
-    let (top, main, bottom, slash) = split_main(area, show_command_bar);
+    let (top, main, bottom, slash) = split_main(area, command_bar_visible(app));
     render_top_bar(frame, top, app);
-    let preview_ratio = if app.preview_visible() || app.shell_output_active() {
+    let preview_ratio = if side_panel_visible(app) {
         Some(app.preview_ratio_percent())
     } else {
         None
     };
     let (left, right, preview) = split_panes(main, preview_ratio);
+    let no_marks = BTreeSet::new();
     let parent_params = EntryListParams {
         entries: &app.parent_entries,
         cursor: None,
+        marked: &no_marks,
         title: "parent",
         search_text: "",
+        search_mode: app.search_mode(),
         theme,
         active: false,
     };
@@ -408,8 +557,10 @@ fn draw(
     let current_params = EntryListParams {
         entries: &app.entries,
         cursor: app.cursor,
+        marked: app.marked_names().unwrap_or(&no_marks),
         title: "current",
         search_text: app.search_text(),
+        search_mode: app.search_mode(),
         theme,
         active: true,
     };
@@ -422,12 +573,30 @@ fn draw(
                 .shell_output_text(height, width)
                 .unwrap_or_else(|| "shell output: empty".to_string());
             render_shell_output_view(frame, preview_area, &text);
+        } else if let Some(prompt) = app.conflict_prompt() {
+            let lines = format_conflict_lines(&prompt);
+            render_list_panel(frame, preview_area, "conflict", &lines, None, theme);
+        } else if let Some(lines) = app.bulk_rename_preview() {
+            render_list_panel(frame, preview_area, "bulkrename", &lines, None, theme);
+        } else if let Some(lines) = app.drop_copy_preview() {
+            render_list_panel(frame, preview_area, "paste", &lines, None, theme);
+        } else if let Some((title, lines, cursor)) = app.results_view_lines() {
+            render_list_panel(frame, preview_area, &title, &lines, cursor, theme);
+        } else if let Some((items, cursor)) = app.trash_view_items() {
+            let lines = items
+                .iter()
+                .map(|item| format!("{}  {}", item.deleted_at, item.original_path.display()))
+                .collect::<Vec<_>>();
+            let cursor = (!lines.is_empty()).then_some(cursor);
+            render_list_panel(frame, preview_area, "trash", &lines, cursor, theme);
         } else {
             let pane_state = match preview_state {
                 PreviewState::Idle => PreviewPaneState::Empty,
                 PreviewState::Loading => PreviewPaneState::Loading,
                 PreviewState::Ready(ready) => PreviewPaneState::Ready {
                     lines: &ready.lines,
+                    first_line: ready.first_line,
+                    focus_line: ready.focus_line,
                     reason: ready.reason.clone(),
                     truncated: ready.truncated,
                     path: ready.path.as_path(),
@@ -440,6 +609,7 @@ fn draw(
         }
     }
     let notice = app.user_notice();
+    let progress = app.copy_progress().map(format_copy_progress);
     let feedback = app.slash_feedback();
     let bottom_bar = crate::ui::bottom_bar::BottomBarState::new(
         metadata_display,
@@ -447,10 +617,15 @@ fn draw(
         git_display,
         notice.as_ref(),
         feedback,
-    );
+    )
+    .with_progress(progress.as_deref());
     render_bottom_bar(frame, bottom, bottom_bar, theme);
     if let Some(slash_area) = slash {
-        if app.slash_input_active() {
+        if let Some(prompt) = app.conflict_prompt() {
+            render_confirm_bar(frame, slash_area, &format_conflict_choices(&prompt), theme);
+        } else if let Some(prompt) = app.confirmation_prompt() {
+            render_confirm_bar(frame, slash_area, &prompt, theme);
+        } else if app.slash_input_active() {
             let candidates = app.slash_candidates();
             let hint = app.slash_hint();
             render_slash_bar(
@@ -462,11 +637,47 @@ fn draw(
                 theme,
             );
         } else if !app.search_text().is_empty() {
-            render_search_bar(frame, slash_area, app.search_text(), theme);
+            render_search_bar(
+                frame,
+                slash_area,
+                app.search_text(),
+                app.search_mode(),
+                theme,
+            );
         }
     }
 }
 
+fn handle_paste_event(app: &mut App, text: &str) {
+    if app.slash_input_active() {
+        text.chars()
+            .filter(|ch| !ch.is_control())
+            .for_each(|ch| app.append_slash_char(ch));
+        return;
+    }
+    if app.confirmation_prompt().is_some() || app.conflict_active() {
+        return;
+    }
+    app.request_drop_copy(parse_dropped_paths(text));
+}
+
+fn command_bar_visible(app: &App) -> bool {
+    app.slash_input_active()
+        || !app.search_text().is_empty()
+        || app.confirmation_prompt().is_some()
+        || app.conflict_active()
+}
+
+fn side_panel_visible(app: &App) -> bool {
+    app.preview_visible()
+        || app.shell_output_active()
+        || app.results_view_active()
+        || app.trash_view_active()
+        || app.bulk_rename_preview().is_some()
+        || app.drop_copy_preview().is_some()
+        || app.conflict_active()
+}
+
 #[derive(Debug, Clone)]
 enum PreviewState {
     Idle,
@@ -522,7 +733,7 @@ impl TerminalGuard {
     fn new() -> AppResult<Self> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        if let Err(error) = execute!(stdout, EnterAlternateScreen, Hide) {
+        if let Err(error) = execute!(stdout, EnterAlternateScreen, EnableBracketedPaste, Hide) {
             let _ = disable_raw_mode();
             return Err(error.into());
         }
@@ -540,6 +751,23 @@ impl TerminalGuard {
     fn terminal_mut(&mut self) -> &mut Terminal<CrosstermBackend<Stdout>> {
         &mut self.terminal
     }
+
+    fn suspend(&mut self) -> AppResult<()> {
+        restore_terminal()?;
+        Ok(())
+    }
+
+    fn resume(&mut self) -> AppResult<()> {
+        enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            EnterAlternateScreen,
+            EnableBracketedPaste,
+            Hide
+        )?;
+        self.terminal.clear()?;
+        Ok(())
+    }
 }
 
 impl Drop for TerminalGuard {
@@ -551,7 +779,7 @@ impl Drop for TerminalGuard {
 fn restore_terminal() -> std::io::Result<()> {
     disable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, LeaveAlternateScreen, Show)?;
+    execute!(stdout, DisableBracketedPaste, LeaveAlternateScreen, Show)?;
     Ok(())
 }
 
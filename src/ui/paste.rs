@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+const ESCAPABLE: &str = " \t()[]{}'\"\\&;!$#*?<>|~`";
+
+pub fn parse_dropped_paths(text: &str) -> Vec<PathBuf> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+    let whole = PathBuf::from(trimmed);
+    if trimmed.contains(char::is_whitespace) && whole.symlink_metadata().is_ok() {
+        return vec![whole];
+    }
+    split_tokens(trimmed)
+        .into_iter()
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn split_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' | '\'' => {
+                in_token = true;
+                for quoted in chars.by_ref() {
+                    if quoted == ch {
+                        break;
+                    }
+                    current.push(quoted);
+                }
+            }
+            '\\' if !cfg!(windows)
+                && chars.peek().is_some_and(|next| ESCAPABLE.contains(*next)) =>
+            {
+                in_token = true;
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ch if ch.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            ch => {
+                in_token = true;
+                current.push(ch);
+            }
+        }
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(windows))]
+    fn parse_dropped_paths_unescapes_spaces_and_parens() {
+        assert_eq!(
+            parse_dropped_paths("/Users/a/My\\ File\\ (1).txt "),
+            vec![PathBuf::from("/Users/a/My File (1).txt")]
+        );
+    }
+
+    #[test]
+    fn parse_dropped_paths_strips_quotes_and_keeps_windows_separators() {
+        assert_eq!(
+            parse_dropped_paths("\"C:\\Users\\a\\My File.txt\" C:\\Users\\a\\MyFile.txt"),
+            vec![
+                PathBuf::from("C:\\Users\\a\\My File.txt"),
+                PathBuf::from("C:\\Users\\a\\MyFile.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_dropped_paths_splits_on_newlines_and_spaces() {
+        assert_eq!(
+            parse_dropped_paths("/tmp/a.txt\n/tmp/b.txt '/tmp/c d.txt'\r\n"),
+            vec![
+                PathBuf::from("/tmp/a.txt"),
+                PathBuf::from("/tmp/b.txt"),
+                PathBuf::from("/tmp/c d.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_dropped_paths_keeps_existing_path_with_spaces_whole() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("My File.txt");
+        std::fs::write(&path, "x").unwrap();
+
+        assert_eq!(parse_dropped_paths(&path.display().to_string()), vec![path]);
+    }
+}
@@ -19,6 +19,8 @@ pub enum PreviewPaneState<'a> {
     Loading,
     Ready {
         lines: &'a [String],
+        first_line: usize,
+        focus_line: Option<usize>,
         reason: Option<String>,
         truncated: bool,
         path: &'a Path,
@@ -29,7 +31,14 @@ pub enum PreviewPaneState<'a> {
 }
 
 pub fn render_preview_pane(frame: &mut Frame<'_>, area: Rect, state: PreviewPaneState<'_>) {
-    let block = Block::default().borders(Borders::ALL).title("preview");
+    let title = match &state {
+        PreviewPaneState::Ready {
+            focus_line: Some(line),
+            ..
+        } => format!("preview :{line}"),
+        _ => "preview".to_string(),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
     let inner = block.inner(area);
     frame.render_widget(block, area);
     if inner.height == 0 || inner.width == 0 {
@@ -45,6 +54,8 @@ fn build_preview_text(state: PreviewPaneState<'_>) -> Text<'static> {
         PreviewPaneState::Loading => Text::from("preview: loading..."),
         PreviewPaneState::Ready {
             lines,
+            first_line,
+            focus_line,
             reason,
             truncated,
             path,
@@ -54,6 +65,13 @@ fn build_preview_text(state: PreviewPaneState<'_>) -> Text<'static> {
             } else {
                 plain_preview_lines(lines)
             };
+            if let Some(line) = focus_line
+                .and_then(|line| line.checked_sub(first_line))
+                .and_then(|index| text.lines.get_mut(index))
+            {
+                *line = std::mem::take(line)
+                    .patch_style(Style::default().add_modifier(Modifier::REVERSED));
+            }
             if let Some(reason) = reason {
                 append_plain_line(&mut text, reason);
             }
@@ -148,6 +166,8 @@ mod tests {
         let lines = vec!["fn main() {".to_string(), "}".to_string()];
         let text = build_preview_text(PreviewPaneState::Ready {
             lines: &lines,
+            first_line: 1,
+            focus_line: None,
             reason: None,
             truncated: false,
             path: Path::new("main.rs"),
@@ -156,11 +176,39 @@ mod tests {
         assert!(text.lines[0].spans.len() > 1);
     }
 
+    #[test]
+    fn build_preview_text_marks_focus_line() {
+        let lines = vec!["one".to_string(), "two".to_string()];
+        let text = build_preview_text(PreviewPaneState::Ready {
+            lines: &lines,
+            first_line: 10,
+            focus_line: Some(11),
+            reason: Some("plain".to_string()),
+            truncated: false,
+            path: Path::new("note.txt"),
+        });
+
+        assert!(
+            !text.lines[0]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+        assert!(
+            text.lines[1]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+    }
+
     #[test]
     fn build_preview_text_falls_back_when_reason_is_present() {
         let lines = vec!["fn main() {".to_string()];
         let text = build_preview_text(PreviewPaneState::Ready {
             lines: &lines,
+            first_line: 1,
+            focus_line: None,
             reason: Some("non-utf8".to_string()),
             truncated: false,
             path: Path::new("main.rs"),
@@ -174,6 +222,8 @@ mod tests {
         let lines = vec!["plain text".to_string()];
         let text = build_preview_text(PreviewPaneState::Ready {
             lines: &lines,
+            first_line: 1,
+            focus_line: None,
             reason: None,
             truncated: false,
             path: Path::new("note.unknownext"),
@@ -20,7 +20,7 @@ impl PreviewWorker {
             request_rx
                 .into_iter()
                 .flat_map(|request| {
-                    let result = load_preview(&request.path, request.max_bytes);
+                    let result = load_preview(&request.path, request.max_bytes, request.focus_line);
                     preview_events(&request, result).into_iter()
                 })
                 .for_each(|event| {
@@ -48,6 +48,8 @@ fn build_ready(request: &PreviewRequest, content: PreviewContent) -> PreviewRead
         id: request.id,
         path: request.path.clone(),
         lines: content.lines,
+        first_line: content.first_line,
+        focus_line: request.focus_line,
         truncated: content.truncated,
         reason: content.reason,
         kind_flags: content.kind_flags,
@@ -84,9 +86,11 @@ mod tests {
             id: 1,
             path: PathBuf::from("note.txt"),
             max_bytes: 10,
+            focus_line: None,
         };
         let content = PreviewContent {
             lines: vec!["line".to_string()],
+            first_line: 1,
             truncated: false,
             reason: None,
             kind_flags: vec![],
@@ -114,6 +118,7 @@ mod tests {
             id: 2,
             path: PathBuf::from("note.txt"),
             max_bytes: 10,
+            focus_line: None,
         };
 
         let events = preview_events(&request, Err(PreviewError::BinaryFile));
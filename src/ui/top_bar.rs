@@ -8,13 +8,30 @@ pub fn render_top_bar(frame: &mut Frame<'_>, area: Rect, app: &App) {
         .selected_entry()
         .map(|entry| entry.name.as_str())
         .unwrap_or("");
-    let bar = Paragraph::new(format!(
+    let mut text = format!(
         "tab {}/{} | {} | {}",
         app.active_tab_number(),
         app.tab_count(),
         path,
         active
-    ));
+    );
+    if let Some(filter) = app.filter_label() {
+        text.push_str(&format!(" | filter: {filter}"));
+    }
+    if let Some(loaded) = app.listing_progress() {
+        text.push_str(&format!(" | loading {loaded}…"));
+    }
+    if app.hide_ignored {
+        text.push_str(" | gitignore");
+    }
+    if app.tree_enabled() {
+        text.push_str(" | tree");
+    }
+    let marked = app.mark_count();
+    if marked > 0 {
+        text.push_str(&format!(" | {marked} marked"));
+    }
+    let bar = Paragraph::new(text);
     frame.render_widget(bar, area);
 }
 
@@ -33,10 +50,7 @@ mod tests {
         let mut terminal = Terminal::new(backend).unwrap();
         let app = App::new(
             PathBuf::from("/tmp"),
-            vec![Entry {
-                name: "a.txt".to_string(),
-                is_dir: false,
-            }],
+            vec![Entry::file("a.txt")],
             Vec::new(),
             Some(0),
             false,
@@ -61,16 +75,7 @@ mod tests {
         let mut terminal = Terminal::new(backend).unwrap();
         let app = App::new(
             PathBuf::from("/tmp"),
-            vec![
-                Entry {
-                    name: "a.txt".to_string(),
-                    is_dir: false,
-                },
-                Entry {
-                    name: "b.txt".to_string(),
-                    is_dir: false,
-                },
-            ],
+            vec![Entry::file("a.txt"), Entry::file("b.txt")],
             Vec::new(),
             Some(1),
             false,